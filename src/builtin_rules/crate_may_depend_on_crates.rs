@@ -0,0 +1,117 @@
+use crate::builtin_rules::utils::external_crate_of;
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use crate::styling::Styling;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// Restricts the crates of the project, like workspace siblings, that the files of `crate_name`
+/// may depend on to `allowed_crates`. Third-party crates are left to the other rules.
+pub struct CrateMayDependOnCrates {
+    pub crate_name: String,
+    pub allowed_crates: Vec<String>,
+}
+
+impl Display for CrateMayDependOnCrates {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl ProjectRule for CrateMayDependOnCrates {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "Crate {} may depend on crates {}",
+            styling.subject(&self.crate_name),
+            styling.subject("[".to_string() + &self.allowed_crates.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "crate_may_depend_on_crates"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.crate_name.clone()),
+            allowed: self.allowed_crates.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let project_crates: BTreeSet<&str> = project
+            .files
+            .iter()
+            .map(|file| file.crate_name.as_str())
+            .collect();
+
+        let mut forbidden_edges = Vec::new();
+        for file in project
+            .files
+            .iter()
+            .filter(|file| file.crate_name == self.crate_name)
+        {
+            for dependency in &file.dependencies {
+                let Some(dependency_crate) = external_crate_of(dependency, file) else {
+                    continue;
+                };
+                if project_crates.contains(dependency_crate)
+                    && !self.allowed_crates.iter().any(|c| c == dependency_crate)
+                {
+                    forbidden_edges.push(format!(
+                        "{} -> {} in file://{}",
+                        file.crate_name,
+                        dependency_crate,
+                        file.location_of(dependency)
+                    ));
+                }
+            }
+        }
+
+        if !forbidden_edges.is_empty() {
+            return Err(format!(
+                "Forbidden crate dependencies:\n{}",
+                forbidden_edges.join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undeclared_workspace_crate_dependency() {
+        let project = RustProject::from_directory("examples/workspace_project").unwrap();
+        let rule = CrateMayDependOnCrates {
+            crate_name: "application".to_string(),
+            allowed_crates: vec!["conversion".to_string()],
+        };
+
+        assert_eq!(
+            rule.apply(&project),
+            Err("Forbidden crate dependencies:\n\
+                 application -> policy_management in file://examples/workspace_project/application/src/lib.rs:2"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_declared_workspace_crate_dependencies() {
+        let project = RustProject::from_directory("examples/workspace_project").unwrap();
+        let rule = CrateMayDependOnCrates {
+            crate_name: "application".to_string(),
+            allowed_crates: vec!["conversion".to_string(), "policy_management".to_string()],
+        };
+
+        assert_eq!(rule.apply(&project), Ok(()));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::rust_project::RustProject;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use syn::visit::{self, Visit};
+use syn::{Attribute, Expr, Item, Lit, Meta};
+
+/// Reports intra-doc links (`[crate::domain::User]`, `[User](crate::domain::User)`) pointing
+/// to modules or items that don't exist in the scanned files.
+///
+/// Only links rooted at `crate::` or at the crate name are checked. Items re-exported with
+/// `pub use` resolve where they are re-exported too, except through glob re-exports. Anything
+/// below a known item (e.g. `User::new`) is accepted, since members aren't tracked.
+#[derive(Debug, Default)]
+pub struct DocLinksMustResolve;
+
+impl Display for DocLinksMustResolve {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Intra-doc links must point to existing modules and items"
+        )
+    }
+}
+
+impl ProjectRule for DocLinksMustResolve {
+    fn name(&self) -> &'static str {
+        "doc_links_must_resolve"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription::of_kind(self.name())
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let mut modules = BTreeSet::new();
+        let mut items = BTreeSet::new();
+        for file in &project.files {
+            collect_items(
+                &file.logical_path,
+                &file.ast.items,
+                &mut modules,
+                &mut items,
+            );
+            modules.insert(file.logical_path.clone());
+            items.extend(file.reexport_origins().keys().cloned());
+        }
+
+        let mut broken = Vec::new();
+        for file in &project.files {
+            let mut visitor = DocsVisitor { docs: vec![] };
+            visitor.visit_file(&file.ast);
+
+            for link in visitor.docs.iter().flat_map(|doc| links(doc)) {
+                let Some(target) = absolute_target(&link, file) else {
+                    continue;
+                };
+
+                if !resolves(&target, &modules, &items) {
+                    broken.push(format!("[{}] in file://{}", link, file.path));
+                }
+            }
+        }
+
+        if !broken.is_empty() {
+            return Err(format!("Broken intra-doc links:\n{}", broken.join("\n")));
+        }
+
+        Ok(())
+    }
+}
+
+fn collect_items(
+    module: &str,
+    items: &[Item],
+    modules: &mut BTreeSet<String>,
+    found: &mut BTreeSet<String>,
+) {
+    for item in items {
+        let ident = match item {
+            Item::Const(item) => &item.ident,
+            Item::Enum(item) => &item.ident,
+            Item::Fn(item) => &item.sig.ident,
+            Item::Macro(item) => match &item.ident {
+                Some(ident) => ident,
+                None => continue,
+            },
+            Item::Mod(item) => {
+                let path = format!("{}::{}", module, item.ident);
+                if let Some((_, content)) = &item.content {
+                    collect_items(&path, content, modules, found);
+                }
+                modules.insert(path);
+                continue;
+            }
+            Item::Static(item) => &item.ident,
+            Item::Struct(item) => &item.ident,
+            Item::Trait(item) => &item.ident,
+            Item::TraitAlias(item) => &item.ident,
+            Item::Type(item) => &item.ident,
+            Item::Union(item) => &item.ident,
+            _ => continue,
+        };
+        found.insert(format!("{}::{}", module, ident));
+    }
+}
+
+/// The link targets in a doc comment: `[path]`, `` [`path`] `` and `[text](path)`.
+fn links(doc: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut rest = doc;
+
+    while let Some(start) = rest.find('[') {
+        let Some(end) = rest[start..].find(']').map(|end| start + end) else {
+            break;
+        };
+        let text = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        let target = match rest
+            .strip_prefix('(')
+            .and_then(|r| r.find(')').map(|e| &r[..e]))
+        {
+            Some(target) => target,
+            None => text,
+        };
+        links.push(target.trim_matches('`').to_string());
+    }
+
+    links
+}
+
+/// The full path a link points to, if it is an intra-doc link rooted in the file's crate.
+fn absolute_target(link: &str, file: &RustFile) -> Option<String> {
+    let link = link
+        .split_once('@')
+        .map_or(link, |(_, path)| path)
+        .trim_end_matches("()")
+        .trim_end_matches('!');
+
+    if link.contains(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':')) {
+        return None;
+    }
+
+    let (root, rest) = link.split_once("::")?;
+    if root == "crate" || root == file.crate_name {
+        Some(format!("{}::{}", file.crate_name, rest))
+    } else {
+        None
+    }
+}
+
+fn resolves(target: &str, modules: &BTreeSet<String>, items: &BTreeSet<String>) -> bool {
+    let segments: Vec<&str> = target.split("::").collect();
+
+    (1..=segments.len()).rev().any(|len| {
+        let prefix = segments[..len].join("::");
+        items.contains(&prefix) || (len == segments.len() && modules.contains(&prefix))
+    })
+}
+
+struct DocsVisitor {
+    docs: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for DocsVisitor {
+    fn visit_attribute(&mut self, attr: &'ast Attribute) {
+        if let Meta::NameValue(meta) = &attr.meta {
+            if meta.path.is_ident("doc") {
+                if let Expr::Lit(expr) = &meta.value {
+                    if let Lit::Str(doc) = &expr.lit {
+                        self.docs.push(doc.value());
+                    }
+                }
+            }
+        }
+        visit::visit_attribute(self, attr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(api: &str) -> RustProject {
+        RustProject {
+            files: vec![
+                RustFile::from_content("src/lib.rs", "my_crate", "pub mod domain;"),
+                RustFile::from_content(
+                    "src/domain/mod.rs",
+                    "my_crate::domain",
+                    "pub mod user; pub struct Error;",
+                ),
+                RustFile::from_content(
+                    "src/domain/user.rs",
+                    "my_crate::domain::user",
+                    "pub struct User; pub mod events { pub struct Created; }",
+                ),
+                RustFile::from_content("src/api.rs", "my_crate::api", api),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_links_to_existing_modules_and_items_resolve() {
+        let project = project(
+            r#"
+            //! Exposes [`crate::domain`] through HTTP.
+
+            /// Returns a [crate::domain::user::User], see [`User::new`](crate::domain::user::User::new).
+            /// Fails with [my_crate::domain::Error], emits [struct@crate::domain::user::events::Created].
+            /// Unrelated brackets: [1], [`Vec`], [see here](https://example.com).
+            pub fn get() {}
+            "#,
+        );
+
+        assert!(DocLinksMustResolve.apply(&project).is_ok());
+    }
+
+    #[test]
+    fn test_links_to_reexported_items_resolve() {
+        let project = project(
+            r#"
+            pub use crate::domain::user::User;
+            pub use crate::domain::Error as DomainError;
+
+            /// Returns a [`crate::api::User`] or fails with [crate::api::DomainError].
+            pub fn get() {}
+            "#,
+        );
+
+        assert!(DocLinksMustResolve.apply(&project).is_ok());
+    }
+
+    #[test]
+    fn test_broken_links_are_reported() {
+        let project = project(
+            r#"
+            /// Returns a [`crate::domain::Customer`] or a [crate::billing::Invoice].
+            pub fn get() {}
+            "#,
+        );
+
+        assert_eq!(
+            DocLinksMustResolve.apply(&project),
+            Err("Broken intra-doc links:\n\
+                 [crate::domain::Customer] in file://src/api.rs\n\
+                 [crate::billing::Invoice] in file://src/api.rs"
+                .to_string())
+        );
+    }
+}
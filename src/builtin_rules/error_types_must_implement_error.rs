@@ -0,0 +1,210 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::resolve_path;
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::rust_project::RustProject;
+use crate::styling::Styling;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Attribute, Ident, ItemEnum, ItemImpl, ItemStruct, Path, Token, Type, Visibility};
+
+/// Requires the `pub` structs and enums of the subject named `*Error` to implement
+/// `std::error::Error`, either with an `impl` anywhere in the project or with
+/// `#[derive(Error)]` (e.g. `thiserror`).
+///
+/// Impls are matched to types by name, and the trait is resolved through the `use` declarations
+/// of the file holding the impl, so `impl Error for X` counts after `use std::error::Error`.
+pub struct ErrorTypesMustImplementError {
+    pub subject: Subject,
+}
+
+impl Display for ErrorTypesMustImplementError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl ProjectRule for ErrorTypesMustImplementError {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} error types must implement std::error::Error",
+            styling.subject(&self.subject)
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "error_types_must_implement_error"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let mut error_types = Vec::new();
+        let mut implemented = HashSet::new();
+
+        for file in &project.files {
+            let mut visitor = ErrorTypesVisitor {
+                file,
+                error_types: vec![],
+                implemented: HashSet::new(),
+            };
+            visitor.visit_file(&file.ast);
+
+            if self.subject.matches(&file.logical_path) {
+                error_types.extend(visitor.error_types);
+            }
+            implemented.extend(visitor.implemented);
+        }
+
+        let missing: Vec<String> = error_types
+            .into_iter()
+            .filter(|(name, _)| !implemented.contains(name))
+            .map(|(name, location)| format!("{} in file://{}", name, location))
+            .collect();
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        Err(format!(
+            "Error types not implementing std::error::Error:\n{}",
+            missing.join("\n")
+        ))
+    }
+}
+
+/// Collects the `pub` error-named types of a file, with their location, and the names of the
+/// types it implements `std::error::Error` for.
+struct ErrorTypesVisitor<'a> {
+    file: &'a RustFile,
+    error_types: Vec<(String, String)>,
+    implemented: HashSet<String>,
+}
+
+impl ErrorTypesVisitor<'_> {
+    fn is_std_error(&self, path: &Path) -> bool {
+        resolve_path(self.file, path)
+            .is_some_and(|path| path == "std::error::Error" || path == "core::error::Error")
+    }
+
+    fn collect_type(&mut self, vis: &Visibility, ident: &Ident, attrs: &[Attribute]) {
+        if !matches!(vis, Visibility::Public(_)) || !ident.to_string().ends_with("Error") {
+            return;
+        }
+
+        if derives_error(attrs) {
+            self.implemented.insert(ident.to_string());
+        }
+        self.error_types.push((
+            ident.to_string(),
+            format!("{}:{}", self.file.path, ident.span().start().line),
+        ));
+    }
+}
+
+/// Whether `attrs` hold a `#[derive(..)]` listing a macro named `Error`.
+fn derives_error(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|path| path.segments.last().is_some_and(|s| s.ident == "Error"))
+}
+
+impl<'ast> Visit<'ast> for ErrorTypesVisitor<'_> {
+    fn visit_item_struct(&mut self, node: &'ast ItemStruct) {
+        self.collect_type(&node.vis, &node.ident, &node.attrs);
+        visit::visit_item_struct(self, node);
+    }
+
+    fn visit_item_enum(&mut self, node: &'ast ItemEnum) {
+        self.collect_type(&node.vis, &node.ident, &node.attrs);
+        visit::visit_item_enum(self, node);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        if let (Some((_, trait_path, _)), Type::Path(self_type)) = (&node.trait_, &*node.self_ty) {
+            if self.is_std_error(trait_path) {
+                if let Some(segment) = self_type.path.segments.last() {
+                    self.implemented.insert(segment.ident.to_string());
+                }
+            }
+        }
+        visit::visit_item_impl(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> ErrorTypesMustImplementError {
+        ErrorTypesMustImplementError {
+            subject: "my_crate::domain".into(),
+        }
+    }
+
+    #[test]
+    fn test_error_type_without_impl_is_reported() {
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/domain.rs",
+                    "my_crate::domain",
+                    "pub enum OrderError { NotFound }\n\
+                     pub struct PaymentError;\n\
+                     pub struct ShippingError;\n\
+                     struct InternalError;\n",
+                ),
+                RustFile::from_content(
+                    "src/errors.rs",
+                    "my_crate::errors",
+                    "use std::error::Error;\n\
+                     use crate::domain::OrderError;\n\
+                     impl Error for OrderError {}\n\
+                     impl std::error::Error for crate::domain::PaymentError {}\n",
+                ),
+            ],
+        };
+
+        assert_eq!(
+            rule().apply(&project),
+            Err("Error types not implementing std::error::Error:\n\
+                 ShippingError in file://src/domain.rs:3"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_derived_error_and_local_error_traits() {
+        let project = RustProject {
+            files: vec![RustFile::from_content(
+                "src/domain.rs",
+                "my_crate::domain",
+                "#[derive(Debug, thiserror::Error)]\n\
+                 pub enum OrderError { NotFound }\n\
+                 pub struct PaymentError;\n\
+                 trait Error {}\n\
+                 impl Error for PaymentError {}\n",
+            )],
+        };
+
+        assert_eq!(
+            rule().apply(&project),
+            Err("Error types not implementing std::error::Error:\n\
+                 PaymentError in file://src/domain.rs:3"
+                .to_string())
+        );
+    }
+}
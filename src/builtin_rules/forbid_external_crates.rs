@@ -0,0 +1,142 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::external_crate_of;
+use crate::rule::{LocatedViolation, Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// Forbids the subject from using third-party crates other than `allowed_crates`.
+/// Crates of the std family and the subject's own crate are always allowed.
+#[derive(Debug)]
+pub struct ForbidExternalCratesRule {
+    pub subject: Subject,
+    pub allowed_crates: Vec<String>,
+}
+
+impl Display for ForbidExternalCratesRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for ForbidExternalCratesRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} may use only the external crates {}",
+            styling.subject(&self.subject),
+            styling.subject("[".to_string() + &self.allowed_crates.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "forbid_external_crates"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            allowed: self.allowed_crates.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        self.apply_located(file).map_err(|(message, _)| message)
+    }
+
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        let mut forbidden_crates: Vec<&str> = Vec::new();
+        let mut first_dependency = None;
+
+        for dependency in &file.dependencies {
+            let Some(external_crate) = external_crate_of(dependency, file) else {
+                continue;
+            };
+
+            if !self.allowed_crates.iter().any(|c| c == external_crate)
+                && !forbidden_crates.contains(&external_crate)
+            {
+                forbidden_crates.push(external_crate);
+                first_dependency.get_or_insert(dependency);
+            }
+        }
+
+        match first_dependency {
+            None => Ok(()),
+            Some(dependency) => Err((
+                format!(
+                    "Forbidden external crates [{}] used by {} in file://{}",
+                    forbidden_crates.join(", "),
+                    &file.logical_path,
+                    file.location_of(dependency)
+                ),
+                file.line_of(dependency),
+            )),
+        }
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_crates_outside_the_allowlist_are_forbidden() {
+        let rule = ForbidExternalCratesRule {
+            subject: "my_crate::api".into(),
+            allowed_crates: vec!["serde".to_string(), "tokio".to_string()],
+        };
+
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            r#"
+            use std::collections::HashMap;
+            use crate::domain::User;
+            use serde::Serialize;
+            use rand::Rng;
+            use tokio::sync::Mutex;
+
+            fn handle<T: Iterator>(client: reqwest::Client, items: T) -> Option<T::Item> {
+                let _ = rand::random::<u8>();
+                items.next()
+            }
+            "#,
+        );
+
+        let expected = "Forbidden external crates [rand, reqwest] used by my_crate::api in file://src/api.rs:5".to_string();
+        assert_eq!(rule.apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_local_modules_are_not_external_crates() {
+        let rule = ForbidExternalCratesRule {
+            subject: "my_crate::api".into(),
+            allowed_crates: vec![],
+        };
+
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            r#"
+            mod model;
+            use model::Order;
+            use crate::domain::billing;
+
+            fn handle(order: Order) -> billing::Invoice {
+                billing::invoice(model::total(&order))
+            }
+            "#,
+        );
+
+        assert_eq!(rule.apply(&file), Ok(()));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::builtin_rules::utils::IsChild;
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::rust_project::RustProject;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Forbids library code from depending on the modules of the crate's binaries, examples, tests
+/// and benches, which the library can't see once compiled.
+///
+/// Targets are recognized by Cargo's conventional layout: `src/main.rs`, `src/bin/`, `examples/`,
+/// `tests/` and `benches/`. Binary targets depending on the library are fine.
+pub struct LibraryMustNotDependOnBinaries;
+
+/// Whether `file` belongs to a target other than the library.
+fn is_binary_target(file: &RustFile) -> bool {
+    let components: Vec<&str> = Path::new(&file.path)
+        .components()
+        .filter_map(|component| component.as_os_str().to_str())
+        .collect();
+    let after_src = |directory: &str| {
+        components
+            .windows(2)
+            .any(|pair| pair[0] == "src" && pair[1] == directory)
+    };
+
+    // `examples/` next to `src/`, not a library module named `examples`
+    let target_directory = file
+        .logical_path
+        .split("::")
+        .nth(1)
+        .filter(|segment| ["examples", "tests", "benches"].contains(segment));
+
+    after_src("bin")
+        || after_src("main.rs")
+        || target_directory
+            .is_some_and(|directory| components.contains(&directory) && !after_src(directory))
+}
+
+impl Display for LibraryMustNotDependOnBinaries {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Library code must not depend on binaries, examples, tests or benches"
+        )
+    }
+}
+
+impl ProjectRule for LibraryMustNotDependOnBinaries {
+    fn name(&self) -> &'static str {
+        "library_must_not_depend_on_binaries"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription::of_kind(self.name())
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let (binaries, library): (Vec<&RustFile>, Vec<&RustFile>) = project
+            .files
+            .iter()
+            .partition(|file| is_binary_target(file));
+        // `src/main.rs` is the crate root, everything would be its child
+        let binary_modules: Vec<&str> = binaries
+            .iter()
+            .filter(|file| file.logical_path != file.crate_name)
+            .map(|file| file.logical_path.as_str())
+            .collect();
+
+        let mut edges = Vec::new();
+        for file in library {
+            for dependency in &file.dependencies {
+                if binary_modules
+                    .iter()
+                    .any(|module| dependency.is_child_of(module))
+                {
+                    edges.push(format!(
+                        "{} -> {} in file://{}",
+                        file.logical_path,
+                        dependency,
+                        file.location_of(dependency)
+                    ));
+                }
+            }
+        }
+
+        if !edges.is_empty() {
+            return Err(format!(
+                "Library code depending on binary targets:\n{}",
+                edges.join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(orders: &str) -> RustProject {
+        RustProject {
+            files: vec![
+                RustFile::from_content("src/lib.rs", "my_crate", "pub mod orders;\n"),
+                RustFile::from_content("src/orders.rs", "my_crate::orders", orders),
+                RustFile::from_content(
+                    "src/main.rs",
+                    "my_crate",
+                    "use my_crate::orders::Order;\nfn main() {}\n",
+                ),
+                RustFile::from_content(
+                    "src/bin/import.rs",
+                    "my_crate::bin::import",
+                    "use my_crate::orders::Order;\npub struct Options;\nfn main() {}\n",
+                ),
+                RustFile::from_content(
+                    "examples/demo.rs",
+                    "my_crate::examples::demo",
+                    "pub fn sample() {}\nfn main() {}\n",
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_binaries_may_depend_on_the_library() {
+        let project = project("pub struct Order;\n");
+
+        assert_eq!(LibraryMustNotDependOnBinaries.apply(&project), Ok(()));
+    }
+
+    #[test]
+    fn test_library_depending_on_a_binary_is_reported() {
+        let project = project(
+            "pub struct Order;\n\nuse crate::bin::import::Options;\nuse crate::examples::demo::sample;\n",
+        );
+
+        assert_eq!(
+            LibraryMustNotDependOnBinaries.apply(&project),
+            Err("Library code depending on binary targets:\n\
+                 my_crate::orders -> my_crate::bin::import::Options in file://src/orders.rs:3\n\
+                 my_crate::orders -> my_crate::examples::demo::sample in file://src/orders.rs:4"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_library_modules_named_like_targets_are_library_code() {
+        let examples = RustFile::from_content("src/examples.rs", "my_crate::examples", "");
+        let test = RustFile::from_content("tests/orders.rs", "my_crate::tests::orders", "");
+
+        assert!(!is_binary_target(&examples));
+        assert!(is_binary_target(&test));
+    }
+}
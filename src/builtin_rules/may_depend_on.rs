@@ -1,81 +1,163 @@
-use crate::builtin_rules::utils::IsChild;
-use crate::rule::Rule;
+use crate::builtin_rules::configured::DependencyRule;
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::{DependencyOptions, IsChild};
+use crate::rule::{LocatedViolation, Rule, RuleDescription};
 use crate::rust_file::RustFile;
-use ansi_term::Color::RGB;
-use ansi_term::Style;
+use crate::styling::Styling;
 use log::debug;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug)]
+/// Restricts the dependencies of the subject to `allowed_dependencies` and the subject itself.
+///
+/// Dependencies inside the subject are always allowed, and the subject is the declared one, not
+/// the path of the checked file: with subject `my_crate::domain`, a file in
+/// `my_crate::domain::orders::model` may depend on `my_crate::domain::billing`.
+#[derive(Debug, Clone)]
 pub struct MayDependOnRule {
     pub subject: String,
     pub allowed_dependencies: Vec<String>,
 }
 
+impl MayDependOnRule {
+    pub fn new(subject: impl Into<String>, allowed_dependencies: Vec<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            allowed_dependencies,
+        }
+    }
+
+    /// Allows `dependencies` too, skipping the ones already allowed.
+    pub fn allow(&mut self, dependencies: &[String]) {
+        for dependency in dependencies {
+            if !self.allowed_dependencies.contains(dependency) {
+                self.allowed_dependencies.push(dependency.clone());
+            }
+        }
+    }
+
+    /// Applies the rule to a file of `subject` with `denied_dependencies` forbidden even when
+    /// allowed, as `MayDependOnExceptRule` does.
+    pub(crate) fn check_except(
+        &self,
+        subject: &Subject,
+        options: &DependencyOptions,
+        file: &RustFile,
+        denied_dependencies: &[String],
+    ) -> Result<(), LocatedViolation> {
+        let forbidden_dependencies: Vec<String> = options
+            .dependencies_of(file)
+            .into_iter()
+            .filter(|&dependency| {
+                let is_within_subject = subject.matches(dependency);
+                let is_allowed = self
+                    .allowed_dependencies
+                    .iter()
+                    .any(|allowed| dependency.is_child_of(allowed));
+
+                let is_denied = denied_dependencies
+                    .iter()
+                    .any(|denied| dependency.is_child_of(denied));
+
+                is_denied || (!is_within_subject && !is_allowed)
+            })
+            .cloned()
+            .collect();
+        let listed_dependencies = options.listed(&forbidden_dependencies);
+
+        if !forbidden_dependencies.is_empty() {
+            return Err((
+                format!(
+                    "Forbidden dependencies to [{}] in file://{}",
+                    listed_dependencies.join(", "),
+                    file.location_of(&forbidden_dependencies[0])
+                ),
+                file.line_of(&forbidden_dependencies[0]),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 impl Display for MayDependOnRule {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl DependencyRule for MayDependOnRule {
+    fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    fn check(
+        &self,
+        subject: &Subject,
+        options: &DependencyOptions,
+        file: &RustFile,
+    ) -> Result<(), LocatedViolation> {
+        self.check_except(subject, options, file, &[])
+    }
+
+    fn render_for(
+        &self,
+        subject: &Subject,
+        _options: &DependencyOptions,
+        styling: &Styling,
+    ) -> String {
         let mut allowed_dependencies: Vec<String> = Vec::new();
         allowed_dependencies.extend(self.allowed_dependencies.clone());
-        let bold = Style::new().bold().fg(RGB(255, 165, 0));
         if allowed_dependencies.is_empty() {
-            write!(
-                f,
-                "{} may not depend on any modules",
-                bold.paint(&self.subject)
-            )
+            format!("{} may not depend on any modules", styling.subject(subject))
         } else {
-            write!(
-                f,
+            format!(
                 "{} may depend on {}",
-                bold.paint(&self.subject),
-                bold.paint("[".to_string() + &allowed_dependencies.join(", ") + "]")
+                styling.subject(subject),
+                styling.subject("[".to_string() + &allowed_dependencies.join(", ") + "]")
             )
         }
     }
 }
 
 impl Rule for MayDependOnRule {
-    fn apply(&self, file: &RustFile) -> Result<(), String> {
-        let forbidden_dependencies: Vec<String> = file
-            .dependencies
-            .iter()
-            .filter(|&dependency| {
-                let is_child_of_subject = dependency.is_child_of(&self.subject);
-                if !is_child_of_subject {
-                    let is_allowed = self
-                        .allowed_dependencies
-                        .iter()
-                        .any(|ad| dependency.is_child_of(ad));
-                    if !is_allowed {
-                        return true;
-                    }
-                }
-
-                false
-            })
-            .cloned()
-            .collect();
+    fn render(&self, styling: &Styling) -> String {
+        self.render_for(
+            &Subject::from(self.subject.as_str()),
+            &DependencyOptions::default(),
+            styling,
+        )
+    }
 
-        if !forbidden_dependencies.is_empty() {
-            let red = Style::new().fg(RGB(255, 0, 0)).bold();
-            return Err(format!(
-                "Forbidden dependencies to {} in file://{}",
-                red.paint("[".to_string() + &forbidden_dependencies.join(", ") + "]"),
-                file.path
-            ));
+    fn name(&self) -> &'static str {
+        "may_depend_on"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.clone()),
+            allowed: self.allowed_dependencies.clone(),
+            ..RuleDescription::of_kind(self.name())
         }
+    }
 
-        Ok(())
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        self.apply_located(file).map_err(|(message, _)| message)
+    }
+
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        self.check(
+            &Subject::from(self.subject.as_str()),
+            &DependencyOptions::default(),
+            file,
+        )
     }
 
     fn is_applicable(&self, file: &RustFile) -> bool {
-        let orange = Style::new().bold().fg(RGB(255, 165, 0));
-        let green = Style::new().bold().fg(RGB(0, 255, 0));
-        debug!(
-            "File {} mapped to module {}",
-            green.paint(&file.path),
-            orange.paint(&file.logical_path)
-        );
+        debug!("File {} mapped to module {}", file.path, file.logical_path);
         file.logical_path.is_child_of(&self.subject)
     }
 }
@@ -83,6 +165,7 @@ impl Rule for MayDependOnRule {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::builtin_rules::configured::Configured;
     use crate::rust_file::RustFile;
 
     #[test]
@@ -100,39 +183,94 @@ mod tests {
     }
 
     #[test]
-    fn test_display_may_depend_on_with_dependencies() {
-        use ansi_term::Color::RGB;
-        use ansi_term::Style;
+    fn test_violation_points_to_the_line_of_the_first_forbidden_import() {
+        let rule = MayDependOnRule::new("sample_project::conversion", vec![]);
+
+        let result = rule.apply(&RustFile::from_file_system(
+            "./../rust_arkitect/examples/sample_project/src/conversion/application.rs",
+        ));
+
+        let expected = "Forbidden dependencies to [sample_project::contracts::external_services::service_call_one] in file://./../rust_arkitect/examples/sample_project/src/conversion/application.rs:1".to_string();
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn test_deep_files_may_depend_on_siblings_under_the_declared_subject() {
+        let rule = MayDependOnRule::new("my_crate::domain", vec!["std::fmt".to_string()]);
+        let file = |content: &str| {
+            RustFile::from_content(
+                "src/domain/orders/model.rs",
+                "my_crate::domain::orders::model",
+                content,
+            )
+        };
 
+        assert!(rule.is_applicable(&file("")));
+        assert_eq!(
+            rule.apply(&file(
+                "use crate::domain::billing::Invoice;\nuse super::repository::Orders;\nuse std::fmt::Display;\n"
+            )),
+            Ok(())
+        );
+        assert_eq!(
+            rule.apply(&file(
+                "use crate::domain::billing::Invoice;\nuse crate::infrastructure::Db;\n"
+            )),
+            Err("Forbidden dependencies to [my_crate::infrastructure::Db] in file://src/domain/orders/model.rs:2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_display_may_depend_on_with_dependencies() {
         let rule = MayDependOnRule {
             subject: "module_3".to_string(),
             allowed_dependencies: vec!["dependency_a".to_string(), "dependency_b".to_string()],
         };
 
-        let bold_orange = Style::new().bold().fg(RGB(255, 165, 0));
-        let expected = format!(
-            "{} may depend on {}",
-            bold_orange.paint("module_3"),
-            bold_orange.paint("[dependency_a, dependency_b]")
-        );
+        let expected = "module_3 may depend on [dependency_a, dependency_b]".to_string();
         assert_eq!(format!("{}", rule), expected);
     }
 
     #[test]
     fn test_display_may_depend_on_no_dependencies() {
-        use ansi_term::Color::RGB;
-        use ansi_term::Style;
-
         let rule = MayDependOnRule {
             subject: "module_4".to_string(),
             allowed_dependencies: vec![],
         };
 
-        let bold_orange = Style::new().bold().fg(RGB(255, 165, 0));
-        let expected = format!(
-            "{} may not depend on any modules",
-            bold_orange.paint("module_4")
-        );
+        let expected = "module_4 may not depend on any modules".to_string();
         assert_eq!(format!("{}", rule), expected);
     }
+
+    #[test]
+    fn test_regex_subject() {
+        let rule = MayDependOnRule::new("", vec!["std::fmt".to_string()])
+            .matching(Subject::regex(r"my_crate::api_v\d+").unwrap());
+        let file = RustFile::from_content(
+            "src/api_v2/handlers.rs",
+            "my_crate::api_v2::handlers",
+            "use crate::api_v2::dto::Order;\nuse crate::db::Pool;\n",
+        );
+
+        assert!(rule.is_applicable(&file));
+        assert_eq!(
+            rule.apply(&file),
+            Err(
+                "Forbidden dependencies to [my_crate::db::Pool] in file://src/api_v2/handlers.rs:2"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            rule.to_string(),
+            r"my_crate::api_v\d+ may depend on [std::fmt]"
+        );
+    }
+
+    #[test]
+    fn test_configured_rule_keeps_its_subject() {
+        let rule = Configured::new(MayDependOnRule::new("my_crate::domain", vec![]));
+
+        assert_eq!(rule.subject().to_string(), "my_crate::domain");
+        assert_eq!(rule.rule().subject, "my_crate::domain");
+    }
 }
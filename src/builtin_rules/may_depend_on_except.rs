@@ -0,0 +1,137 @@
+use crate::builtin_rules::configured::Configured;
+use crate::builtin_rules::may_depend_on::MayDependOnRule;
+use crate::rule::{LocatedViolation, Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// A `MayDependOnRule` with `denied_dependencies` carved out of its allowed ones: a dependency
+/// matching both a broad allowance (`my_crate::shared`) and a narrower denial
+/// (`my_crate::shared::legacy`) is forbidden, as denials win.
+///
+/// Denials are matched like allowances, and the options of the wrapped rule apply to both.
+#[derive(Debug)]
+pub struct MayDependOnExceptRule {
+    pub rule: Configured<MayDependOnRule>,
+    pub denied_dependencies: Vec<String>,
+}
+
+impl Display for MayDependOnExceptRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MayDependOnExceptRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} except {}",
+            self.rule.render(styling),
+            styling.subject("[".to_string() + &self.denied_dependencies.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "may_depend_on_except"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.rule.subject().to_string()),
+            allowed: self.rule.rule().allowed_dependencies.clone(),
+            forbidden: self.denied_dependencies.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        self.apply_located(file).map_err(|(message, _)| message)
+    }
+
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        self.rule.rule().check_except(
+            self.rule.subject(),
+            self.rule.options(),
+            file,
+            &self.denied_dependencies,
+        )
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.rule.is_applicable(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> MayDependOnExceptRule {
+        MayDependOnExceptRule {
+            rule: Configured::new(MayDependOnRule::new(
+                "my_crate::application",
+                vec!["my_crate::shared".to_string(), "std".to_string()],
+            )),
+            denied_dependencies: vec!["my_crate::shared::legacy".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_denied_subpath_of_an_allowed_dependency_is_forbidden() {
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            "use crate::shared::money::Money;\nuse crate::shared::legacy::Gateway;\n",
+        );
+
+        assert_eq!(
+            rule().apply(&file),
+            Err("Forbidden dependencies to [my_crate::shared::legacy::Gateway] in file://src/application.rs:2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_allowed_dependencies_outside_the_denied_ones() {
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            "use crate::shared::money::Money;\nuse crate::shared::legacy_adapter::Adapter;\nuse std::fmt;\n",
+        );
+
+        assert_eq!(rule().apply(&file), Ok(()));
+    }
+
+    #[test]
+    fn test_dependencies_neither_allowed_nor_denied_are_forbidden() {
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            "use crate::infrastructure::Db;\n",
+        );
+
+        assert!(rule().apply(&file).is_err());
+    }
+
+    #[test]
+    fn test_denials_follow_the_options_of_the_wrapped_rule() {
+        let rule = MayDependOnExceptRule {
+            rule: Configured::new(MayDependOnRule::new(
+                "my_crate::application",
+                vec!["my_crate::shared".to_string()],
+            ))
+            .excluding_test_code(),
+            denied_dependencies: vec!["my_crate::shared::legacy".to_string()],
+        };
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            "#[cfg(test)]\nmod tests {\n    use crate::shared::legacy::Gateway;\n}\n",
+        );
+
+        assert_eq!(rule.apply(&file), Ok(()));
+    }
+}
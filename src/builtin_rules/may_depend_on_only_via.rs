@@ -0,0 +1,142 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::IsChild;
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// Dependencies of the subject on `target` must go through one of the `segments`,
+/// e.g. with `api` the subject may use `crate::domain::api::X` but not `crate::domain::entities::X`.
+///
+/// Only module segments count: the imported item itself, like a function `crate::domain::api`,
+/// doesn't go through `api`.
+#[derive(Debug)]
+pub struct MayDependOnOnlyViaRule {
+    pub subject: Subject,
+    pub target: String,
+    pub segments: Vec<String>,
+}
+
+impl MayDependOnOnlyViaRule {
+    fn goes_through_a_segment(&self, dependency: &str) -> bool {
+        let modules = match dependency.rsplit_once("::") {
+            Some((modules, _item)) => modules,
+            None => return false,
+        };
+
+        modules
+            .get(self.target.len()..)
+            .unwrap_or_default()
+            .split("::")
+            .any(|segment| self.segments.iter().any(|s| s == segment))
+    }
+}
+
+impl Display for MayDependOnOnlyViaRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MayDependOnOnlyViaRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} may depend on {} only via {}",
+            styling.subject(&self.subject),
+            styling.subject(&self.target),
+            styling.subject("[".to_string() + &self.segments.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "may_depend_on_only_via"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            allowed: self
+                .segments
+                .iter()
+                .map(|segment| format!("{}::**::{}", self.target, segment))
+                .collect(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let forbidden_dependencies: Vec<String> = file
+            .dependencies
+            .iter()
+            .filter(|dependency| dependency.is_child_of(&self.target))
+            .filter(|dependency| !self.goes_through_a_segment(dependency))
+            .cloned()
+            .collect();
+
+        if forbidden_dependencies.is_empty() {
+            return Ok(());
+        }
+
+        Err(format!(
+            "Dependencies bypassing [{}] in file://{}",
+            forbidden_dependencies.join(", "),
+            file.path
+        ))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_the_named_interface_may_be_used() {
+        let rule = MayDependOnOnlyViaRule {
+            subject: "my_crate::application".into(),
+            target: "my_crate::domain".to_string(),
+            segments: vec!["api".to_string(), "ports".to_string()],
+        };
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            r#"
+            use crate::domain::api::X;
+            use crate::domain::ports::Repository;
+            use crate::domain::entities::X as Entity;
+            use crate::infrastructure::Db;
+            "#,
+        );
+
+        let expected =
+            "Dependencies bypassing [my_crate::domain::entities::X] in file://src/application.rs"
+                .to_string();
+        assert_eq!(rule.apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_an_item_named_like_a_segment_does_not_go_through_it() {
+        let rule = MayDependOnOnlyViaRule {
+            subject: "my_crate::application".into(),
+            target: "my_crate::billing".to_string(),
+            segments: vec!["api".to_string()],
+        };
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            "use crate::billing::api;\nuse crate::billing::api::Invoice;\n",
+        );
+
+        let expected =
+            "Dependencies bypassing [my_crate::billing::api] in file://src/application.rs"
+                .to_string();
+        assert_eq!(rule.apply(&file), Err(expected));
+    }
+}
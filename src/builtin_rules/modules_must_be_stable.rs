@@ -0,0 +1,181 @@
+use crate::builtin_rules::utils::IsChild;
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+/// Flags modules under `root` whose fan-out (distinct modules they depend on) exceeds their
+/// fan-in (distinct modules depending on them) by more than `margin`.
+///
+/// Only the modules of the project count: `std` and external crates don't.
+pub struct ModulesMustBeStable {
+    pub root: String,
+    pub margin: usize,
+}
+
+impl Display for ModulesMustBeStable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Modules under {} must be stable (fan-out <= fan-in + {})",
+            self.root, self.margin
+        )
+    }
+}
+
+impl ProjectRule for ModulesMustBeStable {
+    fn name(&self) -> &'static str {
+        "modules_must_be_stable"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.root.clone()),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let graph = project.to_dependency_graph();
+
+        let modules: BTreeSet<&str> = project
+            .files
+            .iter()
+            .map(|file| file.logical_path.as_str())
+            .collect();
+        let project_dependencies = |module: &str, dependencies: &[String]| -> BTreeSet<String> {
+            dependencies
+                .iter()
+                .filter(|dependency| *dependency != module && modules.contains(dependency.as_str()))
+                .cloned()
+                .collect()
+        };
+
+        let mut fan_in: BTreeMap<String, BTreeSet<&str>> = BTreeMap::new();
+        for (module, dependencies) in &graph {
+            for dependency in project_dependencies(module, dependencies) {
+                fan_in.entry(dependency).or_default().insert(module);
+            }
+        }
+
+        let mut unstable_modules: Vec<String> = graph
+            .iter()
+            .filter(|(module, _)| {
+                modules.contains(module.as_str()) && module.is_child_of(&self.root)
+            })
+            .filter_map(|(module, dependencies)| {
+                let fan_out = project_dependencies(module, dependencies).len();
+                let fan_in = fan_in.get(module.as_str()).map_or(0, |m| m.len());
+
+                (fan_out > fan_in + self.margin)
+                    .then(|| format!("{} (fan-in: {}, fan-out: {})", module, fan_in, fan_out))
+            })
+            .collect();
+        unstable_modules.sort();
+
+        if !unstable_modules.is_empty() {
+            return Err(format!(
+                "Unstable modules detected:\n{}",
+                unstable_modules.join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    fn project() -> RustProject {
+        RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/domain/service.rs",
+                    "my_crate::domain::service",
+                    r#"
+                    use crate::domain::model::Entity;
+                    use crate::infrastructure::db::Connection;
+                    use crate::infrastructure::http::Client;
+                    use crate::infrastructure::queue::Publisher;
+                    use std::sync::Arc;
+                    use serde::Serialize;
+                    "#,
+                ),
+                RustFile::from_content(
+                    "src/infrastructure/db.rs",
+                    "my_crate::infrastructure::db",
+                    "pub struct Connection;",
+                ),
+                RustFile::from_content(
+                    "src/infrastructure/http.rs",
+                    "my_crate::infrastructure::http",
+                    "pub struct Client;",
+                ),
+                RustFile::from_content(
+                    "src/infrastructure/queue.rs",
+                    "my_crate::infrastructure::queue",
+                    "pub struct Publisher;",
+                ),
+                RustFile::from_content(
+                    "src/domain/model.rs",
+                    "my_crate::domain::model",
+                    "pub struct Entity;",
+                ),
+                RustFile::from_content(
+                    "src/application/handler.rs",
+                    "my_crate::application::handler",
+                    "use crate::domain::model::Entity;",
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_module_with_high_fan_out_is_flagged() {
+        let rule = ModulesMustBeStable {
+            root: "my_crate::domain".to_string(),
+            margin: 0,
+        };
+
+        assert_eq!(
+            rule.apply(&project()),
+            Err("Unstable modules detected:\n\
+                 my_crate::domain::service (fan-in: 0, fan-out: 4)"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_margin_tolerates_extra_fan_out() {
+        let rule = ModulesMustBeStable {
+            root: "my_crate::domain".to_string(),
+            margin: 4,
+        };
+
+        assert!(rule.apply(&project()).is_ok());
+    }
+
+    #[test]
+    fn test_only_modules_under_root_are_checked() {
+        let rule = ModulesMustBeStable {
+            root: "my_crate::application".to_string(),
+            margin: 0,
+        };
+
+        assert!(rule.apply(&project()).is_err());
+
+        let rule = ModulesMustBeStable {
+            root: "my_crate::infrastructure".to_string(),
+            margin: 0,
+        };
+
+        assert!(rule.apply(&project()).is_ok());
+    }
+}
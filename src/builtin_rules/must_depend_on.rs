@@ -0,0 +1,130 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::{resolve_crate_prefix, IsChild};
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// Requires every file of the subject to depend on `required_dependency` or one of its children.
+/// A leading `crate`, in `required_dependency` or in the dependencies of the file, stands for the
+/// crate of the checked file.
+#[derive(Debug)]
+pub struct MustDependOnRule {
+    pub subject: Subject,
+    pub required_dependency: String,
+}
+
+impl Display for MustDependOnRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustDependOnRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must depend on {}",
+            styling.subject(&self.subject),
+            styling.subject(&self.required_dependency)
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_depend_on"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            allowed: vec![self.required_dependency.clone()],
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let required = resolve_crate_prefix(&self.required_dependency, &file.crate_name);
+
+        if file.dependencies.iter().any(|dependency| {
+            resolve_crate_prefix(dependency, &file.crate_name).is_child_of(&required)
+        }) {
+            return Ok(());
+        }
+
+        Err(format!(
+            "Missing required dependency {} in file://{}",
+            &required, file.path
+        ))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> MustDependOnRule {
+        MustDependOnRule {
+            subject: "my_crate::handlers".into(),
+            required_dependency: "crate::telemetry".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_file_missing_the_required_dependency() {
+        let file = RustFile::from_content(
+            "src/handlers/orders.rs",
+            "my_crate::handlers::orders",
+            r#"
+            use crate::domain::Order;
+
+            pub fn handle(order: Order) {}
+            "#,
+        );
+
+        let expected =
+            "Missing required dependency my_crate::telemetry in file://src/handlers/orders.rs"
+                .to_string();
+        assert_eq!(rule().apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_file_depending_on_a_child_of_the_required_module() {
+        let file = RustFile::from_content(
+            "src/handlers/orders.rs",
+            "my_crate::handlers::orders",
+            r#"
+            use crate::domain::Order;
+            use crate::telemetry::metrics::record;
+
+            pub fn handle(order: Order) {
+                record("orders");
+            }
+            "#,
+        );
+
+        assert!(rule().is_applicable(&file));
+        assert_eq!(rule().apply(&file), Ok(()));
+    }
+
+    #[test]
+    fn test_file_calling_the_required_module_through_a_crate_path() {
+        let file = RustFile::from_content(
+            "src/handlers/orders.rs",
+            "my_crate::handlers::orders",
+            r#"
+            pub fn handle() {
+                crate::telemetry::record("orders");
+            }
+            "#,
+        );
+
+        assert_eq!(rule().apply(&file), Ok(()));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::{resolve_crate_prefix, IsChild};
+use crate::rule::{LocatedViolation, Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// Enforces dependency inversion: the subject must not depend on the `concretes`, e.g.
+/// `crate::infrastructure`, but on the traits of the `ports`, e.g. `crate::domain::ports`.
+///
+/// The ports are allowed even when they live under a concrete module, as in
+/// `crate::infrastructure::ports`. Other dependencies are left to the other rules.
+/// A leading `crate` in either list stands for the crate of the checked file.
+#[derive(Debug)]
+pub struct MustDependOnAbstractionsRule {
+    pub subject: Subject,
+    pub ports: Vec<String>,
+    pub concretes: Vec<String>,
+}
+
+impl Display for MustDependOnAbstractionsRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustDependOnAbstractionsRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must depend on {} instead of {}",
+            styling.subject(&self.subject),
+            styling.subject("[".to_string() + &self.ports.join(", ") + "]"),
+            styling.subject("[".to_string() + &self.concretes.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_depend_on_abstractions"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            allowed: self.ports.clone(),
+            forbidden: self.concretes.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        self.apply_located(file).map_err(|(message, _)| message)
+    }
+
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        let resolve = |paths: &[String]| -> Vec<String> {
+            paths
+                .iter()
+                .map(|path| resolve_crate_prefix(path, &file.crate_name))
+                .collect()
+        };
+        let ports = resolve(&self.ports);
+        let concretes = resolve(&self.concretes);
+
+        let concrete_dependencies: Vec<&String> = file
+            .dependencies
+            .iter()
+            .filter(|&dependency| {
+                concretes.iter().any(|path| dependency.is_child_of(path))
+                    && !ports.iter().any(|path| dependency.is_child_of(path))
+            })
+            .collect();
+
+        if let Some(first) = concrete_dependencies.first() {
+            let listed: Vec<&str> = concrete_dependencies.iter().map(|d| d.as_str()).collect();
+            return Err((
+                format!(
+                    "Concrete dependencies to [{}] in file://{}, depend on the traits of [{}] instead",
+                    listed.join(", "),
+                    file.location_of(first),
+                    self.ports.join(", ")
+                ),
+                file.line_of(first),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> MustDependOnAbstractionsRule {
+        MustDependOnAbstractionsRule {
+            subject: "my_crate::application".into(),
+            ports: vec!["crate::domain::ports".to_string()],
+            concretes: vec!["crate::infrastructure".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_application_importing_an_infrastructure_concrete() {
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            "use crate::domain::ports::OrderRepository;\nuse crate::infrastructure::PostgresOrderRepository;\n",
+        );
+
+        assert_eq!(
+            rule().apply(&file),
+            Err("Concrete dependencies to [my_crate::infrastructure::PostgresOrderRepository] in file://src/application.rs:2, depend on the traits of [crate::domain::ports] instead".to_string())
+        );
+    }
+
+    #[test]
+    fn test_application_importing_ports_only() {
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            "use crate::domain::ports::OrderRepository;\nuse std::sync::Arc;\n",
+        );
+
+        assert_eq!(rule().apply(&file), Ok(()));
+    }
+}
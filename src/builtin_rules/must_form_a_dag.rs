@@ -0,0 +1,131 @@
+use crate::builtin_rules::must_not_have_circular_dependencies::find_all_cycles_in_dependencies;
+use crate::builtin_rules::utils::{resolve_crate_prefix, IsChild};
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+
+/// Requires the modules under `subtree` (e.g. `crate::domain`) to form a directed acyclic graph,
+/// at full module granularity. Dependencies leaving the subtree and cycles elsewhere are ignored.
+/// A leading `crate` in `subtree` stands for each scanned crate.
+pub struct MustFormADag {
+    pub subtree: String,
+}
+
+impl Display for MustFormADag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Modules under {} must form a DAG", self.subtree)
+    }
+}
+
+impl ProjectRule for MustFormADag {
+    fn name(&self) -> &'static str {
+        "must_form_a_dag"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subtree.clone()),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let subtrees: BTreeSet<String> = project
+            .files
+            .iter()
+            .map(|file| resolve_crate_prefix(&self.subtree, &file.crate_name))
+            .collect();
+        let in_subtree = |module: &str| subtrees.iter().any(|subtree| module.is_child_of(subtree));
+
+        let graph: HashMap<String, Vec<String>> = project
+            .to_dependency_graph()
+            .into_iter()
+            .filter(|(module, _)| in_subtree(module))
+            .map(|(module, dependencies)| {
+                let dependencies = dependencies
+                    .into_iter()
+                    .filter(|dependency| in_subtree(dependency))
+                    .collect();
+                (module, dependencies)
+            })
+            .collect();
+
+        let cycles = find_all_cycles_in_dependencies(&graph, usize::MAX);
+
+        if !cycles.is_empty() {
+            return Err(format!(
+                "Circular dependencies under {}:\n{}",
+                self.subtree,
+                cycles.join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    fn project() -> RustProject {
+        RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/domain/orders/order.rs",
+                    "my_crate::domain::orders::order",
+                    "use crate::domain::orders::line::Line;\n",
+                ),
+                RustFile::from_content(
+                    "src/domain/orders/line.rs",
+                    "my_crate::domain::orders::line",
+                    "use crate::domain::orders::order::Order;\nuse crate::application::Service;\n",
+                ),
+                RustFile::from_content(
+                    "src/domain/billing.rs",
+                    "my_crate::domain::billing",
+                    "use crate::domain::orders::order::Order;\n",
+                ),
+                RustFile::from_content(
+                    "src/application.rs",
+                    "my_crate::application",
+                    "use crate::infrastructure::Db;\nuse crate::domain::billing::Invoice;\n",
+                ),
+                RustFile::from_content(
+                    "src/infrastructure.rs",
+                    "my_crate::infrastructure",
+                    "use crate::application::Service;\n",
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_cycle_inside_the_subtree_is_reported() {
+        let rule = MustFormADag {
+            subtree: "crate::domain".to_string(),
+        };
+
+        assert_eq!(
+            rule.apply(&project()),
+            Err("Circular dependencies under crate::domain:\n\
+                 my_crate::domain::orders::line -> my_crate::domain::orders::order -> my_crate::domain::orders::line"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_cycles_outside_the_subtree_are_ignored() {
+        let rule = MustFormADag {
+            subtree: "my_crate::domain::billing".to_string(),
+        };
+
+        assert_eq!(rule.apply(&project()), Ok(()));
+    }
+}
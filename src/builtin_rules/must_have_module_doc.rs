@@ -0,0 +1,124 @@
+use crate::builtin_rules::subject::Subject;
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+use syn::{AttrStyle, Expr, Lit, Meta};
+
+/// Requires every file of the subject to have a module doc (`//!` or `#![doc = ".."]`).
+/// A doc made of blank lines only satisfies the rule when `allow_empty` is set.
+#[derive(Debug)]
+pub struct MustHaveModuleDocRule {
+    pub subject: Subject,
+    pub allow_empty: bool,
+}
+
+impl MustHaveModuleDocRule {
+    /// The text of the file's inner doc attributes, `None` when there is none.
+    fn module_doc(file: &RustFile) -> Option<String> {
+        let lines: Vec<String> = file
+            .ast
+            .attrs
+            .iter()
+            .filter(|attr| matches!(attr.style, AttrStyle::Inner(_)) && attr.path().is_ident("doc"))
+            .map(|attr| match &attr.meta {
+                Meta::NameValue(doc) => match &doc.value {
+                    Expr::Lit(expr) => match &expr.lit {
+                        Lit::Str(text) => text.value(),
+                        _ => String::new(),
+                    },
+                    _ => String::new(),
+                },
+                _ => String::new(),
+            })
+            .collect();
+
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+}
+
+impl Display for MustHaveModuleDocRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustHaveModuleDocRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!("{} must have a module doc", styling.subject(&self.subject))
+    }
+
+    fn name(&self) -> &'static str {
+        "must_have_module_doc"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        match Self::module_doc(file) {
+            None => Err(format!("Missing module doc in file://{}", file.path)),
+            Some(doc) if doc.trim().is_empty() && !self.allow_empty => {
+                Err(format!("Empty module doc in file://{}", file.path))
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(allow_empty: bool) -> MustHaveModuleDocRule {
+        MustHaveModuleDocRule {
+            subject: "my_crate::domain".into(),
+            allow_empty,
+        }
+    }
+
+    #[test]
+    fn test_documented_file() {
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            "//! Orders and their lifecycle.\n\npub struct Order;\n",
+        );
+
+        assert_eq!(rule(false).apply(&file), Ok(()));
+    }
+
+    #[test]
+    fn test_undocumented_file() {
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            "/// Not a module doc.\npub struct Order;\n",
+        );
+
+        assert_eq!(
+            rule(false).apply(&file),
+            Err("Missing module doc in file://src/domain.rs".to_string())
+        );
+    }
+
+    #[test]
+    fn test_empty_doc_counts_only_when_allowed() {
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            "//!\n//!   \npub struct Order;\n",
+        );
+
+        assert!(rule(false).apply(&file).is_err());
+        assert_eq!(rule(true).apply(&file), Ok(()));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::builtin_rules::utils::{resolve_crate_prefix, IsChild};
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use std::fmt::{Display, Formatter};
+
+/// Restricts a component to its public surface: modules outside `component` may depend on it,
+/// but not on its `private_submodules`, given relative to the component (e.g. `internal`).
+/// A leading `crate` in `component` stands for the crate of each checked file.
+pub struct MustHideInternals {
+    pub component: String,
+    pub private_submodules: Vec<String>,
+}
+
+impl Display for MustHideInternals {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} must hide its internals [{}]",
+            self.component,
+            self.private_submodules.join(", ")
+        )
+    }
+}
+
+impl ProjectRule for MustHideInternals {
+    fn name(&self) -> &'static str {
+        "must_hide_internals"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.component.clone()),
+            forbidden: self
+                .private_submodules
+                .iter()
+                .map(|submodule| format!("{}::{}", self.component, submodule))
+                .collect(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let mut reached = Vec::new();
+
+        for file in &project.files {
+            let component = resolve_crate_prefix(&self.component, &file.crate_name);
+            if file.logical_path.is_child_of(&component) {
+                continue;
+            }
+
+            let private_paths: Vec<String> = self
+                .private_submodules
+                .iter()
+                .map(|submodule| format!("{}::{}", component, submodule))
+                .collect();
+
+            for dependency in &file.dependencies {
+                let resolved = resolve_crate_prefix(dependency, &file.crate_name);
+                if private_paths
+                    .iter()
+                    .any(|private| resolved.is_child_of(private))
+                {
+                    reached.push(format!(
+                        "{} -> {} in file://{}",
+                        file.logical_path,
+                        resolved,
+                        file.location_of(dependency)
+                    ));
+                }
+            }
+        }
+
+        if !reached.is_empty() {
+            return Err(format!(
+                "Internals of {} reached from outside:\n{}",
+                self.component,
+                reached.join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    #[test]
+    fn test_importing_internals_from_outside_is_reported() {
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/orders.rs",
+                    "my_crate::orders",
+                    "use crate::billing::Invoice;\nuse crate::billing::internal::Secret;\n",
+                ),
+                RustFile::from_content(
+                    "src/billing/mod.rs",
+                    "my_crate::billing",
+                    "use crate::billing::internal::Secret;\npub struct Invoice;\n",
+                ),
+                RustFile::from_content(
+                    "src/billing/internal.rs",
+                    "my_crate::billing::internal",
+                    "pub struct Secret;\n",
+                ),
+            ],
+        };
+
+        let rule = MustHideInternals {
+            component: "crate::billing".to_string(),
+            private_submodules: vec!["internal".to_string()],
+        };
+
+        assert_eq!(
+            rule.apply(&project),
+            Err(
+                "Internals of crate::billing reached from outside:\n\
+                 my_crate::orders -> my_crate::billing::internal::Secret in file://src/orders.rs:2"
+                    .to_string()
+            )
+        );
+    }
+}
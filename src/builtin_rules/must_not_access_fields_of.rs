@@ -0,0 +1,184 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::{resolve_path, IsChild};
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+use syn::visit::{self, Visit};
+use syn::{Expr, ExprField, ExprStruct, Member, PatStruct, Path};
+
+/// Forbids the subject from building or reading the fields of types defined in
+/// `forbidden_components`, leaving constructors and methods as the only way in.
+///
+/// Struct literals (`billing::Invoice { .. }`), struct patterns and field accesses on paths
+/// (`billing::DEFAULT_INVOICE.amount`) are checked, with paths resolved through the file's `use`
+/// declarations. Field accesses on local bindings need type inference and aren't detected.
+#[derive(Debug)]
+pub struct MustNotAccessFieldsOfRule {
+    pub subject: Subject,
+    pub forbidden_components: Vec<String>,
+}
+
+/// Collects the paths whose fields are accessed, with the line they are accessed at.
+struct FieldAccessVisitor<'a> {
+    file: &'a RustFile,
+    accesses: Vec<(String, usize)>,
+}
+
+impl FieldAccessVisitor<'_> {
+    fn record(&mut self, path: &Path, field: Option<&Member>) {
+        let Some(resolved) = resolve_path(self.file, path) else {
+            return;
+        };
+        let line = path
+            .segments
+            .first()
+            .map(|segment| segment.ident.span().start().line)
+            .unwrap_or_default();
+        let access = match field {
+            Some(Member::Named(ident)) => format!("{}.{}", resolved, ident),
+            Some(Member::Unnamed(index)) => format!("{}.{}", resolved, index.index),
+            None => format!("{} {{ .. }}", resolved),
+        };
+
+        self.accesses.push((access, line));
+    }
+}
+
+impl<'ast> Visit<'ast> for FieldAccessVisitor<'_> {
+    fn visit_expr_struct(&mut self, node: &'ast ExprStruct) {
+        self.record(&node.path, None);
+        visit::visit_expr_struct(self, node);
+    }
+
+    fn visit_pat_struct(&mut self, node: &'ast PatStruct) {
+        self.record(&node.path, None);
+        visit::visit_pat_struct(self, node);
+    }
+
+    fn visit_expr_field(&mut self, node: &'ast ExprField) {
+        if let Expr::Path(base) = node.base.as_ref() {
+            self.record(&base.path, Some(&node.member));
+        }
+        visit::visit_expr_field(self, node);
+    }
+}
+
+impl Display for MustNotAccessFieldsOfRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustNotAccessFieldsOfRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must not access the fields of {}",
+            styling.subject(&self.subject),
+            styling.subject("[".to_string() + &self.forbidden_components.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_access_fields_of"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            forbidden: self.forbidden_components.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let mut visitor = FieldAccessVisitor {
+            file,
+            accesses: vec![],
+        };
+        visitor.visit_file(&file.ast);
+
+        let forbidden: Vec<(String, usize)> = visitor
+            .accesses
+            .into_iter()
+            .filter(|(access, _)| {
+                self.forbidden_components
+                    .iter()
+                    .any(|component| access.is_child_of(component))
+            })
+            .collect();
+
+        let Some((_, line)) = forbidden.first() else {
+            return Ok(());
+        };
+
+        let accesses: Vec<&str> = forbidden
+            .iter()
+            .map(|(access, _)| access.as_str())
+            .collect();
+        Err(format!(
+            "Direct field access to forbidden components [{}] in file://{}:{}",
+            accesses.join(", "),
+            file.path,
+            line
+        ))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> MustNotAccessFieldsOfRule {
+        MustNotAccessFieldsOfRule {
+            subject: "my_crate::orders".into(),
+            forbidden_components: vec!["my_crate::billing".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_cross_component_struct_literal_and_field_access() {
+        let file = RustFile::from_content(
+            "src/orders.rs",
+            "my_crate::orders",
+            r#"
+            use crate::billing::Invoice;
+            use crate::billing;
+
+            fn checkout(total: u64) -> Invoice {
+                let _ = billing::DEFAULT_INVOICE.amount;
+                Invoice { amount: total }
+            }
+
+            fn draft() -> Order {
+                Order { lines: vec![] }
+            }
+            "#,
+        );
+
+        let expected = "Direct field access to forbidden components [my_crate::billing::DEFAULT_INVOICE.amount, my_crate::billing::Invoice { .. }] in file://src/orders.rs:6".to_string();
+        assert_eq!(rule().apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_constructors_of_other_components_are_allowed() {
+        let file = RustFile::from_content(
+            "src/orders.rs",
+            "my_crate::orders",
+            r#"
+            use crate::billing::Invoice;
+
+            fn checkout(total: u64) -> u64 {
+                let invoice = Invoice::new(total);
+                invoice.amount()
+            }
+            "#,
+        );
+
+        assert_eq!(rule().apply(&file), Ok(()));
+    }
+}
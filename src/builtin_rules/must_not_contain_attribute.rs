@@ -0,0 +1,215 @@
+use crate::builtin_rules::subject::Subject;
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use quote::ToTokens;
+use std::fmt::{Display, Formatter};
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Attribute, Meta, Path, Token};
+
+/// Forbids the subject from carrying an attribute, e.g. `#[tokio::main]` outside the binaries or
+/// `#[derive(Serialize)]` in the domain layer.
+///
+/// `attribute` is written as in source, with or without the surrounding `#[..]`. Attributes on the
+/// file, on items and on their fields or variants are checked. A list pattern such as
+/// `derive(Serialize)` matches any attribute of that path listing all of its entries, so
+/// `#[derive(Debug, serde::Serialize)]` matches too.
+#[derive(Debug)]
+pub struct MustNotContainAttributeRule {
+    pub subject: Subject,
+    pub attribute: String,
+}
+
+impl MustNotContainAttributeRule {
+    /// The attribute pattern without its `#[..]` delimiters.
+    fn pattern_of(attribute: &str) -> &str {
+        let attribute = attribute.trim();
+        attribute
+            .strip_prefix("#![")
+            .or_else(|| attribute.strip_prefix("#["))
+            .and_then(|inner| inner.strip_suffix(']'))
+            .unwrap_or(attribute)
+            .trim()
+    }
+
+    /// Parses `attribute`, panicking when it isn't a valid attribute.
+    pub(crate) fn parse_pattern(attribute: &str) -> Meta {
+        syn::parse_str(Self::pattern_of(attribute))
+            .unwrap_or_else(|e| panic!("Invalid attribute {}: {}", attribute, e))
+    }
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+fn tokens_to_string(tokens: &impl ToTokens) -> String {
+    tokens.to_token_stream().to_string()
+}
+
+/// Whether `attr` is an occurrence of `pattern`.
+fn matches(pattern: &Meta, attr: &Attribute) -> bool {
+    if path_to_string(pattern.path()) != path_to_string(attr.path()) {
+        return false;
+    }
+
+    match (pattern, &attr.meta) {
+        (Meta::Path(_), _) => true,
+        (Meta::List(expected), Meta::List(actual)) => {
+            let parser = Punctuated::<Path, Token![,]>::parse_terminated;
+            match (
+                expected.parse_args_with(parser),
+                actual.parse_args_with(parser),
+            ) {
+                (Ok(expected), Ok(actual)) => expected.iter().all(|wanted| {
+                    let wanted = path_to_string(wanted);
+                    actual.iter().any(|path| {
+                        let path = path_to_string(path);
+                        path == wanted || path.ends_with(&format!("::{}", wanted))
+                    })
+                }),
+                _ => tokens_to_string(&expected.tokens) == tokens_to_string(&actual.tokens),
+            }
+        }
+        (Meta::NameValue(expected), Meta::NameValue(actual)) => {
+            tokens_to_string(&expected.value) == tokens_to_string(&actual.value)
+        }
+        _ => false,
+    }
+}
+
+/// Collects the lines of the attributes matching the pattern.
+struct AttributeVisitor<'a> {
+    pattern: &'a Meta,
+    lines: Vec<usize>,
+}
+
+impl<'ast> Visit<'ast> for AttributeVisitor<'_> {
+    fn visit_attribute(&mut self, node: &'ast Attribute) {
+        if matches(self.pattern, node) {
+            self.lines.push(node.pound_token.span.start().line);
+        }
+        visit::visit_attribute(self, node);
+    }
+}
+
+impl Display for MustNotContainAttributeRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustNotContainAttributeRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must not contain attribute {}",
+            styling.subject(&self.subject),
+            styling.forbidden(format!("#[{}]", Self::pattern_of(&self.attribute)))
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_contain_attribute"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            forbidden: vec![Self::pattern_of(&self.attribute).to_string()],
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let pattern = Self::parse_pattern(&self.attribute);
+        let mut visitor = AttributeVisitor {
+            pattern: &pattern,
+            lines: vec![],
+        };
+        visitor.visit_file(&file.ast);
+
+        let Some(line) = visitor.lines.first() else {
+            return Ok(());
+        };
+
+        Err(format!(
+            "Forbidden attribute #[{}] in file://{}:{}",
+            Self::pattern_of(&self.attribute),
+            file.path,
+            line
+        ))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(attribute: &str) -> MustNotContainAttributeRule {
+        MustNotContainAttributeRule {
+            subject: "my_crate::domain".into(),
+            attribute: attribute.to_string(),
+        }
+    }
+
+    fn domain_file(content: &str) -> RustFile {
+        RustFile::from_content("src/domain.rs", "my_crate::domain", content)
+    }
+
+    #[test]
+    fn test_derive_on_struct() {
+        let file = domain_file(
+            "use serde::Serialize;\n\n#[derive(Debug, Serialize)]\npub struct Order {\n    id: u32,\n}\n",
+        );
+
+        assert_eq!(
+            rule("#[derive(Serialize)]").apply(&file),
+            Err("Forbidden attribute #[derive(Serialize)] in file://src/domain.rs:3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_qualified_derive_matches() {
+        let file = domain_file("#[derive(serde::Serialize)]\npub struct Order;\n");
+
+        assert!(rule("derive(Serialize)").apply(&file).is_err());
+    }
+
+    #[test]
+    fn test_other_derives_are_allowed() {
+        let file = domain_file(
+            "#[derive(Debug, Clone, Deserialize)]\npub struct Order;\n\n#[serialize]\nfn f() {}\n",
+        );
+
+        assert_eq!(rule("#[derive(Serialize)]").apply(&file), Ok(()));
+    }
+
+    #[test]
+    fn test_path_attribute_on_nested_items() {
+        let file = domain_file(
+            "mod runtime {\n    #[tokio::main]\n    async fn main() {}\n}\n\n#[main]\nfn other() {}\n",
+        );
+
+        assert_eq!(
+            rule("#[tokio::main]").apply(&file),
+            Err("Forbidden attribute #[tokio::main] in file://src/domain.rs:2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_inner_file_attribute() {
+        let file = domain_file("#![allow(dead_code)]\n\npub struct Order;\n");
+
+        assert!(rule("#![allow(dead_code)]").apply(&file).is_err());
+        assert_eq!(rule("allow(unused)").apply(&file), Ok(()));
+    }
+}
@@ -1,8 +1,9 @@
-use crate::builtin_rules::utils::IsChild;
-use crate::rule::Rule;
+use crate::builtin_rules::configured::DependencyRule;
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::{DependencyOptions, IsChild};
+use crate::rule::{LocatedViolation, Rule, RuleDescription};
 use crate::rust_file::RustFile;
-use ansi_term::Color::RGB;
-use ansi_term::Style;
+use crate::styling::Styling;
 use log::debug;
 use std::fmt::{Display, Formatter};
 
@@ -13,9 +14,9 @@ pub struct MustNotDependOnRule {
 }
 
 impl MustNotDependOnRule {
-    pub fn new(subject: String, forbidden_dependencies: Vec<String>) -> Self {
+    pub fn new(subject: impl Into<String>, forbidden_dependencies: Vec<String>) -> Self {
         Self {
-            subject,
+            subject: subject.into(),
             forbidden_dependencies,
         }
     }
@@ -29,25 +30,24 @@ impl From<MustNotDependOnRule> for Box<dyn Rule> {
 
 impl Display for MustNotDependOnRule {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let bold = Style::new().bold().fg(RGB(255, 165, 0));
-        if self.forbidden_dependencies.is_empty() {
-            write!(f, "{} may depend on any module", bold.paint(&self.subject))
-        } else {
-            write!(
-                f,
-                "{} must not depend on {}",
-                bold.paint(&self.subject),
-                bold.paint("[".to_string() + &self.forbidden_dependencies.join(", ") + "]")
-            )
-        }
+        f.write_str(&self.render(&Styling::plain()))
     }
 }
 
-impl Rule for MustNotDependOnRule {
-    fn apply(&self, file: &RustFile) -> Result<(), String> {
-        let forbidden_dependencies: Vec<String> = file
-            .dependencies
-            .iter()
+impl DependencyRule for MustNotDependOnRule {
+    fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    fn check(
+        &self,
+        _subject: &Subject,
+        options: &DependencyOptions,
+        file: &RustFile,
+    ) -> Result<(), LocatedViolation> {
+        let forbidden_dependencies: Vec<String> = options
+            .dependencies_of(file)
+            .into_iter()
             .filter(|&dependency| {
                 self.forbidden_dependencies
                     .iter()
@@ -55,27 +55,79 @@ impl Rule for MustNotDependOnRule {
             })
             .cloned()
             .collect();
+        let listed_dependencies = options.listed(&forbidden_dependencies);
 
         if !forbidden_dependencies.is_empty() {
-            let red = Style::new().fg(RGB(255, 0, 0)).bold();
-            return Err(format!(
-                "Forbidden dependencies to {} in file://{}",
-                red.paint("[".to_string() + &forbidden_dependencies.join(", ") + "]"),
-                file.path
+            return Err((
+                format!(
+                    "Forbidden dependencies to [{}] in file://{}",
+                    listed_dependencies.join(", "),
+                    file.location_of(&forbidden_dependencies[0])
+                ),
+                file.line_of(&forbidden_dependencies[0]),
             ));
         }
 
         Ok(())
     }
 
+    fn render_for(
+        &self,
+        subject: &Subject,
+        _options: &DependencyOptions,
+        styling: &Styling,
+    ) -> String {
+        if self.forbidden_dependencies.is_empty() {
+            format!("{} may depend on any module", styling.subject(subject))
+        } else {
+            format!(
+                "{} must not depend on {}",
+                styling.subject(subject),
+                styling.subject("[".to_string() + &self.forbidden_dependencies.join(", ") + "]")
+            )
+        }
+    }
+}
+
+impl Rule for MustNotDependOnRule {
+    fn render(&self, styling: &Styling) -> String {
+        self.render_for(
+            &Subject::from(self.subject.as_str()),
+            &DependencyOptions::default(),
+            styling,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_depend_on"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.clone()),
+            forbidden: self.forbidden_dependencies.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        self.apply_located(file).map_err(|(message, _)| message)
+    }
+
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        self.check(
+            &Subject::from(self.subject.as_str()),
+            &DependencyOptions::default(),
+            file,
+        )
+    }
+
     fn is_applicable(&self, file: &RustFile) -> bool {
-        let orange = Style::new().bold().fg(RGB(255, 165, 0));
-        let green = Style::new().bold().fg(RGB(0, 255, 0));
-        debug!(
-            "File {} mapped to module {}",
-            green.paint(&file.path),
-            orange.paint(&file.logical_path)
-        );
+        debug!("File {} mapped to module {}", file.path, file.logical_path);
         file.logical_path.is_child_of(&self.subject)
     }
 }
@@ -115,35 +167,84 @@ mod tests {
 
     #[test]
     fn test_display_may_depend_on_with_dependencies() {
-        use ansi_term::Color::RGB;
-        use ansi_term::Style;
-
         let rule = MustNotDependOnRule {
             subject: "module_3".to_string(),
             forbidden_dependencies: vec!["dependency_a".to_string(), "dependency_b".to_string()],
         };
 
-        let bold_orange = Style::new().bold().fg(RGB(255, 165, 0));
-        let expected = format!(
-            "{} must not depend on {}",
-            bold_orange.paint("module_3"),
-            bold_orange.paint("[dependency_a, dependency_b]")
-        );
+        let expected = "module_3 must not depend on [dependency_a, dependency_b]".to_string();
         assert_eq!(format!("{}", rule), expected);
     }
 
     #[test]
     fn test_display_may_depend_on_no_dependencies() {
-        use ansi_term::Color::RGB;
-        use ansi_term::Style;
-
         let rule = MustNotDependOnRule {
             subject: "module_4".to_string(),
             forbidden_dependencies: vec![],
         };
 
-        let bold_orange = Style::new().bold().fg(RGB(255, 165, 0));
-        let expected = format!("{} may depend on any module", bold_orange.paint("module_4"));
+        let expected = "module_4 may depend on any module".to_string();
         assert_eq!(format!("{}", rule), expected);
     }
+
+    #[test]
+    fn test_test_code_can_be_excluded() {
+        let rule = MustNotDependOnRule::new(
+            "my_crate::domain",
+            vec!["my_crate::application".to_string()],
+        );
+        let inline_test_module = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            r#"
+            #[cfg(test)]
+            mod tests {
+                use crate::application::Service;
+            }
+            "#,
+        );
+        let integration_test = RustFile::from_content(
+            "tests/domain.rs",
+            "my_crate::domain::tests",
+            "use my_crate::application::Service;",
+        );
+
+        assert!(rule.apply(&inline_test_module).is_err());
+        assert!(rule.apply(&integration_test).is_err());
+
+        let rule = rule.excluding_test_code();
+
+        assert!(rule.apply(&inline_test_module).is_ok());
+        assert!(rule.apply(&integration_test).is_ok());
+    }
+
+    #[test]
+    fn test_dependencies_can_be_collapsed_to_modules() {
+        let rule = MustNotDependOnRule::new(
+            "my_crate::application",
+            vec!["my_crate::domain".to_string()],
+        );
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            r#"
+            use crate::domain::foo::{Order, OrderId, OrderLine};
+            "#,
+        );
+
+        let expected = |listed: &str| {
+            format!(
+                "Forbidden dependencies to {} in file://src/application.rs:2",
+                listed
+            )
+        };
+        assert_eq!(
+            rule.apply(&file),
+            Err(expected("[my_crate::domain::foo::Order, my_crate::domain::foo::OrderId, my_crate::domain::foo::OrderLine]"))
+        );
+
+        let rule = rule.collapsing_to_modules();
+
+        assert_eq!(rule.apply(&file), Err(expected("[my_crate::domain::foo]")));
+    }
 }
@@ -1,8 +1,11 @@
-use crate::builtin_rules::utils::IsChild;
-use crate::rule::Rule;
+use crate::builtin_rules::configured::{Configured, DependencyRule};
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::{
+    classify_dependency, DependencyKind, DependencyOptions, IsChild,
+};
+use crate::rule::{LocatedViolation, Rule, RuleDescription};
 use crate::rust_file::RustFile;
-use ansi_term::Color::RGB;
-use ansi_term::Style;
+use crate::styling::Styling;
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug)]
@@ -11,35 +14,52 @@ pub struct MustNotDependOnAnythingRule {
     pub allowed_external_dependencies: Vec<String>,
 }
 
+impl MustNotDependOnAnythingRule {
+    pub fn new(subject: impl Into<String>, allowed_external_dependencies: Vec<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            allowed_external_dependencies,
+        }
+    }
+
+    /// Allows `std`, `core` and `alloc` without listing them one by one.
+    pub fn allow_std(self) -> Configured<Self> {
+        Configured::new(self).allow_std()
+    }
+}
+
+impl Configured<MustNotDependOnAnythingRule> {
+    /// Allows `std`, `core` and `alloc` without listing them one by one.
+    pub fn allow_std(mut self) -> Self {
+        self.options_mut().allow_std = true;
+        self
+    }
+}
+
 impl Display for MustNotDependOnAnythingRule {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut allowed_dependencies: Vec<String> = Vec::new();
-        allowed_dependencies.extend(self.allowed_external_dependencies.clone());
-        let bold = Style::new().bold().fg(ansi_term::Color::RGB(255, 165, 0));
-        if allowed_dependencies.is_empty() {
-            write!(
-                f,
-                "{} may not depend on any modules",
-                bold.paint(&self.subject),
-            )
-        } else {
-            write!(
-                f,
-                "{} may depend on {}",
-                bold.paint(&self.subject),
-                bold.paint("[".to_string() + &allowed_dependencies.join(", ") + "]")
-            )
-        }
+        f.write_str(&self.render(&Styling::plain()))
     }
 }
 
-impl Rule for MustNotDependOnAnythingRule {
-    fn apply(&self, file: &RustFile) -> Result<(), String> {
-        let forbidden_dependencies: Vec<String> = file
-            .dependencies
-            .iter()
+impl DependencyRule for MustNotDependOnAnythingRule {
+    fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    fn check(
+        &self,
+        subject: &Subject,
+        options: &DependencyOptions,
+        file: &RustFile,
+    ) -> Result<(), LocatedViolation> {
+        let forbidden_dependencies: Vec<String> = options
+            .dependencies_of(file)
+            .into_iter()
             .filter(|&dependency| {
-                !(dependency.is_child_of(&self.subject)
+                !(subject.matches(dependency)
+                    || (options.allow_std
+                        && classify_dependency(dependency, file) == DependencyKind::Std)
                     || self
                         .allowed_external_dependencies
                         .iter()
@@ -47,19 +67,82 @@ impl Rule for MustNotDependOnAnythingRule {
             })
             .cloned()
             .collect();
+        let listed_dependencies = options.listed(&forbidden_dependencies);
 
         if forbidden_dependencies.is_empty() {
             Ok(())
         } else {
-            let red = Style::new().fg(RGB(255, 0, 0)).bold();
-            Err(format!(
-                "Forbidden dependencies to {} in file://{}",
-                red.paint("[".to_string() + &forbidden_dependencies.join(", ") + "]"),
-                file.path
+            Err((
+                format!(
+                    "Forbidden dependencies to [{}] in file://{}",
+                    listed_dependencies.join(", "),
+                    file.location_of(&forbidden_dependencies[0])
+                ),
+                file.line_of(&forbidden_dependencies[0]),
             ))
         }
     }
 
+    fn render_for(
+        &self,
+        subject: &Subject,
+        options: &DependencyOptions,
+        styling: &Styling,
+    ) -> String {
+        let mut allowed_dependencies: Vec<String> = Vec::new();
+        if options.allow_std {
+            allowed_dependencies.push("std".to_string());
+        }
+        allowed_dependencies.extend(self.allowed_external_dependencies.clone());
+        if allowed_dependencies.is_empty() {
+            format!("{} may not depend on any modules", styling.subject(subject),)
+        } else {
+            format!(
+                "{} may depend on {}",
+                styling.subject(subject),
+                styling.subject("[".to_string() + &allowed_dependencies.join(", ") + "]")
+            )
+        }
+    }
+}
+
+impl Rule for MustNotDependOnAnythingRule {
+    fn render(&self, styling: &Styling) -> String {
+        self.render_for(
+            &Subject::from(self.subject.as_str()),
+            &DependencyOptions::default(),
+            styling,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_depend_on_anything"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.clone()),
+            allowed: self.allowed_external_dependencies.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        self.apply_located(file).map_err(|(message, _)| message)
+    }
+
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        self.check(
+            &Subject::from(self.subject.as_str()),
+            &DependencyOptions::default(),
+            file,
+        )
+    }
+
     fn is_applicable(&self, file: &RustFile) -> bool {
         file.logical_path.is_child_of(&self.subject)
     }
@@ -68,30 +151,22 @@ impl Rule for MustNotDependOnAnythingRule {
 #[cfg(test)]
 mod tests {
     use crate::builtin_rules::must_not_depend_on_anything::MustNotDependOnAnythingRule;
+    use crate::rule::Rule;
+    use crate::rust_file::RustFile;
 
     #[test]
     fn test_display_must_not_depend_on_anything_no_dependencies() {
-        use ansi_term::Color::RGB;
-        use ansi_term::Style;
-
         let rule = MustNotDependOnAnythingRule {
             subject: "module_2".to_string(),
             allowed_external_dependencies: vec![],
         };
 
-        let bold_orange = Style::new().bold().fg(RGB(255, 165, 0));
-        let expected = format!(
-            "{} may not depend on any modules",
-            bold_orange.paint("module_2")
-        );
+        let expected = "module_2 may not depend on any modules".to_string();
         assert_eq!(format!("{}", rule), expected);
     }
 
     #[test]
     fn test_display_must_not_depend_on_anything_with_dependencies() {
-        use ansi_term::Color::RGB;
-        use ansi_term::Style;
-
         let rule = MustNotDependOnAnythingRule {
             subject: "module_1".to_string(),
             allowed_external_dependencies: vec![
@@ -100,12 +175,48 @@ mod tests {
             ],
         };
 
-        let bold_orange = Style::new().bold().fg(RGB(255, 165, 0));
-        let expected = format!(
-            "{} may depend on {}",
-            bold_orange.paint("module_1"),
-            bold_orange.paint("[dependency_1, dependency_2]")
-        );
+        let expected = "module_1 may depend on [dependency_1, dependency_2]".to_string();
         assert_eq!(format!("{}", rule), expected);
     }
+
+    #[test]
+    fn test_allow_std_allows_the_whole_std_family() {
+        let rule = MustNotDependOnAnythingRule::new("my_crate::domain", vec![]).allow_std();
+
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            r#"
+            use std::collections::HashMap;
+            use core::fmt;
+            use alloc::vec::Vec;
+            use serde::Serialize;
+            "#,
+        );
+
+        let expected =
+            "Forbidden dependencies to [serde::Serialize] in file://src/domain.rs:5".to_string();
+        assert_eq!(rule.apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_no_std_policy() {
+        let rule = MustNotDependOnAnythingRule::new(
+            "my_crate::domain",
+            vec!["core".to_string(), "alloc".to_string()],
+        );
+
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            r#"
+            use core::fmt;
+            use alloc::string::String;
+            use std::fs;
+            "#,
+        );
+
+        let expected = "Forbidden dependencies to [std::fs] in file://src/domain.rs:4".to_string();
+        assert_eq!(rule.apply(&file), Err(expected));
+    }
 }
@@ -0,0 +1,126 @@
+use crate::builtin_rules::utils::external_crate_of;
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+/// Limits the number of distinct external crates each crate of the project uses, across all
+/// its files. The crates of the project itself, like workspace siblings, aren't counted.
+pub struct MustNotExceedExternalCrates {
+    pub max_external_crates: usize,
+}
+
+impl Display for MustNotExceedExternalCrates {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Each crate must use at most {} external crates",
+            self.max_external_crates
+        )
+    }
+}
+
+impl ProjectRule for MustNotExceedExternalCrates {
+    fn name(&self) -> &'static str {
+        "must_not_exceed_external_crates"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            allowed: vec![format!("max {}", self.max_external_crates)],
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let project_crates: BTreeSet<&str> = project
+            .files
+            .iter()
+            .map(|file| file.crate_name.as_str())
+            .collect();
+
+        let mut external_crates: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for file in &project.files {
+            let used = external_crates.entry(&file.crate_name).or_default();
+            used.extend(
+                file.dependencies
+                    .iter()
+                    .filter_map(|dependency| external_crate_of(dependency, file))
+                    .filter(|external_crate| !project_crates.contains(external_crate)),
+            );
+        }
+
+        let exceeding: Vec<String> = external_crates
+            .into_iter()
+            .filter(|(_, used)| used.len() > self.max_external_crates)
+            .map(|(crate_name, used)| {
+                format!(
+                    "{} uses {} external crates: {}",
+                    crate_name,
+                    used.len(),
+                    used.into_iter().collect::<Vec<_>>().join(", ")
+                )
+            })
+            .collect();
+
+        if !exceeding.is_empty() {
+            return Err(format!(
+                "Crates exceeding {} external crates:\n{}",
+                self.max_external_crates,
+                exceeding.join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    #[test]
+    fn test_crates_above_the_limit_are_reported() {
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "api/src/lib.rs",
+                    "api",
+                    "use serde::Serialize; use axum::Router; use domain::Order; use std::fmt;",
+                ),
+                RustFile::from_content(
+                    "api/src/handlers.rs",
+                    "api::handlers",
+                    "use tokio::spawn; use serde::Deserialize;",
+                ),
+                RustFile::from_content(
+                    "domain/src/lib.rs",
+                    "domain",
+                    "mod events; mod rules; use serde::Serialize; use events::Placed; use rules::Check;",
+                ),
+            ],
+        };
+
+        let rule = MustNotExceedExternalCrates {
+            max_external_crates: 2,
+        };
+
+        assert_eq!(
+            rule.apply(&project),
+            Err("Crates exceeding 2 external crates:\n\
+                 api uses 3 external crates: axum, serde, tokio"
+                .to_string())
+        );
+
+        let rule = MustNotExceedExternalCrates {
+            max_external_crates: 3,
+        };
+
+        assert_eq!(rule.apply(&project), Ok(()));
+    }
+}
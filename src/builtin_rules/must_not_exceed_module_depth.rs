@@ -0,0 +1,94 @@
+use crate::builtin_rules::subject::Subject;
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// Caps the nesting of the subject's modules: the depth of a module is the number of segments
+/// of its logical path, the crate included, so `my_crate::domain::orders` has depth 3.
+#[derive(Debug)]
+pub struct MustNotExceedModuleDepthRule {
+    pub subject: Subject,
+    pub max_depth: usize,
+}
+
+impl Display for MustNotExceedModuleDepthRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustNotExceedModuleDepthRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must not nest modules deeper than {}",
+            styling.subject(&self.subject),
+            self.max_depth
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_exceed_module_depth"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let depth = file.logical_path.split("::").count();
+        if depth > self.max_depth {
+            return Err(format!(
+                "Module {} has depth {}, more than {} in file://{}",
+                &file.logical_path, depth, self.max_depth, file.path
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> MustNotExceedModuleDepthRule {
+        MustNotExceedModuleDepthRule {
+            subject: "my_crate::domain".into(),
+            max_depth: 3,
+        }
+    }
+
+    #[test]
+    fn test_deeply_nested_module_is_reported() {
+        let file = RustFile::from_content(
+            "src/domain/orders/lines/discounts.rs",
+            "my_crate::domain::orders::lines::discounts",
+            "",
+        );
+
+        assert_eq!(
+            rule().apply(&file),
+            Err("Module my_crate::domain::orders::lines::discounts has depth 5, more than 3 in file://src/domain/orders/lines/discounts.rs".to_string())
+        );
+    }
+
+    #[test]
+    fn test_shallow_module_complies() {
+        let file = RustFile::from_content("src/domain/orders.rs", "my_crate::domain::orders", "");
+
+        assert!(rule().is_applicable(&file));
+        assert_eq!(rule().apply(&file), Ok(()));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::builtin_rules::subject::Subject;
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+use syn::{Item, Visibility};
+
+/// Caps the `pub` functions, structs, enums, traits and constants declared at the top level of
+/// each of the subject's files, to keep modules focused. Restricted visibilities like
+/// `pub(crate)` don't count.
+#[derive(Debug)]
+pub struct MustNotExceedPublicItemsRule {
+    pub subject: Subject,
+    pub max_public_items: usize,
+}
+
+impl Display for MustNotExceedPublicItemsRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustNotExceedPublicItemsRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must not declare more than {} public items per module",
+            styling.subject(&self.subject),
+            self.max_public_items
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_exceed_public_items"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let count = file
+            .ast
+            .items
+            .iter()
+            .filter(|item| matches!(visibility_of(item), Some(Visibility::Public(_))))
+            .count();
+
+        if count > self.max_public_items {
+            return Err(format!(
+                "Module {} has {} public items, more than {} in file://{}",
+                &file.logical_path, count, self.max_public_items, file.path
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+/// The visibility of the items counted by the rule.
+fn visibility_of(item: &Item) -> Option<&Visibility> {
+    match item {
+        Item::Fn(item) => Some(&item.vis),
+        Item::Struct(item) => Some(&item.vis),
+        Item::Enum(item) => Some(&item.vis),
+        Item::Trait(item) => Some(&item.vis),
+        Item::Const(item) => Some(&item.vis),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> MustNotExceedPublicItemsRule {
+        MustNotExceedPublicItemsRule {
+            subject: "my_crate::domain".into(),
+            max_public_items: 3,
+        }
+    }
+
+    #[test]
+    fn test_module_above_the_limit_is_reported() {
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            r#"pub struct Order;
+pub enum Status { Open }
+pub trait Repository {}
+pub const MAX_LINES: usize = 10;
+pub fn total() -> u32 { 0 }
+"#,
+        );
+
+        assert_eq!(
+            rule().apply(&file),
+            Err(
+                "Module my_crate::domain has 5 public items, more than 3 in file://src/domain.rs"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_module_within_the_limit() {
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            r#"pub struct Order;
+pub fn total() -> u32 { 0 }
+pub(crate) fn helper() {}
+struct Line;
+pub use crate::shared::Money;
+pub mod lines {}
+"#,
+        );
+
+        assert_eq!(rule().apply(&file), Ok(()));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::rule::ProjectRule;
+use crate::rule::{ProjectRule, RuleDescription};
 use crate::rust_project::RustProject;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
@@ -7,6 +7,17 @@ pub struct MustNotHaveCircularDependencies {
     pub max_depth: usize,
 }
 
+impl MustNotHaveCircularDependencies {
+    /// The same rule, with dependencies on re-exported items counting as dependencies on their
+    /// origin, see `RustProject::to_dependency_graph_through_reexports`.
+    pub fn through_reexports(self) -> MustNotHaveCircularDependenciesThroughReexports {
+        MustNotHaveCircularDependenciesThroughReexports(self)
+    }
+}
+
+/// See `MustNotHaveCircularDependencies::through_reexports`.
+pub struct MustNotHaveCircularDependenciesThroughReexports(MustNotHaveCircularDependencies);
+
 impl Display for MustNotHaveCircularDependencies {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -18,22 +29,63 @@ impl Display for MustNotHaveCircularDependencies {
 }
 
 impl ProjectRule for MustNotHaveCircularDependencies {
+    fn name(&self) -> &'static str {
+        "must_not_have_circular_dependencies"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription::of_kind(self.name())
+    }
+
     fn apply(&self, project: &RustProject) -> Result<(), String> {
-        let graph = project.to_dependency_graph();
+        check_cycles(&project.to_dependency_graph(), self.max_depth)
+    }
+}
 
-        let cycles = find_all_cycles_in_dependencies(&graph, self.max_depth);
+impl Display for MustNotHaveCircularDependenciesThroughReexports {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, through re-exports", self.0)
+    }
+}
 
-        if !cycles.is_empty() {
-            return Err(format!(
-                "Circular dependencies detected:\n{}",
-                cycles.join("\n")
-            ));
-        }
+impl ProjectRule for MustNotHaveCircularDependenciesThroughReexports {
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
 
-        Ok(())
+    fn describe(&self) -> RuleDescription {
+        self.0.describe()
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        check_cycles(
+            &project.to_dependency_graph_through_reexports(),
+            self.0.max_depth,
+        )
     }
 }
 
+fn check_cycles(graph: &HashMap<String, Vec<String>>, max_depth: usize) -> Result<(), String> {
+    let cycles = find_all_cycles_in_dependencies(graph, max_depth);
+
+    if !cycles.is_empty() {
+        return Err(format!(
+            "Circular dependencies detected:\n{}",
+            cycles.join("\n")
+        ));
+    }
+
+    Ok(())
+}
+
 pub fn find_all_cycles_in_dependencies(
     graph: &HashMap<String, Vec<String>>,
     max_depth: usize,
@@ -55,8 +107,9 @@ pub fn find_all_cycles_in_dependencies(
     // Process each SCC to extract cycles
     for scc in sccs {
         if scc.len() > 1 {
-            let cycle_path = reconstruct_cycle_path(&scc, &nodes, &adjacency_list);
-            cycles.push(cycle_path);
+            if let Some(cycle_path) = reconstruct_cycle_path(&scc, &nodes, &adjacency_list) {
+                cycles.push(cycle_path);
+            }
         } else if scc.len() == 1 {
             let only_node = scc[0];
             if adjacency_list[only_node].contains(&only_node) {
@@ -69,6 +122,7 @@ pub fn find_all_cycles_in_dependencies(
         }
     }
 
+    cycles.sort();
     cycles
 }
 
@@ -85,6 +139,8 @@ fn build_adjacency_list(
                 adjacency_list[i].push(j);
             }
         }
+        // Visit dependencies in name order
+        adjacency_list[i].sort_unstable();
     }
 
     adjacency_list
@@ -99,6 +155,7 @@ fn tarjan_scc(adjacency_list: &[Vec<usize>]) -> Vec<Vec<usize>> {
     let mut lowlink = vec![-1; n];
     let mut sccs = Vec::new();
 
+    #[allow(clippy::too_many_arguments)]
     fn strongconnect(
         v: usize,
         index: &mut i32,
@@ -165,26 +222,29 @@ fn tarjan_scc(adjacency_list: &[Vec<usize>]) -> Vec<Vec<usize>> {
     sccs
 }
 
-fn reconstruct_cycle_path(scc: &[usize], nodes: &[&str], adjacency_list: &[Vec<usize>]) -> String {
+/// Reads a cycle through every node of `scc` as `A -> B -> A`, starting from the node with
+/// the smallest name so that the same cycle is always reported the same way.
+/// Returns `None` for pure self-references.
+fn reconstruct_cycle_path(
+    scc: &[usize],
+    nodes: &[&str],
+    adjacency_list: &[Vec<usize>],
+) -> Option<String> {
     if scc.len() == 1 {
         let v = scc[0];
-        if adjacency_list[v].contains(&v) {
-            if adjacency_list[v].len() == 1 {
-                // Ignore pure self-references
-                println!("Ignoring reconstruction of self-cycle: {}", nodes[v]);
-                return "".to_string();
-            }
-            return format!("{} -> {}", nodes[v], nodes[v]);
+        if adjacency_list[v].contains(&v) && adjacency_list[v].len() > 1 {
+            return Some(format!("{} -> {}", nodes[v], nodes[v]));
         }
+        return None;
     }
 
-    // Proceed with normal cycle reconstruction...
     let scc_set: HashSet<usize> = scc.iter().cloned().collect();
-    let start = scc[0];
+    // Nodes are indexed in name order
+    let start = *scc.iter().min()?;
 
     let mut path = Vec::new();
     let mut visited = HashSet::new();
-    if let Some(cycle) = dfs_cycle_reconstruct(
+    dfs_cycle_reconstruct(
         start,
         start,
         adjacency_list,
@@ -192,11 +252,8 @@ fn reconstruct_cycle_path(scc: &[usize], nodes: &[&str], adjacency_list: &[Vec<u
         &mut path,
         &mut visited,
         nodes,
-    ) {
-        return cycle;
-    }
-
-    "".to_string()
+    )
+    .filter(|cycle| !cycle.is_empty())
 }
 
 fn dfs_cycle_reconstruct(
@@ -443,12 +500,12 @@ mod tests {
 
         assert!(
             !cycles.is_empty(),
-            "A real cycle (`Y -> X -> Y`) should be detected even if `X` has a self-reference."
+            "A real cycle (`X -> Y -> X`) should be detected even if `X` has a self-reference."
         );
 
         assert!(
-            cycles.iter().any(|c| c.contains("Y -> X -> Y")),
-            "The detected cycle should include `Y -> X -> Y`."
+            cycles.iter().any(|c| c.contains("X -> Y -> X")),
+            "The detected cycle should include `X -> Y -> X`."
         );
 
         assert!(
@@ -494,8 +551,8 @@ mod tests {
         );
 
         assert!(
-            cycles.iter().any(|c| c.contains("B -> A -> B")),
-            "The detected cycle should include `B -> A -> B`."
+            cycles.iter().any(|c| c.contains("A -> B -> A")),
+            "The detected cycle should include `A -> B -> A`."
         );
     }
 
@@ -514,4 +571,66 @@ mod tests {
             "No cycles should be detected in an acyclic graph."
         );
     }
+
+    /// **Test: Cycles are never empty and always start from the smallest node**
+    #[test]
+    fn test_cycles_are_stable_and_non_empty() {
+        for _ in 0..20 {
+            let mut graph = HashMap::new();
+            graph.insert("crate::c".to_string(), vec!["crate::a".to_string()]);
+            graph.insert("crate::b".to_string(), vec!["crate::c".to_string()]);
+            graph.insert(
+                "crate::a".to_string(),
+                vec!["crate::b".to_string(), "crate::d".to_string()],
+            );
+            graph.insert("crate::d".to_string(), vec!["crate::d".to_string()]);
+            graph.insert("crate::y".to_string(), vec!["crate::x".to_string()]);
+            graph.insert("crate::x".to_string(), vec!["crate::y".to_string()]);
+
+            let cycles = find_all_cycles_in_dependencies(&graph, 3);
+
+            assert!(cycles.iter().all(|cycle| !cycle.is_empty()));
+            assert_eq!(
+                cycles,
+                vec![
+                    "crate::a -> crate::b -> crate::c -> crate::a",
+                    "crate::x -> crate::y -> crate::x",
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_cycle_hidden_behind_a_reexport() {
+        use crate::rust_file::RustFile;
+
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content("src/web.rs", "my_crate::web", "use crate::api::Thing;\n"),
+                RustFile::from_content(
+                    "src/api.rs",
+                    "my_crate::api",
+                    "pub use crate::domain::Thing;\n",
+                ),
+                RustFile::from_content(
+                    "src/domain.rs",
+                    "my_crate::domain",
+                    "use crate::web::Handler;\npub struct Thing;\n",
+                ),
+            ],
+        };
+        let rule = MustNotHaveCircularDependencies { max_depth: 3 };
+
+        // The direct web <-> domain cycle only shows through the facade
+        assert_eq!(
+            rule.apply(&project),
+            Err("Circular dependencies detected:\nmy_crate::api -> my_crate::domain -> my_crate::web -> my_crate::api".to_string())
+        );
+        assert_eq!(
+            MustNotHaveCircularDependencies { max_depth: 3 }
+                .through_reexports()
+                .apply(&project),
+            Err("Circular dependencies detected:\nmy_crate::domain -> my_crate::web -> my_crate::domain".to_string())
+        );
+    }
 }
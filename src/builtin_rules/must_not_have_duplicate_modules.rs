@@ -0,0 +1,107 @@
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Reports the logical paths defined by more than one file, e.g. `foo.rs` next to `foo/mod.rs`
+/// or a `#[path]` mistake, which the dependency graph would otherwise silently merge.
+///
+/// `src/main.rs` is the root of a binary crate sharing the library's name, so it never
+/// conflicts with `src/lib.rs`.
+pub struct MustNotHaveDuplicateModules;
+
+impl Display for MustNotHaveDuplicateModules {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Every module must be defined by a single file")
+    }
+}
+
+impl ProjectRule for MustNotHaveDuplicateModules {
+    fn name(&self) -> &'static str {
+        "must_not_have_duplicate_modules"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription::of_kind(self.name())
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let mut files_by_module: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for file in &project.files {
+            let is_binary_root = file.logical_path == file.crate_name
+                && Path::new(&file.path)
+                    .file_name()
+                    .is_some_and(|name| name == "main.rs");
+            if !is_binary_root {
+                files_by_module
+                    .entry(&file.logical_path)
+                    .or_default()
+                    .push(&file.path);
+            }
+        }
+
+        let duplicates: Vec<String> = files_by_module
+            .into_iter()
+            .filter(|(_, files)| files.len() > 1)
+            .map(|(module, files)| {
+                let files: Vec<String> = files
+                    .iter()
+                    .map(|path| format!("file://{}", path))
+                    .collect();
+                format!("{}: {}", module, files.join(", "))
+            })
+            .collect();
+
+        if !duplicates.is_empty() {
+            return Err(format!(
+                "Modules defined by several files:\n{}",
+                duplicates.join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    #[test]
+    fn test_files_mapping_to_the_same_module_are_reported() {
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content("src/lib.rs", "my_crate", "mod orders;\n"),
+                RustFile::from_content("src/main.rs", "my_crate", "fn main() {}\n"),
+                RustFile::from_content("src/orders.rs", "my_crate::orders", ""),
+                RustFile::from_content("src/orders/mod.rs", "my_crate::orders", ""),
+                RustFile::from_content("src/billing.rs", "my_crate::billing", ""),
+            ],
+        };
+
+        assert_eq!(
+            MustNotHaveDuplicateModules.apply(&project),
+            Err("Modules defined by several files:\n\
+                 my_crate::orders: file://src/orders.rs, file://src/orders/mod.rs"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_library_and_binary_roots_are_distinct() {
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content("src/lib.rs", "my_crate", ""),
+                RustFile::from_content("src/main.rs", "my_crate", "fn main() {}\n"),
+            ],
+        };
+
+        assert_eq!(MustNotHaveDuplicateModules.apply(&project), Ok(()));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::builtin_rules::utils::IsChild;
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// Modules used as entry points unless configured otherwise.
+pub const DEFAULT_ROOTS: [&str; 3] = ["lib", "main", "bin"];
+
+/// Reports the scanned modules no other module depends on, except the entry points.
+/// Crate roots (`lib.rs`, `main.rs`) are always entry points.
+///
+/// A root containing `::` matches the modules under it; a single segment (e.g. `lib`, `bin`)
+/// matches any module having that segment in its path.
+pub struct MustNotHaveOrphanModules {
+    pub roots: Vec<String>,
+    /// When `false`, modules with a `tests` segment are never reported.
+    pub include_test_modules: bool,
+}
+
+impl MustNotHaveOrphanModules {
+    pub fn new(roots: &[&str]) -> Self {
+        Self {
+            roots: roots.iter().map(|root| root.to_string()).collect(),
+            include_test_modules: false,
+        }
+    }
+
+    pub fn including_test_modules(self) -> Self {
+        Self {
+            include_test_modules: true,
+            ..self
+        }
+    }
+
+    fn is_root(&self, module: &str) -> bool {
+        if !module.contains("::") {
+            return true;
+        }
+
+        self.roots.iter().any(|root| {
+            if root.contains("::") {
+                module.is_child_of(root)
+            } else {
+                module.split("::").skip(1).any(|segment| segment == root)
+            }
+        })
+    }
+
+    fn is_test_module(module: &str) -> bool {
+        module.split("::").skip(1).any(|segment| segment == "tests")
+    }
+}
+
+impl Default for MustNotHaveOrphanModules {
+    fn default() -> Self {
+        Self::new(&DEFAULT_ROOTS)
+    }
+}
+
+impl Display for MustNotHaveOrphanModules {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Every module except [{}] must be depended on",
+            self.roots.join(", ")
+        )
+    }
+}
+
+impl ProjectRule for MustNotHaveOrphanModules {
+    fn name(&self) -> &'static str {
+        "must_not_have_orphan_modules"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            allowed: self.roots.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let graph = project.to_dependency_graph();
+
+        let depended_on: BTreeSet<&str> = graph
+            .iter()
+            .flat_map(|(module, dependencies)| {
+                dependencies
+                    .iter()
+                    .filter(move |dependency| *dependency != module)
+            })
+            .map(String::as_str)
+            .collect();
+
+        let orphans: BTreeSet<&str> = project
+            .files
+            .iter()
+            .map(|file| file.logical_path.as_str())
+            .filter(|module| !depended_on.contains(module))
+            .filter(|module| !self.is_root(module))
+            .filter(|module| self.include_test_modules || !Self::is_test_module(module))
+            .collect();
+
+        if !orphans.is_empty() {
+            return Err(format!(
+                "Orphan modules detected:\n{}",
+                orphans.into_iter().collect::<Vec<_>>().join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    fn project() -> RustProject {
+        RustProject {
+            files: vec![
+                RustFile::from_content("src/lib.rs", "my_crate", "use crate::service::run;"),
+                RustFile::from_content(
+                    "src/service.rs",
+                    "my_crate::service",
+                    "use crate::domain::User; pub fn run() {}",
+                ),
+                RustFile::from_content("src/domain.rs", "my_crate::domain", "pub struct User;"),
+                RustFile::from_content("src/legacy.rs", "my_crate::legacy", "pub fn old() {}"),
+                RustFile::from_content(
+                    "tests/service.rs",
+                    "my_crate::tests::service",
+                    "use my_crate::service::run;",
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_isolated_module_is_reported() {
+        let result = MustNotHaveOrphanModules::default().apply(&project());
+
+        assert_eq!(
+            result,
+            Err("Orphan modules detected:\nmy_crate::legacy".to_string())
+        );
+    }
+
+    #[test]
+    fn test_configured_roots_are_not_reported() {
+        let rule = MustNotHaveOrphanModules::new(&["my_crate::legacy"]);
+
+        assert!(rule.apply(&project()).is_ok());
+    }
+
+    #[test]
+    fn test_test_modules_can_be_counted() {
+        let rule = MustNotHaveOrphanModules::default().including_test_modules();
+
+        assert_eq!(
+            rule.apply(&project()),
+            Err("Orphan modules detected:\nmy_crate::legacy\nmy_crate::tests::service".to_string())
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::IsChild;
+use crate::rule::{LocatedViolation, Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use proc_macro2::{TokenStream, TokenTree};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use syn::visit::{self, Visit};
+use syn::{Ident, ItemUse, Macro, MetaList, UseTree, Visibility};
+
+/// Flags `use` items whose imported name is never referenced in the rest of the file:
+/// they add a dependency edge without any actual coupling.
+///
+/// Only imports from the subject's own crate are checked: a trait imported to call its methods,
+/// like `std::io::Write`, is never named again, so imports of other crates can't be told apart
+/// from unused ones. Re-exports like `pub use` or `pub(crate) use` are meant for other modules
+/// and are skipped, as are glob imports and `as _` renames. Crate traits imported only to call
+/// their methods are still reported.
+#[derive(Debug)]
+pub struct MustNotHaveUnusedImportsRule {
+    pub subject: Subject,
+}
+
+impl Display for MustNotHaveUnusedImportsRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustNotHaveUnusedImportsRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must not have unused imports",
+            styling.subject(&self.subject)
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_have_unused_imports"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        self.apply_located(file).map_err(|(message, _)| message)
+    }
+
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        let mut visitor = ImportsVisitor::new(&file.logical_path);
+        visitor.visit_file(&file.ast);
+
+        let unused_imports: Vec<String> = visitor
+            .imports
+            .into_iter()
+            .filter(|(name, path)| {
+                !visitor.references.contains(name) && path.is_child_of(&file.crate_name)
+            })
+            .map(|(_, path)| path)
+            .collect();
+
+        match unused_imports.first() {
+            None => Ok(()),
+            Some(first) => Err((
+                format!(
+                    "Unused imports [{}] in file://{}",
+                    unused_imports.join(", "),
+                    file.location_of(first)
+                ),
+                file.line_of(first),
+            )),
+        }
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+/// Collects the names brought in by `use` items and every identifier used elsewhere.
+struct ImportsVisitor {
+    /// The logical path of the visited file, against which `crate`, `self` and `super` resolve.
+    current_module: String,
+    /// Imported name and the resolved path it was imported from.
+    imports: Vec<(String, String)>,
+    references: HashSet<String>,
+}
+
+impl ImportsVisitor {
+    fn new(current_module: &str) -> Self {
+        ImportsVisitor {
+            current_module: current_module.to_string(),
+            imports: vec![],
+            references: HashSet::new(),
+        }
+    }
+
+    fn collect_imports(&mut self, tree: &UseTree, prefix: &str) {
+        let join = |ident: &Ident| {
+            if prefix.is_empty() {
+                ident.to_string()
+            } else {
+                format!("{}::{}", prefix, ident)
+            }
+        };
+
+        match tree {
+            UseTree::Path(use_path) => {
+                let base = if prefix.is_empty() {
+                    self.current_module.as_str()
+                } else {
+                    prefix
+                };
+                let path = match use_path.ident.to_string().as_str() {
+                    "crate" => base.split("::").next().unwrap_or(base).to_string(),
+                    "self" => base.to_string(),
+                    "super" => base
+                        .rsplit_once("::")
+                        .map(|(parent, _)| parent.to_string())
+                        .unwrap_or_default(),
+                    _ => join(&use_path.ident),
+                };
+                self.collect_imports(&use_path.tree, &path);
+            }
+            UseTree::Group(group) => {
+                for item in &group.items {
+                    self.collect_imports(item, prefix);
+                }
+            }
+            UseTree::Name(use_name) if use_name.ident == "self" => {
+                if let Some(name) = prefix.rsplit("::").next() {
+                    self.imports.push((name.to_string(), prefix.to_string()));
+                }
+            }
+            UseTree::Name(use_name) => {
+                self.imports
+                    .push((use_name.ident.to_string(), join(&use_name.ident)));
+            }
+            UseTree::Rename(rename) if rename.rename != "_" => {
+                self.imports
+                    .push((rename.rename.to_string(), join(&rename.ident)));
+            }
+            UseTree::Rename(_) | UseTree::Glob(_) => {}
+        }
+    }
+
+    fn collect_tokens(&mut self, tokens: TokenStream) {
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) => {
+                    self.references.insert(ident.to_string());
+                }
+                TokenTree::Group(group) => self.collect_tokens(group.stream()),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ImportsVisitor {
+    fn visit_item_use(&mut self, node: &'ast ItemUse) {
+        if let Visibility::Inherited = node.vis {
+            self.collect_imports(&node.tree, "");
+        }
+    }
+
+    fn visit_ident(&mut self, node: &'ast Ident) {
+        self.references.insert(node.to_string());
+    }
+
+    fn visit_macro(&mut self, node: &'ast Macro) {
+        self.collect_tokens(node.tokens.clone());
+        visit::visit_macro(self, node);
+    }
+
+    fn visit_meta_list(&mut self, node: &'ast MetaList) {
+        self.collect_tokens(node.tokens.clone());
+        visit::visit_meta_list(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> MustNotHaveUnusedImportsRule {
+        MustNotHaveUnusedImportsRule {
+            subject: "my_crate::application".into(),
+        }
+    }
+
+    #[test]
+    fn test_unused_import_is_flagged() {
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            r#"
+            use crate::domain::User;
+            use crate::infrastructure::X;
+
+            pub fn load() -> User { todo!() }
+            "#,
+        );
+
+        let expected =
+            "Unused imports [my_crate::infrastructure::X] in file://src/application.rs:3"
+                .to_string();
+        assert_eq!(rule().apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_imports_used_in_bodies_macros_and_attributes() {
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            r#"
+            use crate::domain::{self, Email};
+            use crate::infrastructure::Repository as Repo;
+            use serde::Serialize;
+            use std::io::Write as _;
+            use crate::prelude::*;
+
+            #[derive(Serialize)]
+            pub struct Command;
+
+            pub fn run() {
+                let repo = Repo::new();
+                println!("{:?}", Email::parse("a@b.c"));
+                domain::validate();
+            }
+            "#,
+        );
+
+        assert!(rule().apply(&file).is_ok());
+    }
+
+    #[test]
+    fn test_traits_of_other_crates_used_for_their_methods() {
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            r#"
+            use std::io::Write;
+            use super::shared::Clock;
+
+            pub fn log(w: &mut Vec<u8>) {
+                w.write_all(b"done").unwrap();
+            }
+            "#,
+        );
+
+        let expected =
+            "Unused imports [my_crate::shared::Clock] in file://src/application.rs:3".to_string();
+        assert_eq!(rule().apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_reexports_of_a_facade_are_not_imports() {
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            r#"
+            pub use crate::domain::User;
+            pub(crate) use crate::infrastructure::Repository;
+            use crate::infrastructure::X;
+            "#,
+        );
+
+        let expected =
+            "Unused imports [my_crate::infrastructure::X] in file://src/application.rs:4"
+                .to_string();
+        assert_eq!(rule().apply(&file), Err(expected));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::IsChild;
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// Forbids a module from re-exporting (`pub use`) items of the given modules,
+/// so that a façade can't leak a lower layer to its clients.
+#[derive(Debug)]
+pub struct MustNotReexportFromRule {
+    pub subject: Subject,
+    pub forbidden_modules: Vec<String>,
+}
+
+impl Display for MustNotReexportFromRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustNotReexportFromRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must not re-export from {}",
+            styling.subject(&self.subject),
+            styling.subject("[".to_string() + &self.forbidden_modules.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_reexport_from"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            forbidden: self.forbidden_modules.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let forbidden_reexports: Vec<String> = file
+            .reexports
+            .iter()
+            .filter(|&reexport| {
+                self.forbidden_modules
+                    .iter()
+                    .any(|forbidden| reexport.is_child_of(forbidden))
+            })
+            .cloned()
+            .collect();
+
+        if forbidden_reexports.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Forbidden re-exports of [{}] in file://{}",
+                forbidden_reexports.join(", "),
+                file.path
+            ))
+        }
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> MustNotReexportFromRule {
+        MustNotReexportFromRule {
+            subject: "my_crate::api".into(),
+            forbidden_modules: vec!["my_crate::infrastructure".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_reexport_crossing_a_forbidden_boundary() {
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            r#"
+            pub use crate::domain::User;
+            pub use crate::infrastructure::PostgresRepository;
+            "#,
+        );
+
+        let expected = "Forbidden re-exports of [my_crate::infrastructure::PostgresRepository] in file://src/api.rs".to_string();
+        assert_eq!(rule().apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_private_use_is_not_a_reexport() {
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            r#"
+            use crate::infrastructure::PostgresRepository;
+            "#,
+        );
+
+        assert!(rule().apply(&file).is_ok());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::builtin_rules::subject::Subject;
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+use syn::visit::Visit;
+use syn::{ItemUse, UseTree, Visibility};
+
+/// Forbids the subject from re-exporting with a wildcard (`pub use crate::internal::*`), which
+/// exposes a module's whole surface and hides what is actually part of the API.
+///
+/// Only `pub` glob imports are reported: private and `pub(crate)` ones don't reach the clients.
+#[derive(Debug)]
+pub struct MustNotReexportGlobsRule {
+    pub subject: Subject,
+}
+
+impl Display for MustNotReexportGlobsRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustNotReexportGlobsRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must not re-export with wildcards",
+            styling.subject(&self.subject)
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_reexport_globs"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let mut visitor = GlobReexportsVisitor::default();
+        visitor.visit_file(&file.ast);
+
+        if visitor.globs.is_empty() {
+            return Ok(());
+        }
+
+        let globs: Vec<String> = visitor
+            .globs
+            .iter()
+            .map(|(glob, line)| format!("{} in file://{}:{}", glob, file.path, line))
+            .collect();
+
+        Err(format!("Wildcard re-exports {}", globs.join(", ")))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+/// Collects the glob paths of `pub use` items, with their line.
+#[derive(Default)]
+struct GlobReexportsVisitor {
+    globs: Vec<(String, usize)>,
+}
+
+impl GlobReexportsVisitor {
+    fn collect_globs(&mut self, tree: &UseTree, prefix: &str, line: usize) {
+        match tree {
+            UseTree::Path(use_path) => {
+                let prefix = if prefix.is_empty() {
+                    use_path.ident.to_string()
+                } else {
+                    format!("{}::{}", prefix, use_path.ident)
+                };
+                self.collect_globs(&use_path.tree, &prefix, line);
+            }
+            UseTree::Group(group) => {
+                for item in &group.items {
+                    self.collect_globs(item, prefix, line);
+                }
+            }
+            UseTree::Glob(_) => self.globs.push((format!("{}::*", prefix), line)),
+            UseTree::Name(_) | UseTree::Rename(_) => {}
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for GlobReexportsVisitor {
+    fn visit_item_use(&mut self, node: &'ast ItemUse) {
+        if matches!(node.vis, Visibility::Public(_)) {
+            self.collect_globs(&node.tree, "", node.use_token.span.start().line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> MustNotReexportGlobsRule {
+        MustNotReexportGlobsRule {
+            subject: "my_crate::api".into(),
+        }
+    }
+
+    #[test]
+    fn test_pub_glob_reexport_is_reported() {
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            "pub use crate::internal::*;\nuse crate::domain::*;\npub use crate::model::{Order, events::*};\n",
+        );
+
+        let expected = "Wildcard re-exports crate::internal::* in file://src/api.rs:1, crate::model::events::* in file://src/api.rs:3".to_string();
+        assert_eq!(rule().apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_non_pub_globs_are_allowed() {
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            "use crate::domain::*;\npub(crate) use crate::internal::*;\npub use crate::model::Order;\n",
+        );
+
+        assert!(rule().apply(&file).is_ok());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::builtin_rules::subject::Subject;
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+use syn::visit::{self, Visit};
+use syn::{ExprAsync, ExprAwait, Signature};
+
+/// Keeps the subject sync-only, e.g. a pure domain layer with async confined to infrastructure:
+/// `async fn`, `async` blocks and `.await` are forbidden.
+///
+/// Like other macro arguments, async code inside macro invocations isn't parsed and goes unnoticed.
+#[derive(Debug)]
+pub struct MustNotUseAsyncRule {
+    pub subject: Subject,
+}
+
+impl Display for MustNotUseAsyncRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustNotUseAsyncRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!("{} must not use async code", styling.subject(&self.subject))
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_use_async"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            forbidden: vec!["async".to_string()],
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let mut visitor = AsyncVisitor { usages: vec![] };
+        visitor.visit_file(&file.ast);
+
+        if visitor.usages.is_empty() {
+            return Ok(());
+        }
+
+        let usages: Vec<String> = visitor
+            .usages
+            .iter()
+            .map(|(usage, line)| format!("{} in file://{}:{}", usage, file.path, line))
+            .collect();
+
+        Err(format!("Forbidden async code {}", usages.join(", ")))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+/// Collects the async functions, blocks and awaits, with their line.
+struct AsyncVisitor {
+    usages: Vec<(String, usize)>,
+}
+
+impl<'ast> Visit<'ast> for AsyncVisitor {
+    fn visit_signature(&mut self, node: &'ast Signature) {
+        if let Some(asyncness) = &node.asyncness {
+            self.usages.push((
+                format!("async fn {}", node.ident),
+                asyncness.span.start().line,
+            ));
+        }
+
+        visit::visit_signature(self, node);
+    }
+
+    fn visit_expr_async(&mut self, node: &'ast ExprAsync) {
+        self.usages.push((
+            "async block".to_string(),
+            node.async_token.span.start().line,
+        ));
+
+        visit::visit_expr_async(self, node);
+    }
+
+    fn visit_expr_await(&mut self, node: &'ast ExprAwait) {
+        // The operand comes first in the source, e.g. `load(id).await` awaiting inside `load`
+        visit::visit_expr_await(self, node);
+
+        self.usages
+            .push((".await".to_string(), node.await_token.span.start().line));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> MustNotUseAsyncRule {
+        MustNotUseAsyncRule {
+            subject: "my_crate::domain".into(),
+        }
+    }
+
+    #[test]
+    fn test_async_code_in_the_domain_is_forbidden() {
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            r#"pub struct Order;
+
+pub async fn load(id: u32) -> Order {
+    fetch(id).await
+}
+"#,
+        );
+
+        assert_eq!(
+            rule().apply(&file),
+            Err("Forbidden async code async fn load in file://src/domain.rs:3, .await in file://src/domain.rs:4".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sync_code_is_allowed() {
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            "pub fn total(prices: &[u32]) -> u32 { prices.iter().sum() }\n",
+        );
+
+        assert_eq!(rule().apply(&file), Ok(()));
+    }
+}
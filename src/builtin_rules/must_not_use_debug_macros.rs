@@ -0,0 +1,148 @@
+use crate::builtin_rules::subject::Subject;
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+use syn::visit::{self, Visit};
+use syn::Macro;
+
+/// The macros forbidden by default: `dbg!` is never meant to be committed.
+pub const DEFAULT_DEBUG_MACROS: &[&str] = &["dbg"];
+
+/// Forbids the subject from invoking the given macros, e.g. `dbg`, `print`, `println`, `eprint`
+/// and `eprintln`, so stray debug output doesn't end up in library code.
+///
+/// Macros are matched by the last segment of their path, so `std::dbg!` counts. Invocations
+/// nested in the arguments of other macros, like `vec![dbg!(x)]`, aren't parsed and go unnoticed.
+#[derive(Debug)]
+pub struct MustNotUseDebugMacrosRule {
+    pub subject: Subject,
+    pub forbidden_macros: Vec<String>,
+}
+
+impl Display for MustNotUseDebugMacrosRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustNotUseDebugMacrosRule {
+    fn render(&self, styling: &Styling) -> String {
+        let macros: Vec<String> = self
+            .forbidden_macros
+            .iter()
+            .map(|name| format!("{}!", name))
+            .collect();
+        format!(
+            "{} must not use {}",
+            styling.subject(&self.subject),
+            styling.subject("[".to_string() + &macros.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_use_debug_macros"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            forbidden: self.forbidden_macros.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let mut visitor = MacrosVisitor {
+            forbidden_macros: &self.forbidden_macros,
+            invocations: vec![],
+        };
+        visitor.visit_file(&file.ast);
+
+        if visitor.invocations.is_empty() {
+            return Ok(());
+        }
+
+        let invocations: Vec<String> = visitor
+            .invocations
+            .iter()
+            .map(|(name, line)| format!("{}! in file://{}:{}", name, file.path, line))
+            .collect();
+
+        Err(format!("Forbidden macros {}", invocations.join(", ")))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+/// Collects the invocations of the forbidden macros, with their line.
+struct MacrosVisitor<'a> {
+    forbidden_macros: &'a [String],
+    invocations: Vec<(String, usize)>,
+}
+
+impl<'ast> Visit<'ast> for MacrosVisitor<'_> {
+    fn visit_macro(&mut self, node: &'ast Macro) {
+        if let Some(segment) = node.path.segments.last() {
+            let name = segment.ident.to_string();
+            if self.forbidden_macros.contains(&name) {
+                self.invocations
+                    .push((name, segment.ident.span().start().line));
+            }
+        }
+
+        visit::visit_macro(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn domain_file() -> RustFile {
+        RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            r#"pub fn total(prices: &[u32]) -> u32 {
+    let total = dbg!(prices.iter().sum());
+    println!("total: {}", total);
+    std::dbg!(total)
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn test_dbg_is_forbidden_by_default() {
+        let rule = MustNotUseDebugMacrosRule {
+            subject: "my_crate::domain".into(),
+            forbidden_macros: DEFAULT_DEBUG_MACROS
+                .iter()
+                .map(|&m| m.to_string())
+                .collect(),
+        };
+
+        assert_eq!(
+            rule.apply(&domain_file()),
+            Err(
+                "Forbidden macros dbg! in file://src/domain.rs:2, dbg! in file://src/domain.rs:4"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_configured_macros_are_forbidden() {
+        let rule = MustNotUseDebugMacrosRule {
+            subject: "my_crate::domain".into(),
+            forbidden_macros: vec!["println".to_string(), "eprintln".to_string()],
+        };
+
+        assert_eq!(
+            rule.apply(&domain_file()),
+            Err("Forbidden macros println! in file://src/domain.rs:3".to_string())
+        );
+    }
+}
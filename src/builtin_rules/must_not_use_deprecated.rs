@@ -0,0 +1,147 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::IsChild;
+use crate::rule::{LocatedViolation, Rule, RuleDescription, Severity};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Flags the subject's dependencies on modules being phased out, suggesting their replacement
+/// when one is configured in `replacements` (keyed by deprecated module).
+///
+/// Violations are warnings: the rule points at the code to migrate without failing the check.
+#[derive(Debug)]
+pub struct MustNotUseDeprecatedRule {
+    pub subject: Subject,
+    pub deprecated_modules: Vec<String>,
+    pub replacements: BTreeMap<String, String>,
+}
+
+impl MustNotUseDeprecatedRule {
+    /// The deprecated module `dependency` belongs to, if any.
+    fn deprecated_module_of(&self, dependency: &str) -> Option<&String> {
+        self.deprecated_modules
+            .iter()
+            .find(|module| dependency.is_child_of(module))
+    }
+}
+
+impl Display for MustNotUseDeprecatedRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustNotUseDeprecatedRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must not use deprecated {}",
+            styling.subject(&self.subject),
+            styling.subject("[".to_string() + &self.deprecated_modules.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_not_use_deprecated"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            forbidden: self.deprecated_modules.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        self.apply_located(file).map_err(|(message, _)| message)
+    }
+
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        let deprecated: Vec<(&String, &String)> = file
+            .dependencies
+            .iter()
+            .filter_map(|dependency| Some((dependency, self.deprecated_module_of(dependency)?)))
+            .collect();
+
+        let Some((first, _)) = deprecated.first() else {
+            return Ok(());
+        };
+
+        let hints: Vec<String> = deprecated
+            .iter()
+            .map(
+                |&(dependency, module)| match self.replacements.get(module) {
+                    Some(replacement) => format!("{} (use {} instead)", dependency, replacement),
+                    None => dependency.to_string(),
+                },
+            )
+            .collect();
+
+        Err((
+            format!(
+                "Deprecated dependencies to [{}] in file://{}",
+                hints.join(", "),
+                file.location_of(first)
+            ),
+            file.line_of(first),
+        ))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> MustNotUseDeprecatedRule {
+        MustNotUseDeprecatedRule {
+            subject: "my_crate".into(),
+            deprecated_modules: vec![
+                "my_crate::legacy_db".to_string(),
+                "my_crate::old_auth".to_string(),
+            ],
+            replacements: BTreeMap::from([(
+                "my_crate::legacy_db".to_string(),
+                "my_crate::storage".to_string(),
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_deprecated_dependency_suggests_its_replacement() {
+        let file = RustFile::from_content(
+            "src/orders.rs",
+            "my_crate::orders",
+            "use crate::storage::Pool;\nuse crate::legacy_db::Connection;\nuse crate::old_auth::Token;\n",
+        );
+
+        assert_eq!(
+            rule().apply(&file),
+            Err("Deprecated dependencies to [my_crate::legacy_db::Connection (use my_crate::storage instead), my_crate::old_auth::Token] in file://src/orders.rs:2".to_string())
+        );
+        assert_eq!(rule().severity(), Severity::Warning);
+    }
+
+    #[test]
+    fn test_modules_sharing_a_prefix_are_not_deprecated() {
+        let file = RustFile::from_content(
+            "src/orders.rs",
+            "my_crate::orders",
+            "use crate::legacy_db_migration::Step;\n",
+        );
+
+        assert_eq!(rule().apply(&file), Ok(()));
+    }
+}
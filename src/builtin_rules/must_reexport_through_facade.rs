@@ -0,0 +1,142 @@
+use crate::builtin_rules::utils::{resolve_crate_prefix, IsChild};
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use std::fmt::{Display, Formatter};
+
+/// Enforces the façade pattern: the `pub` items of the `internal_submodules` of `component`
+/// (given relative to it, e.g. `internal`) must be re-exported by the component root with
+/// `pub use`. Re-exporting the item, a module containing it, or a glob covering it counts.
+/// A leading `crate` in `component` stands for the crate of each checked file.
+pub struct MustReexportThroughFacade {
+    pub component: String,
+    pub internal_submodules: Vec<String>,
+}
+
+impl Display for MustReexportThroughFacade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} must re-export the public items of [{}]",
+            self.component,
+            self.internal_submodules.join(", ")
+        )
+    }
+}
+
+impl ProjectRule for MustReexportThroughFacade {
+    fn name(&self) -> &'static str {
+        "must_reexport_through_facade"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.component.clone()),
+            forbidden: self
+                .internal_submodules
+                .iter()
+                .map(|submodule| format!("{}::{}", self.component, submodule))
+                .collect(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let mut hidden = Vec::new();
+
+        for file in &project.files {
+            let component = resolve_crate_prefix(&self.component, &file.crate_name);
+            let is_internal = self.internal_submodules.iter().any(|submodule| {
+                file.logical_path
+                    .is_child_of(&format!("{}::{}", component, submodule))
+            });
+            if !is_internal {
+                continue;
+            }
+
+            let reexports: Vec<&String> = project
+                .files
+                .iter()
+                .filter(|facade| facade.logical_path == component)
+                .flat_map(|facade| &facade.reexports)
+                .collect();
+            // `pub use internal::Item` is relative to the façade and isn't resolved by the parser
+            let is_reexported = |path: &str| {
+                reexports.iter().any(|reexport| {
+                    let reexport = reexport.strip_suffix("::*").unwrap_or(reexport);
+                    path.is_child_of(reexport)
+                        || path.is_child_of(&format!("{}::{}", component, reexport))
+                })
+            };
+
+            let public_api = file.public_api();
+            for item in &public_api {
+                // Methods are reached through their type, only the items of the module count
+                let is_method = item
+                    .path
+                    .rsplit_once("::")
+                    .is_some_and(|(parent, _)| public_api.iter().any(|other| other.path == parent));
+                if !is_method && !is_reexported(&item.path) {
+                    hidden.push(format!("{} in file://{}", item.path, file.path));
+                }
+            }
+        }
+
+        if !hidden.is_empty() {
+            return Err(format!(
+                "Public items not re-exported by {}:\n{}",
+                self.component,
+                hidden.join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    fn project(facade: &str) -> RustProject {
+        RustProject {
+            files: vec![
+                RustFile::from_content("src/domain/mod.rs", "my_crate::domain", facade),
+                RustFile::from_content(
+                    "src/domain/internal.rs",
+                    "my_crate::domain::internal",
+                    "pub struct Order;\n\nimpl Order {\n    pub fn new() -> Self { Order }\n}\n\npub struct Secret;\n\nstruct Private;\n",
+                ),
+            ],
+        }
+    }
+
+    fn rule() -> MustReexportThroughFacade {
+        MustReexportThroughFacade {
+            component: "crate::domain".to_string(),
+            internal_submodules: vec!["internal".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_item_not_reexported_is_reported() {
+        let project = project("mod internal;\n\npub use internal::Order;\n");
+
+        assert_eq!(
+            rule().apply(&project),
+            Err("Public items not re-exported by crate::domain:\n\
+                 my_crate::domain::internal::Secret in file://src/domain/internal.rs"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_reexported_items_comply() {
+        let explicit =
+            project("mod internal;\n\npub use self::internal::{Order, Secret as Key};\n");
+        let glob = project("mod internal;\n\npub use internal::*;\n");
+
+        assert_eq!(rule().apply(&explicit), Ok(()));
+        assert_eq!(rule().apply(&glob), Ok(()));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::builtin_rules::subject::Subject;
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// Checks that the files of a module physically live under the expected directory,
+/// catching misplaced files hidden behind misleading `mod` declarations.
+#[derive(Debug)]
+pub struct MustResideInRule {
+    pub subject: Subject,
+    pub expected_directory: String,
+}
+
+impl MustResideInRule {
+    /// Derives the expected directory from the subject: `my_crate::infrastructure::db`
+    /// must reside in `infrastructure/db`.
+    pub fn new(subject: String) -> Self {
+        let expected_directory = subject.split("::").skip(1).collect::<Vec<_>>().join("/");
+
+        Self {
+            subject: subject.into(),
+            expected_directory,
+        }
+    }
+
+    pub fn with_directory(subject: impl Into<Subject>, expected_directory: String) -> Self {
+        Self {
+            subject: subject.into(),
+            expected_directory: expected_directory.trim_matches('/').to_string(),
+        }
+    }
+
+    /// Whether the file at `relative_path` from the crate root is in the expected directory,
+    /// taken from the crate root or from its `src` directory.
+    fn resides_in_expected_directory(&self, relative_path: &str) -> bool {
+        if self.expected_directory.is_empty() {
+            return true;
+        }
+
+        let directory = &self.expected_directory;
+        let path = relative_path.trim_start_matches("./");
+
+        [Some(path), path.strip_prefix("src/")]
+            .into_iter()
+            .flatten()
+            .any(|path| {
+                path.starts_with(&format!("{}/", directory)) || path == format!("{}.rs", directory)
+            })
+    }
+}
+
+impl From<MustResideInRule> for Box<dyn Rule> {
+    fn from(rule: MustResideInRule) -> Self {
+        Box::new(rule)
+    }
+}
+
+impl Display for MustResideInRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for MustResideInRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must reside in {}",
+            styling.subject(&self.subject),
+            styling.subject(&self.expected_directory)
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_reside_in"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            allowed: vec![self.expected_directory.clone()],
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        if self.resides_in_expected_directory(&file.relative_path) {
+            return Ok(());
+        }
+        Err(format!(
+            "Module {} is not located under {} in file://{}",
+            &file.logical_path, &self.expected_directory, file.path
+        ))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    #[test]
+    fn test_expected_directory_is_derived_from_subject() {
+        let rule = MustResideInRule::new("my_crate::infrastructure::db".to_string());
+
+        assert_eq!(rule.expected_directory, "infrastructure/db");
+    }
+
+    #[test]
+    fn test_file_in_expected_directory() {
+        let rule = MustResideInRule::new("sample_project::conversion".to_string());
+
+        let result = rule.apply(&RustFile::from_file_system(
+            "./../rust_arkitect/examples/sample_project/src/conversion/application.rs",
+        ));
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_module_file_named_after_directory() {
+        let rule = MustResideInRule::new("my_crate::infrastructure".to_string());
+
+        let file = RustFile::from_content("src/infrastructure.rs", "my_crate::infrastructure", "");
+
+        assert!(rule.apply(&file).is_ok());
+    }
+
+    #[test]
+    fn test_file_in_wrong_directory() {
+        let rule = MustResideInRule::new("my_crate::infrastructure".to_string());
+
+        let file = RustFile::from_content(
+            "src/domain/repository.rs",
+            "my_crate::infrastructure::repository",
+            "",
+        );
+
+        assert!(rule.is_applicable(&file));
+        assert!(rule.apply(&file).is_err());
+    }
+
+    #[test]
+    fn test_directory_nested_elsewhere_is_not_the_expected_one() {
+        let rule = MustResideInRule::new("my_crate::infrastructure".to_string());
+
+        let file = RustFile::from_content(
+            "src/domain/infrastructure/repository.rs",
+            "my_crate::infrastructure::repository",
+            "",
+        );
+
+        assert!(rule.apply(&file).is_err());
+    }
+
+    #[test]
+    fn test_explicit_directory() {
+        let rule = MustResideInRule::with_directory(
+            "my_crate::adapters".to_string(),
+            "src/infrastructure/".to_string(),
+        );
+
+        let misplaced =
+            RustFile::from_content("src/adapters/http.rs", "my_crate::adapters::http", "");
+        let well_placed =
+            RustFile::from_content("src/infrastructure/http.rs", "my_crate::adapters::http", "");
+
+        assert!(rule.apply(&misplaced).is_err());
+        assert!(rule.apply(&well_placed).is_ok());
+    }
+}
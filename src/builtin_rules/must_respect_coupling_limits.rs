@@ -0,0 +1,239 @@
+use crate::builtin_rules::utils::{resolve_crate_prefix, IsChild};
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+/// The most components a component may be coupled with, unbounded when `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CouplingLimit {
+    /// Afferent coupling (Ca): the components depending on it.
+    pub max_afferent: Option<usize>,
+    /// Efferent coupling (Ce): the components it depends on.
+    pub max_efferent: Option<usize>,
+}
+
+/// Computes the afferent and efferent coupling of components given as `(name, location)` pairs,
+/// e.g. `("domain", "crate::domain")`, and fails when a component exceeds its `limits`.
+///
+/// Modules belong to the component with the longest matching location, and dependencies on
+/// modules outside every component are left out. A leading `crate` in a location stands for
+/// each scanned crate.
+pub struct MustRespectCouplingLimits {
+    pub components: Vec<(String, String)>,
+    pub limits: Vec<(String, CouplingLimit)>,
+}
+
+/// Afferent and efferent coupling of a component.
+#[derive(Debug, Default)]
+struct Coupling<'a> {
+    afferent: BTreeSet<&'a str>,
+    efferent: BTreeSet<&'a str>,
+}
+
+impl Display for Coupling<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (afferent, efferent) = (self.afferent.len(), self.efferent.len());
+        let instability = match afferent + efferent {
+            0 => 0.0,
+            total => efferent as f64 / total as f64,
+        };
+        write!(
+            f,
+            "Ca: {}, Ce: {}, I: {:.2}",
+            afferent, efferent, instability
+        )
+    }
+}
+
+impl MustRespectCouplingLimits {
+    /// The name of the component `module` belongs to, if any.
+    fn component_of(&self, module: &str, crate_names: &BTreeSet<String>) -> Option<&str> {
+        self.components
+            .iter()
+            .flat_map(|(name, location)| {
+                crate_names
+                    .iter()
+                    .map(move |crate_name| (name, resolve_crate_prefix(location, crate_name)))
+            })
+            .filter(|(_, location)| module.is_child_of(location))
+            .max_by_key(|(_, location)| location.len())
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+impl Display for MustRespectCouplingLimits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let limits: Vec<&str> = self.limits.iter().map(|(name, _)| name.as_str()).collect();
+        write!(
+            f,
+            "Components [{}] must respect their coupling limits",
+            limits.join(", ")
+        )
+    }
+}
+
+impl ProjectRule for MustRespectCouplingLimits {
+    fn name(&self) -> &'static str {
+        "must_respect_coupling_limits"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription::of_kind(self.name())
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let crate_names: BTreeSet<String> = project
+            .files
+            .iter()
+            .map(|file| file.crate_name.clone())
+            .collect();
+
+        let mut couplings: BTreeMap<&str, Coupling> = BTreeMap::new();
+        for (module, dependencies) in project.to_dependency_graph() {
+            let Some(from) = self.component_of(&module, &crate_names) else {
+                continue;
+            };
+            for dependency in dependencies {
+                match self.component_of(&dependency, &crate_names) {
+                    Some(to) if to != from => {
+                        couplings.entry(from).or_default().efferent.insert(to);
+                        couplings.entry(to).or_default().afferent.insert(from);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let no_coupling = Coupling::default();
+        let mut exceeded = Vec::new();
+        for (component, limit) in &self.limits {
+            let coupling = couplings.get(component.as_str()).unwrap_or(&no_coupling);
+            let mut excesses = Vec::new();
+            if let Some(max) = limit
+                .max_afferent
+                .filter(|&max| coupling.afferent.len() > max)
+            {
+                excesses.push(format!("afferent coupling above {}", max));
+            }
+            if let Some(max) = limit
+                .max_efferent
+                .filter(|&max| coupling.efferent.len() > max)
+            {
+                excesses.push(format!("efferent coupling above {}", max));
+            }
+
+            if !excesses.is_empty() {
+                exceeded.push(format!(
+                    "{} ({}): {}",
+                    component,
+                    coupling,
+                    excesses.join(", ")
+                ));
+            }
+        }
+
+        if !exceeded.is_empty() {
+            return Err(format!(
+                "Coupling limits exceeded:\n{}",
+                exceeded.join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    fn project() -> RustProject {
+        RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/application/checkout.rs",
+                    "my_crate::application::checkout",
+                    "use crate::domain::Order;\n\
+                     use crate::infrastructure::Db;\n\
+                     use crate::billing::Invoice;\n\
+                     use crate::application::cart::Cart;\n\
+                     use std::fmt;\n",
+                ),
+                RustFile::from_content(
+                    "src/infrastructure.rs",
+                    "my_crate::infrastructure",
+                    "use crate::domain::Order;\n",
+                ),
+                RustFile::from_content(
+                    "src/billing.rs",
+                    "my_crate::billing",
+                    "use crate::domain::Order;\n",
+                ),
+            ],
+        }
+    }
+
+    fn rule(limits: &[(&str, CouplingLimit)]) -> MustRespectCouplingLimits {
+        MustRespectCouplingLimits {
+            components: [
+                ("domain", "crate::domain"),
+                ("application", "crate::application"),
+                ("infrastructure", "crate::infrastructure"),
+                ("billing", "crate::billing"),
+            ]
+            .iter()
+            .map(|(name, location)| (name.to_string(), location.to_string()))
+            .collect(),
+            limits: limits
+                .iter()
+                .map(|(name, limit)| (name.to_string(), *limit))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_component_exceeding_its_efferent_coupling_limit() {
+        let rule = rule(&[(
+            "application",
+            CouplingLimit {
+                max_efferent: Some(2),
+                ..CouplingLimit::default()
+            },
+        )]);
+
+        assert_eq!(
+            rule.apply(&project()),
+            Err("Coupling limits exceeded:\n\
+                 application (Ca: 0, Ce: 3, I: 1.00): efferent coupling above 2"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_components_within_their_limits() {
+        let rule = rule(&[
+            (
+                "domain",
+                CouplingLimit {
+                    max_afferent: Some(3),
+                    max_efferent: Some(0),
+                },
+            ),
+            (
+                "application",
+                CouplingLimit {
+                    max_efferent: Some(3),
+                    ..CouplingLimit::default()
+                },
+            ),
+        ]);
+
+        assert_eq!(rule.apply(&project()), Ok(()));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::builtin_rules::utils::IsChild;
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// Forbids a module from depending on a module with a lower maturity tier
+/// (e.g. `stable = 2` must not depend on `alpha = 0`).
+///
+/// A module's tier is the one of its longest matching prefix; modules without a tier are ignored.
+pub struct MustRespectMaturityTiers {
+    pub tiers: Vec<(String, usize)>,
+}
+
+impl MustRespectMaturityTiers {
+    fn tier_of(&self, module: &str) -> Option<&(String, usize)> {
+        self.tiers
+            .iter()
+            .filter(|(prefix, _)| module.is_child_of(prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+    }
+}
+
+impl Display for MustRespectMaturityTiers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let tiers: Vec<String> = self
+            .tiers
+            .iter()
+            .map(|(prefix, tier)| format!("{} = {}", prefix, tier))
+            .collect();
+
+        write!(f, "Must respect maturity tiers [{}]", tiers.join(", "))
+    }
+}
+
+impl ProjectRule for MustRespectMaturityTiers {
+    fn name(&self) -> &'static str {
+        "must_respect_maturity_tiers"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            allowed: self
+                .tiers
+                .iter()
+                .map(|(prefix, tier)| format!("{} = {}", prefix, tier))
+                .collect(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let mut offending_edges = BTreeSet::new();
+
+        for file in &project.files {
+            let Some((_, file_tier)) = self.tier_of(&file.logical_path) else {
+                continue;
+            };
+
+            for dependency in &file.dependencies {
+                if let Some((dependency_prefix, dependency_tier)) = self.tier_of(dependency) {
+                    if dependency_tier < file_tier {
+                        offending_edges.insert(format!(
+                            "{} (tier {}) -> {} (tier {})",
+                            file.logical_path, file_tier, dependency_prefix, dependency_tier
+                        ));
+                    }
+                }
+            }
+        }
+
+        if !offending_edges.is_empty() {
+            return Err(format!(
+                "Dependencies on less mature modules detected:\n{}",
+                offending_edges.into_iter().collect::<Vec<_>>().join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    fn project_with_stable_to_alpha_edge() -> RustProject {
+        RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/stable/api.rs",
+                    "my_crate::stable::api",
+                    "use crate::alpha::experiment::Feature;",
+                ),
+                RustFile::from_content(
+                    "src/alpha/experiment.rs",
+                    "my_crate::alpha::experiment",
+                    "use crate::stable::api::Api;",
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_stable_module_must_not_depend_on_alpha() {
+        let rule = MustRespectMaturityTiers {
+            tiers: vec![
+                ("my_crate::stable".to_string(), 2),
+                ("my_crate::alpha".to_string(), 0),
+            ],
+        };
+
+        let result = rule.apply(&project_with_stable_to_alpha_edge());
+
+        assert_eq!(
+            result,
+            Err("Dependencies on less mature modules detected:\n\
+                 my_crate::stable::api (tier 2) -> my_crate::alpha (tier 0)"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_same_tier_dependencies_are_allowed() {
+        let rule = MustRespectMaturityTiers {
+            tiers: vec![
+                ("my_crate::stable".to_string(), 1),
+                ("my_crate::alpha".to_string(), 1),
+            ],
+        };
+
+        assert!(rule.apply(&project_with_stable_to_alpha_edge()).is_ok());
+    }
+
+    #[test]
+    fn test_longest_prefix_defines_the_tier() {
+        let rule = MustRespectMaturityTiers {
+            tiers: vec![
+                ("my_crate".to_string(), 2),
+                ("my_crate::alpha".to_string(), 0),
+            ],
+        };
+
+        assert_eq!(
+            rule.tier_of("my_crate::alpha::experiment"),
+            Some(&("my_crate::alpha".to_string(), 0))
+        );
+        assert_eq!(
+            rule.tier_of("my_crate::stable"),
+            Some(&("my_crate".to_string(), 2))
+        );
+        assert_eq!(rule.tier_of("other_crate"), None);
+    }
+}
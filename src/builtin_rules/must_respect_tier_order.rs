@@ -0,0 +1,166 @@
+use crate::builtin_rules::utils::IsChild;
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// Forbids edges of the dependency graph going from a tier to a higher one.
+///
+/// Tiers are listed from the lowest to the highest, each as a list of module prefixes:
+/// `[["my_crate::domain"], ["my_crate::application"], ["my_crate::infrastructure", "my_crate::cli"]]`
+/// lets `cli` depend on `application` and `domain`, but not `domain` on anything above it.
+/// A module belongs to the tier of its longest matching prefix; modules without a tier are ignored.
+pub struct MustRespectTierOrder {
+    pub tiers: Vec<Vec<String>>,
+}
+
+impl MustRespectTierOrder {
+    fn tier_of(&self, module: &str) -> Option<usize> {
+        self.tiers
+            .iter()
+            .enumerate()
+            .flat_map(|(tier, prefixes)| prefixes.iter().map(move |prefix| (tier, prefix)))
+            .filter(|(_, prefix)| module.is_child_of(prefix))
+            .max_by_key(|(_, prefix)| prefix.len())
+            .map(|(tier, _)| tier)
+    }
+}
+
+impl Display for MustRespectTierOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let tiers: Vec<String> = self
+            .tiers
+            .iter()
+            .map(|prefixes| format!("[{}]", prefixes.join(", ")))
+            .collect();
+
+        write!(f, "Must respect tier order {}", tiers.join(" < "))
+    }
+}
+
+impl ProjectRule for MustRespectTierOrder {
+    fn name(&self) -> &'static str {
+        "must_respect_tier_order"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            allowed: self
+                .tiers
+                .iter()
+                .enumerate()
+                .map(|(tier, prefixes)| format!("{} = {}", prefixes.join(", "), tier))
+                .collect(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let mut upward_edges = BTreeSet::new();
+
+        for (module, dependencies) in project.to_dependency_graph() {
+            let Some(module_tier) = self.tier_of(&module) else {
+                continue;
+            };
+
+            for dependency in dependencies {
+                match self.tier_of(&dependency) {
+                    Some(dependency_tier) if dependency_tier > module_tier => {
+                        upward_edges.insert(format!(
+                            "{} (tier {}) -> {} (tier {})",
+                            module, module_tier, dependency, dependency_tier
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if !upward_edges.is_empty() {
+            return Err(format!(
+                "Dependencies on higher tiers detected:\n{}",
+                upward_edges.into_iter().collect::<Vec<_>>().join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    fn rule() -> MustRespectTierOrder {
+        MustRespectTierOrder {
+            tiers: vec![
+                vec!["my_crate::domain".to_string()],
+                vec!["my_crate::application".to_string()],
+                vec![
+                    "my_crate::infrastructure".to_string(),
+                    "my_crate::cli".to_string(),
+                ],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_upward_edges_are_reported() {
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/cli.rs",
+                    "my_crate::cli",
+                    "use crate::application::Service; use crate::domain::User;",
+                ),
+                RustFile::from_content(
+                    "src/application.rs",
+                    "my_crate::application",
+                    "use crate::domain::User; pub struct Service;",
+                ),
+                RustFile::from_content(
+                    "src/domain.rs",
+                    "my_crate::domain",
+                    "use crate::infrastructure::Db; pub struct User;",
+                ),
+                RustFile::from_content(
+                    "src/infrastructure.rs",
+                    "my_crate::infrastructure",
+                    "pub struct Db;",
+                ),
+            ],
+        };
+
+        assert_eq!(
+            rule().apply(&project),
+            Err("Dependencies on higher tiers detected:\n\
+                 my_crate::domain (tier 0) -> my_crate::infrastructure (tier 2)"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_edges_towards_lower_tiers_are_allowed() {
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/cli.rs",
+                    "my_crate::cli",
+                    "use crate::application::Service; use crate::infrastructure::Db;",
+                ),
+                RustFile::from_content(
+                    "src/application.rs",
+                    "my_crate::application",
+                    "use crate::domain::User; use std::fmt;",
+                ),
+            ],
+        };
+
+        assert!(rule().apply(&project).is_ok());
+    }
+}
@@ -0,0 +1,182 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::public_signatures;
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use crate::styling::Styling;
+use quote::ToTokens;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use syn::{GenericArgument, PathArguments, ReturnType, Type};
+
+/// Forbids the public functions of a module from returning `Result`s with different error types.
+pub struct MustUseSingleErrorType {
+    pub subject: Subject,
+}
+
+impl Display for MustUseSingleErrorType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl ProjectRule for MustUseSingleErrorType {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} must use a single error type",
+            styling.subject(&self.subject)
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "must_use_single_error_type"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        let mut error_types: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for file in &project.files {
+            if !self.subject.matches(&file.logical_path) {
+                continue;
+            }
+
+            for signature in public_signatures(&file.ast) {
+                if let Some(error_type) = error_type_of(&signature.output) {
+                    let functions = error_types.entry(error_type).or_default();
+                    functions.push(format!("{} in file://{}", signature.ident, file.path));
+                }
+            }
+        }
+
+        if error_types.len() <= 1 {
+            return Ok(());
+        }
+
+        let details: Vec<String> = error_types
+            .iter()
+            .map(|(error_type, functions)| format!("{}: {}", error_type, functions.join(", ")))
+            .collect();
+
+        Err(format!(
+            "Multiple error types used in {}:\n{}",
+            &self.subject,
+            details.join("\n")
+        ))
+    }
+}
+
+/// Extracts `E` from a `Result<T, E>` return type.
+fn error_type_of(output: &ReturnType) -> Option<String> {
+    let ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    let Type::Path(type_path) = ty.as_ref() else {
+        return None;
+    };
+    let last_segment = type_path.path.segments.last()?;
+    if last_segment.ident != "Result" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &last_segment.arguments else {
+        return None;
+    };
+
+    let mut types = arguments.args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+
+    types
+        .nth(1)
+        .map(|error_type| error_type.to_token_stream().to_string().replace(' ', ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_file::RustFile;
+
+    fn rule() -> MustUseSingleErrorType {
+        MustUseSingleErrorType {
+            subject: "my_crate::api".into(),
+        }
+    }
+
+    #[test]
+    fn test_two_error_types_are_flagged() {
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/api/users.rs",
+                    "my_crate::api::users",
+                    r#"
+                    pub fn get_user() -> Result<User, ApiError> { todo!() }
+                    "#,
+                ),
+                RustFile::from_content(
+                    "src/api/files.rs",
+                    "my_crate::api::files",
+                    r#"
+                    pub fn read() -> Result<Vec<u8>, std::io::Error> { todo!() }
+                    "#,
+                ),
+            ],
+        };
+
+        let result = rule().apply(&project);
+
+        assert!(result.is_err());
+        let message = result.unwrap_err();
+        assert!(message.contains("ApiError: get_user"));
+        assert!(message.contains("std::io::Error: read"));
+    }
+
+    #[test]
+    fn test_single_error_type() {
+        let project = RustProject {
+            files: vec![RustFile::from_content(
+                "src/api/users.rs",
+                "my_crate::api::users",
+                r#"
+                pub fn get_user() -> Result<User, ApiError> { todo!() }
+
+                pub struct Users;
+
+                impl Users {
+                    pub fn list(&self) -> std::result::Result<Vec<User>, ApiError> { todo!() }
+                }
+
+                fn internal() -> Result<(), std::io::Error> { todo!() }
+                "#,
+            )],
+        };
+
+        assert!(rule().apply(&project).is_ok());
+    }
+
+    #[test]
+    fn test_files_outside_subject_are_ignored() {
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/api/users.rs",
+                    "my_crate::api::users",
+                    "pub fn get_user() -> Result<User, ApiError> { todo!() }",
+                ),
+                RustFile::from_content(
+                    "src/infrastructure/db.rs",
+                    "my_crate::infrastructure::db",
+                    "pub fn connect() -> Result<Connection, DbError> { todo!() }",
+                ),
+            ],
+        };
+
+        assert!(rule().apply(&project).is_ok());
+    }
+}
@@ -0,0 +1,239 @@
+use crate::rule::{ProjectRule, RuleDescription};
+use crate::rust_project::RustProject;
+use log::info;
+use proc_macro2::TokenStream;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Set to write the snapshots from the current public API instead of checking them, e.g.
+/// `ARKITECT_UPDATE_SNAPSHOTS=1 cargo test`.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "ARKITECT_UPDATE_SNAPSHOTS";
+
+/// Compares the public API of the project with a committed snapshot and reports
+/// removed or changed items. New items are allowed.
+///
+/// The snapshot holds one `path<TAB>signature` line per public item. The check only reads it:
+/// a missing snapshot is an error, and it is written by `update` or when `UPDATE_SNAPSHOTS_VAR`
+/// is set.
+pub struct PublicApiMustMatchSnapshot {
+    pub snapshot_path: String,
+}
+
+/// Public items of a project, by path.
+pub type ApiSnapshot = BTreeMap<String, String>;
+
+impl PublicApiMustMatchSnapshot {
+    pub fn snapshot(project: &RustProject) -> ApiSnapshot {
+        project
+            .files
+            .iter()
+            .flat_map(|file| file.public_api())
+            .map(|item| (item.path, item.signature))
+            .collect()
+    }
+
+    pub fn render(snapshot: &ApiSnapshot) -> String {
+        snapshot
+            .iter()
+            .map(|(path, signature)| format!("{}\t{}\n", path, signature))
+            .collect()
+    }
+
+    pub fn parse(content: &str) -> ApiSnapshot {
+        content
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(path, signature)| (path.to_string(), signature.to_string()))
+            .collect()
+    }
+
+    /// Lists the items of `expected` that are missing or have a different signature in `actual`.
+    /// Signatures are compared as tokens, so spacing doesn't matter.
+    pub fn diff(expected: &ApiSnapshot, actual: &ApiSnapshot) -> Vec<String> {
+        expected
+            .iter()
+            .filter_map(|(path, signature)| match actual.get(path) {
+                None => Some(format!("removed: {} ({})", path, signature)),
+                Some(current) if normalized(current) != normalized(signature) => {
+                    Some(format!("changed: {} ({} -> {})", path, signature, current))
+                }
+                Some(_) => None,
+            })
+            .collect()
+    }
+
+    /// Writes the public API of `project` to the snapshot, replacing it.
+    pub fn update(&self, project: &RustProject) -> Result<(), String> {
+        fs::write(&self.snapshot_path, Self::render(&Self::snapshot(project))).map_err(|e| {
+            format!(
+                "Failed to write public API snapshot {}: {}",
+                self.snapshot_path, e
+            )
+        })?;
+        info!("Public API snapshot written to {}", self.snapshot_path);
+
+        Ok(())
+    }
+}
+
+/// The signature as re-printed from its tokens, or as it is when it can't be tokenized.
+fn normalized(signature: &str) -> String {
+    TokenStream::from_str(signature)
+        .map(|tokens| tokens.to_string())
+        .unwrap_or_else(|_| signature.to_string())
+}
+
+impl Display for PublicApiMustMatchSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Public API must match snapshot {}", self.snapshot_path)
+    }
+}
+
+impl ProjectRule for PublicApiMustMatchSnapshot {
+    fn name(&self) -> &'static str {
+        "public_api_must_match_snapshot"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription::of_kind(self.name())
+    }
+
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        if env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+            return self.update(project);
+        }
+
+        let actual = Self::snapshot(project);
+        let path = Path::new(&self.snapshot_path);
+
+        if !path.exists() {
+            return Err(format!(
+                "Public API snapshot {} is missing: run with {}=1 to write it",
+                self.snapshot_path, UPDATE_SNAPSHOTS_VAR
+            ));
+        }
+
+        let content = fs::read_to_string(path).map_err(|e| {
+            format!(
+                "Failed to read public API snapshot {}: {}",
+                self.snapshot_path, e
+            )
+        })?;
+
+        let differences = Self::diff(&Self::parse(&content), &actual);
+        if !differences.is_empty() {
+            return Err(format!(
+                "Public API differs from snapshot {}:\n{}",
+                self.snapshot_path,
+                differences.join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::temp_project;
+    use crate::rust_file::RustFile;
+
+    fn project(content: &str) -> RustProject {
+        RustProject {
+            files: vec![RustFile::from_content(
+                "src/api.rs",
+                "my_crate::api",
+                content,
+            )],
+        }
+    }
+
+    #[test]
+    fn test_removed_function_is_reported() {
+        let before = project(
+            r#"
+            pub fn get_user(id: u32) -> User { todo!() }
+            pub fn delete_user(id: u32) { todo!() }
+            "#,
+        );
+        let after = project(
+            r#"
+            pub fn get_user(id: u32) -> User { todo!() }
+            pub fn list_users() -> Vec<User> { todo!() }
+            "#,
+        );
+
+        let differences = PublicApiMustMatchSnapshot::diff(
+            &PublicApiMustMatchSnapshot::snapshot(&before),
+            &PublicApiMustMatchSnapshot::snapshot(&after),
+        );
+
+        assert_eq!(
+            differences,
+            vec!["removed: my_crate::api::delete_user (fn delete_user (id : u32))"]
+        );
+    }
+
+    #[test]
+    fn test_changed_signature_is_reported() {
+        let before = project("pub fn get_user(id: u32) -> User { todo!() }");
+        let after = project("pub fn get_user(id: u64) -> User { todo!() }");
+
+        let differences = PublicApiMustMatchSnapshot::diff(
+            &PublicApiMustMatchSnapshot::snapshot(&before),
+            &PublicApiMustMatchSnapshot::snapshot(&after),
+        );
+
+        assert_eq!(
+            differences,
+            vec!["changed: my_crate::api::get_user (fn get_user (id : u32) -> User -> fn get_user (id : u64) -> User)"]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let snapshot = PublicApiMustMatchSnapshot::snapshot(&project(
+            r#"
+            pub fn get_user(id: u32) -> User { todo!() }
+            pub struct User { pub name: String }
+            "#,
+        ));
+
+        let rendered = PublicApiMustMatchSnapshot::render(&snapshot);
+
+        assert_eq!(PublicApiMustMatchSnapshot::parse(&rendered), snapshot);
+    }
+
+    #[test]
+    fn test_missing_snapshot_is_reported_without_writing_it() {
+        let dir = temp_project(&[]);
+        let path = dir.path().join("api.snap");
+        let rule = PublicApiMustMatchSnapshot {
+            snapshot_path: path.to_string_lossy().to_string(),
+        };
+        let project = project("pub fn get_user(id: u32) -> User { todo!() }");
+
+        assert!(rule.apply(&project).is_err());
+        assert!(!path.exists());
+
+        rule.update(&project).unwrap();
+        assert!(rule.apply(&project).is_ok());
+    }
+
+    #[test]
+    fn test_signatures_are_compared_as_tokens() {
+        let expected = PublicApiMustMatchSnapshot::parse(
+            "my_crate::api::get_user\tfn get_user(id: u32) -> Option<User>\n",
+        );
+        let actual = PublicApiMustMatchSnapshot::snapshot(&project(
+            "pub fn get_user(id: u32) -> Option<User> { todo!() }",
+        ));
+
+        assert!(PublicApiMustMatchSnapshot::diff(&expected, &actual).is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::{
+    classify_dependency, public_signatures, resolve_path, signature_type_paths, DependencyKind,
+};
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// Restricts the external crates whose types may appear in the parameters and return types
+/// of the subject's public functions: exposing e.g. `chrono::NaiveDate` forces every consumer
+/// to depend on `chrono` too.
+///
+/// A type is resolved through the file's `use` declarations, so both `chrono::NaiveDate` and
+/// an imported `NaiveDate` are recognised. Unimported single-segment types are assumed local.
+#[derive(Debug)]
+pub struct PublicSignaturesMayExposeExternalRule {
+    pub subject: Subject,
+    pub allowed_external: Vec<String>,
+}
+
+impl Display for PublicSignaturesMayExposeExternalRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for PublicSignaturesMayExposeExternalRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} public signatures may expose external types only from {}",
+            styling.subject(&self.subject),
+            styling.subject("[".to_string() + &self.allowed_external.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "public_signatures_may_expose_external"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            allowed: self.allowed_external.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let mut leaked = Vec::new();
+
+        for signature in public_signatures(&file.ast) {
+            for type_path in signature_type_paths(signature) {
+                let Some(full_path) = resolve_path(file, &type_path.path) else {
+                    continue;
+                };
+
+                if classify_dependency(&full_path, file) != DependencyKind::External {
+                    continue;
+                }
+
+                let external_crate = full_path.split("::").next().unwrap_or_default();
+                if !self.allowed_external.iter().any(|c| c == external_crate) {
+                    leaked.push(format!("{}: {}", signature.ident, full_path));
+                }
+            }
+        }
+
+        if leaked.is_empty() {
+            return Ok(());
+        }
+
+        Err(format!(
+            "External types leaked through public signatures [{}] in file://{}",
+            leaked.join(", "),
+            file.path
+        ))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> PublicSignaturesMayExposeExternalRule {
+        PublicSignaturesMayExposeExternalRule {
+            subject: "my_crate::api".into(),
+            allowed_external: vec!["uuid".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_external_types_not_allowed_are_flagged() {
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            r#"
+            use chrono::NaiveDate;
+            use std::collections::HashMap;
+            use crate::model::User;
+
+            pub fn birthday(user: &User) -> NaiveDate { todo!() }
+            pub fn created_at() -> Option<chrono::DateTime<chrono::Utc>> { todo!() }
+            pub fn by_id(id: uuid::Uuid) -> HashMap<String, User> { todo!() }
+            pub fn first<T: IntoIterator>(items: T) -> Option<T::Item> { todo!() }
+            fn private() -> NaiveDate { todo!() }
+            "#,
+        );
+
+        let expected = "External types leaked through public signatures [birthday: chrono::NaiveDate, created_at: chrono::DateTime, created_at: chrono::Utc] in file://src/api.rs".to_string();
+        assert_eq!(rule().apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_allowed_external_crates_may_be_exposed() {
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            r#"
+            use uuid::Uuid;
+
+            pub fn new_id() -> Uuid { todo!() }
+            "#,
+        );
+
+        assert!(rule().apply(&file).is_ok());
+    }
+
+    #[test]
+    fn test_types_of_local_modules_are_not_external() {
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            r#"
+            mod dto;
+
+            pub fn order() -> dto::Order { todo!() }
+            "#,
+        );
+
+        assert!(rule().apply(&file).is_ok());
+    }
+}
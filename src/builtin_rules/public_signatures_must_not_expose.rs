@@ -0,0 +1,137 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::{
+    public_signatures, resolve_crate_prefix, resolve_path, signature_type_paths, IsChild,
+};
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// Forbids the parameters and return types of the subject's public functions from exposing
+/// types owned by the `forbidden_components` (e.g. `crate::infrastructure`), even where the
+/// subject may use them internally: the public API would leak the component to every consumer.
+///
+/// Types are resolved through the file's `use` declarations like in
+/// `PublicSignaturesMayExposeExternalRule`. A leading `crate` in a component stands for the
+/// crate of the checked file.
+#[derive(Debug)]
+pub struct PublicSignaturesMustNotExposeRule {
+    pub subject: Subject,
+    pub forbidden_components: Vec<String>,
+}
+
+impl Display for PublicSignaturesMustNotExposeRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for PublicSignaturesMustNotExposeRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} public signatures must not expose {}",
+            styling.subject(&self.subject),
+            styling.subject("[".to_string() + &self.forbidden_components.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "public_signatures_must_not_expose"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            forbidden: self.forbidden_components.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let components: Vec<String> = self
+            .forbidden_components
+            .iter()
+            .map(|component| resolve_crate_prefix(component, &file.crate_name))
+            .collect();
+        let mut leaked = Vec::new();
+
+        for signature in public_signatures(&file.ast) {
+            for type_path in signature_type_paths(signature) {
+                let Some(full_path) = resolve_path(file, &type_path.path) else {
+                    continue;
+                };
+
+                if components
+                    .iter()
+                    .any(|component| full_path.is_child_of(component))
+                {
+                    leaked.push(format!("{}: {}", signature.ident, full_path));
+                }
+            }
+        }
+
+        if leaked.is_empty() {
+            return Ok(());
+        }
+
+        Err(format!(
+            "Forbidden types exposed through public signatures [{}] in file://{}",
+            leaked.join(", "),
+            file.path
+        ))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> PublicSignaturesMustNotExposeRule {
+        PublicSignaturesMustNotExposeRule {
+            subject: "my_crate::application".into(),
+            forbidden_components: vec!["crate::infrastructure".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_public_function_returning_a_forbidden_type() {
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            r#"
+            use crate::infrastructure::db::Connection;
+            use crate::domain::Order;
+
+            pub fn connect() -> Connection { todo!() }
+            pub fn save(order: Order, pool: &crate::infrastructure::Pool) {}
+            fn internal() -> Connection { todo!() }
+            "#,
+        );
+
+        let expected = "Forbidden types exposed through public signatures [connect: my_crate::infrastructure::db::Connection, save: my_crate::infrastructure::Pool] in file://src/application.rs".to_string();
+        assert_eq!(rule().apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_forbidden_types_used_internally_are_allowed() {
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            r#"
+            use crate::infrastructure::db::Connection;
+            use crate::domain::Order;
+
+            pub fn place(order: Order) -> Result<(), String> {
+                let _connection = Connection::open();
+                Ok(())
+            }
+            "#,
+        );
+
+        assert!(rule().apply(&file).is_ok());
+    }
+}
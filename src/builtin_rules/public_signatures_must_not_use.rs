@@ -0,0 +1,138 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::{public_signatures, signature_type_paths};
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+use syn::TypePath;
+
+/// Forbids the given types (e.g. `Vec`, `std::collections::HashMap`) in the parameters and
+/// return types of the subject's public functions, generic arguments included.
+///
+/// A type matches when its path and the configured one end the same way, so `HashMap`
+/// matches `std::collections::HashMap` and `Vec` matches `std::vec::Vec`.
+#[derive(Debug)]
+pub struct PublicSignaturesMustNotUseRule {
+    pub subject: Subject,
+    pub forbidden_types: Vec<String>,
+}
+
+impl PublicSignaturesMustNotUseRule {
+    fn is_forbidden(&self, type_path: &TypePath) -> bool {
+        let segments: Vec<String> = type_path
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+
+        self.forbidden_types.iter().any(|forbidden| {
+            let forbidden: Vec<&str> = forbidden.split("::").collect();
+            let common = segments.len().min(forbidden.len());
+
+            segments[segments.len() - common..]
+                .iter()
+                .zip(&forbidden[forbidden.len() - common..])
+                .all(|(segment, forbidden)| segment == forbidden)
+        })
+    }
+}
+
+impl Display for PublicSignaturesMustNotUseRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for PublicSignaturesMustNotUseRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "{} public signatures must not use {}",
+            styling.subject(&self.subject),
+            styling.subject("[".to_string() + &self.forbidden_types.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "public_signatures_must_not_use"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            forbidden: self.forbidden_types.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        let mut offending = Vec::new();
+
+        for signature in public_signatures(&file.ast) {
+            for type_path in signature_type_paths(signature) {
+                if self.is_forbidden(type_path) {
+                    let type_name = type_path.path.segments.last().unwrap().ident.to_string();
+                    offending.push(format!("{}: {}", signature.ident, type_name));
+                }
+            }
+        }
+
+        if offending.is_empty() {
+            return Ok(());
+        }
+
+        Err(format!(
+            "Forbidden types in public signatures [{}] in file://{}",
+            offending.join(", "),
+            file.path
+        ))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> PublicSignaturesMustNotUseRule {
+        PublicSignaturesMustNotUseRule {
+            subject: "my_crate::api".into(),
+            forbidden_types: vec!["Vec".to_string(), "std::collections::HashMap".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_concrete_collections_are_flagged() {
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            r#"
+            use std::collections::HashMap;
+
+            pub fn list<T>() -> Vec<T> { todo!() }
+            pub fn index(users: Option<HashMap<String, User>>) {}
+            pub fn iter() -> impl Iterator<Item = User> { todo!() }
+            fn private() -> Vec<User> { todo!() }
+            "#,
+        );
+
+        let expected =
+            "Forbidden types in public signatures [list: Vec, index: HashMap] in file://src/api.rs"
+                .to_string();
+        assert_eq!(rule().apply(&file), Err(expected));
+    }
+
+    #[test]
+    fn test_types_with_the_same_name_in_other_modules_are_allowed() {
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            "pub fn index() -> crate::cache::HashMap { todo!() }",
+        );
+
+        assert!(rule().apply(&file).is_ok());
+    }
+}
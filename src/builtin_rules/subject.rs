@@ -0,0 +1,91 @@
+use crate::builtin_rules::utils::IsChild;
+use regex::Regex;
+use std::fmt::{Display, Formatter};
+
+/// The modules a rule applies to: either a module and its children, or every module matching a regex.
+#[derive(Debug, Clone)]
+pub enum Subject {
+    /// A module and all of its children, e.g. `my_crate::domain`.
+    Prefix(String),
+    /// Every module whose path starts with a match of the regex ending on a `::` boundary,
+    /// e.g. `my_crate::api_v\d+` matches `my_crate::api_v1` and `my_crate::api_v2::handlers`.
+    Regex(Regex),
+}
+
+impl Subject {
+    pub fn regex(pattern: &str) -> Result<Subject, regex::Error> {
+        Regex::new(&format!("^(?:{})(?:::|$)", pattern)).map(Subject::Regex)
+    }
+
+    pub fn matches(&self, module: &str) -> bool {
+        match self {
+            Subject::Prefix(prefix) => module.is_child_of(prefix),
+            Subject::Regex(regex) => regex.is_match(module),
+        }
+    }
+}
+
+impl Display for Subject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Subject::Prefix(prefix) => write!(f, "{}", prefix),
+            Subject::Regex(regex) => {
+                let pattern = regex.as_str();
+                let pattern = pattern
+                    .strip_prefix("^(?:")
+                    .and_then(|p| p.strip_suffix(")(?:::|$)"))
+                    .unwrap_or(pattern);
+                write!(f, "{}", pattern)
+            }
+        }
+    }
+}
+
+impl From<&str> for Subject {
+    fn from(prefix: &str) -> Self {
+        Subject::Prefix(prefix.to_string())
+    }
+}
+
+impl From<String> for Subject {
+    fn from(prefix: String) -> Self {
+        Subject::Prefix(prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_subject() {
+        let subject = Subject::from("my_crate::api");
+
+        assert!(subject.matches("my_crate::api"));
+        assert!(subject.matches("my_crate::api::handlers"));
+        assert!(!subject.matches("my_crate::api_v1"));
+    }
+
+    #[test]
+    fn test_regex_subject() {
+        let subject = Subject::regex(r"my_crate::api_v\d+").unwrap();
+
+        assert!(subject.matches("my_crate::api_v1"));
+        assert!(subject.matches("my_crate::api_v2::handlers"));
+        assert!(!subject.matches("my_crate::api"));
+        assert!(!subject.matches("my_crate::api_v1beta"));
+        assert!(!subject.matches("other_crate::my_crate::api_v1"));
+    }
+
+    #[test]
+    fn test_regex_subject_display() {
+        let subject = Subject::regex(r"my_crate::api_v\d+").unwrap();
+
+        assert_eq!(subject.to_string(), r"my_crate::api_v\d+");
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        assert!(Subject::regex("my_crate::(").is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::utils::{resolve_crate_prefix, IsChild};
+use crate::rule::{LocatedViolation, Rule, RuleDescription};
+use crate::rust_file::RustFile;
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+
+/// Restricts the dependencies of the subject's test code, the files under `tests/` and the
+/// `#[cfg(test)]` items, to `allowed_dependencies` and the subject itself. Production code is
+/// left to the other rules, so tests can get dedicated, looser allowances.
+///
+/// Dependencies shared with the production code of the same file are not test code.
+/// A leading `crate` in `allowed_dependencies` stands for the crate of the checked file.
+#[derive(Debug)]
+pub struct TestCodeMayDependOnRule {
+    pub subject: Subject,
+    pub allowed_dependencies: Vec<String>,
+}
+
+impl Display for TestCodeMayDependOnRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for TestCodeMayDependOnRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!(
+            "Tests of {} may depend on {}",
+            styling.subject(&self.subject),
+            styling.subject("[".to_string() + &self.allowed_dependencies.join(", ") + "]")
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "test_code_may_depend_on"
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        false
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            allowed: self.allowed_dependencies.clone(),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        self.apply_located(file).map_err(|(message, _)| message)
+    }
+
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        let test_dependencies = if file.is_test {
+            &file.dependencies
+        } else {
+            &file.test_dependencies
+        };
+        let allowed: Vec<String> = self
+            .allowed_dependencies
+            .iter()
+            .map(|path| resolve_crate_prefix(path, &file.crate_name))
+            .collect();
+
+        let forbidden_dependencies: Vec<&String> = test_dependencies
+            .iter()
+            .filter(|&dependency| {
+                !self.subject.matches(dependency)
+                    && !allowed.iter().any(|path| dependency.is_child_of(path))
+            })
+            .collect();
+
+        if let Some(first) = forbidden_dependencies.first() {
+            let listed: Vec<&str> = forbidden_dependencies.iter().map(|d| d.as_str()).collect();
+            return Err((
+                format!(
+                    "Forbidden test dependencies to [{}] in file://{}",
+                    listed.join(", "),
+                    file.location_of(first)
+                ),
+                file.line_of(first),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> TestCodeMayDependOnRule {
+        TestCodeMayDependOnRule {
+            subject: "my_crate::domain".into(),
+            allowed_dependencies: vec!["crate::testing".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_test_module_importing_a_forbidden_component() {
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            r#"use crate::infrastructure::Db;
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::fixtures::order;
+    use crate::domain::Order;
+    use crate::application::Service;
+}
+"#,
+        );
+
+        assert_eq!(
+            rule().apply(&file),
+            Err("Forbidden test dependencies to [my_crate::application::Service] in file://src/domain.rs:7".to_string())
+        );
+    }
+
+    #[test]
+    fn test_files_under_tests_are_test_code() {
+        let rule = TestCodeMayDependOnRule {
+            subject: "my_crate::tests".into(),
+            allowed_dependencies: vec!["my_crate::domain".to_string()],
+        };
+        let file = |content: &str| {
+            RustFile::from_content("tests/orders.rs", "my_crate::tests::orders", content)
+        };
+
+        assert!(rule.apply(&file("use my_crate::domain::Order;\n")).is_ok());
+        assert!(rule
+            .apply(&file("use my_crate::infrastructure::Db;\n"))
+            .is_err());
+    }
+}
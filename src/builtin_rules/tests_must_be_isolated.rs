@@ -0,0 +1,213 @@
+use crate::builtin_rules::subject::Subject;
+use crate::rule::{Rule, RuleDescription};
+use crate::rust_file::{is_cfg_test, is_under_tests_directory, RustFile};
+use crate::styling::Styling;
+use std::fmt::{Display, Formatter};
+use syn::visit::{self, Visit};
+use syn::{Attribute, ItemFn, ItemMod};
+
+/// Flags `#[test]` functions that are neither in a file under `tests/`
+/// nor inside a `#[cfg(test)]` module.
+#[derive(Debug)]
+pub struct TestsMustBeIsolatedRule {
+    pub subject: Subject,
+}
+
+impl TestsMustBeIsolatedRule {
+    pub fn new(subject: impl Into<Subject>) -> Self {
+        Self {
+            subject: subject.into(),
+        }
+    }
+}
+
+impl Display for TestsMustBeIsolatedRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&Styling::plain()))
+    }
+}
+
+impl Rule for TestsMustBeIsolatedRule {
+    fn render(&self, styling: &Styling) -> String {
+        format!("{} tests must be isolated", styling.subject(&self.subject))
+    }
+
+    fn name(&self) -> &'static str {
+        "tests_must_be_isolated"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription {
+            subject: Some(self.subject.to_string()),
+            ..RuleDescription::of_kind(self.name())
+        }
+    }
+
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        if is_under_tests_directory(&file.relative_path) {
+            return Ok(());
+        }
+
+        let mut visitor = StrayTestsVisitor {
+            test_context_depth: 0,
+            stray_tests: vec![],
+        };
+        visitor.visit_file(&file.ast);
+
+        if visitor.stray_tests.is_empty() {
+            return Ok(());
+        }
+
+        Err(format!(
+            "Tests outside of a test context [{}] in file://{}",
+            visitor.stray_tests.join(", "),
+            file.path
+        ))
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.subject.matches(&file.logical_path)
+    }
+}
+
+fn is_test_attribute(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "test")
+}
+
+struct StrayTestsVisitor {
+    test_context_depth: usize,
+    stray_tests: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for StrayTestsVisitor {
+    fn visit_file(&mut self, node: &'ast syn::File) {
+        if node.attrs.iter().any(is_cfg_test) {
+            return;
+        }
+
+        visit::visit_file(self, node);
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let is_test_module = node.attrs.iter().any(is_cfg_test);
+        if is_test_module {
+            self.test_context_depth += 1;
+        }
+
+        visit::visit_item_mod(self, node);
+
+        if is_test_module {
+            self.test_context_depth -= 1;
+        }
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        if self.test_context_depth == 0 && node.attrs.iter().any(is_test_attribute) {
+            self.stray_tests.push(node.sig.ident.to_string());
+        }
+
+        visit::visit_item_fn(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> TestsMustBeIsolatedRule {
+        TestsMustBeIsolatedRule::new("my_crate")
+    }
+
+    #[test]
+    fn test_stray_test_in_production_module() {
+        let file = RustFile::from_content(
+            "src/domain/model.rs",
+            "my_crate::domain::model",
+            r#"
+            pub fn production() {}
+
+            #[test]
+            fn stray_test() {}
+            "#,
+        );
+
+        let result = rule().apply(&file);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("stray_test"));
+    }
+
+    #[test]
+    fn test_tests_in_cfg_test_module() {
+        let file = RustFile::from_content(
+            "src/domain/model.rs",
+            "my_crate::domain::model",
+            r#"
+            pub fn production() {}
+
+            #[cfg(test)]
+            mod tests {
+                mod nested {
+                    #[test]
+                    fn isolated_test() {}
+                }
+
+                #[tokio::test]
+                async fn async_isolated_test() {}
+            }
+            "#,
+        );
+
+        assert!(rule().apply(&file).is_ok());
+    }
+
+    #[test]
+    fn test_tests_in_cfg_test_file() {
+        let file = RustFile::from_content(
+            "src/domain/model_tests.rs",
+            "my_crate::domain::model_tests",
+            r#"
+            #![cfg(test)]
+
+            #[test]
+            fn isolated_test() {}
+            "#,
+        );
+
+        assert!(rule().apply(&file).is_ok());
+    }
+
+    #[test]
+    fn test_tests_under_tests_directory() {
+        let file = RustFile::from_content(
+            "tests/integration.rs",
+            "my_crate::tests::integration",
+            r#"
+            #[test]
+            fn integration_test() {}
+            "#,
+        );
+
+        assert!(rule().apply(&file).is_ok());
+    }
+
+    #[test]
+    fn test_tests_in_module_with_other_cfg() {
+        let file = RustFile::from_content(
+            "src/domain/model.rs",
+            "my_crate::domain::model",
+            r#"
+            #[cfg(feature = "extra")]
+            mod extra {
+                #[test]
+                fn stray_test() {}
+            }
+            "#,
+        );
+
+        assert!(rule().apply(&file).is_err());
+    }
+}
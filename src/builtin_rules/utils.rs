@@ -1,3 +1,7 @@
+use crate::rust_file::RustFile;
+use syn::visit::{self, Visit};
+use syn::{ImplItemFn, ItemFn, Path, Signature, TypePath, Visibility};
+
 pub trait IsChild {
     fn is_child_of(&self, module: &str) -> bool;
 }
@@ -18,9 +22,205 @@ impl IsChild for String {
     }
 }
 
+/// `path` with a leading `crate` replaced by `crate_name`.
+pub fn resolve_crate_prefix(path: &str, crate_name: &str) -> String {
+    match path.strip_prefix("crate") {
+        Some(rest) if rest.is_empty() || rest.starts_with("::") => {
+            format!("{}{}", crate_name, rest)
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// The third-party crate a dependency of `file` belongs to, if any.
+pub fn external_crate_of<'a>(dependency: &'a str, file: &RustFile) -> Option<&'a str> {
+    let root = dependency.split("::").next().unwrap_or(dependency);
+
+    // Unresolved paths such as `T::Item` or `Self::Error` don't start with a crate
+    let is_crate_name =
+        root.starts_with(|c: char| c.is_ascii_lowercase()) && !["self", "super"].contains(&root);
+
+    (is_crate_name && classify_dependency(dependency, file) == DependencyKind::External)
+        .then_some(root)
+}
+
+/// Collapses the dependencies sharing their parent module into that module, removing duplicates:
+/// `[a::foo::A, a::foo::B, a::bar::C]` becomes `[a::foo, a::bar::C]`.
+pub fn collapse_to_modules(dependencies: &[String]) -> Vec<String> {
+    let parent = |dependency: &str| {
+        dependency
+            .rsplit_once("::")
+            .map(|(parent, _)| parent.to_string())
+    };
+
+    let mut collapsed: Vec<String> = Vec::new();
+    for dependency in dependencies {
+        let shares_parent = parent(dependency).filter(|module| {
+            dependencies
+                .iter()
+                .any(|other| other != dependency && parent(other).as_ref() == Some(module))
+        });
+        let listed = shares_parent.unwrap_or_else(|| dependency.clone());
+        if !collapsed.contains(&listed) {
+            collapsed.push(listed);
+        }
+    }
+
+    collapsed
+}
+
+/// The options of a `Configured` dependency rule, all off by default as for the rule it wraps.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DependencyOptions {
+    /// Skips files under `tests/` and dependencies only used in `#[cfg(test)]` code.
+    pub exclude_test_code: bool,
+    /// Lists the dependencies sharing a parent module once, as that module, instead of one entry
+    /// per imported item.
+    pub collapse_to_modules: bool,
+    /// Allows `std`, `core` and `alloc` without listing them one by one. Only read by
+    /// `MustNotDependOnAnythingRule`.
+    pub allow_std: bool,
+}
+
+impl DependencyOptions {
+    /// The dependencies of `file` the rule checks.
+    pub fn dependencies_of<'a>(&self, file: &'a RustFile) -> Vec<&'a String> {
+        file.dependencies(self.exclude_test_code)
+    }
+
+    /// The forbidden dependencies as listed in a violation.
+    pub fn listed(&self, forbidden_dependencies: &[String]) -> Vec<String> {
+        if self.collapse_to_modules {
+            collapse_to_modules(forbidden_dependencies)
+        } else {
+            forbidden_dependencies.to_vec()
+        }
+    }
+}
+
+/// Crates shipped with the Rust toolchain.
+const STD_FAMILY_CRATES: [&str; 3] = ["std", "core", "alloc"];
+
+/// Where a dependency points to, judged by its crate root.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DependencyKind {
+    /// `std`, `core` or `alloc`.
+    Std,
+    /// A third-party crate.
+    External,
+    /// The crate the dependency is declared in, local names of the file included.
+    Internal,
+}
+
+/// Classifies a dependency of `file`. Paths starting with a module the file declares or a name
+/// it imports, like `model::Order` after `mod model;`, are internal.
+pub fn classify_dependency(dependency: &str, file: &RustFile) -> DependencyKind {
+    let root = dependency.split("::").next().unwrap_or(dependency);
+
+    if root == file.crate_name || root == "crate" || file.declares_local_name(root) {
+        DependencyKind::Internal
+    } else if STD_FAMILY_CRATES.contains(&root) {
+        DependencyKind::Std
+    } else {
+        DependencyKind::External
+    }
+}
+
+/// The full path of `path` as seen from `file`, if it can be told. The first segment is
+/// resolved through the file's `use` declarations and `crate` is replaced by the crate name.
+/// Unimported single-segment paths are assumed local and give `None`, as do `self`/`super`
+/// and generic (`T::Item`) paths.
+pub fn resolve_path(file: &RustFile, path: &Path) -> Option<String> {
+    let segments: Vec<String> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    let first = segments.first()?;
+
+    if first == "crate" {
+        return Some(
+            std::iter::once(file.crate_name.clone())
+                .chain(segments[1..].iter().cloned())
+                .collect::<Vec<_>>()
+                .join("::"),
+        );
+    }
+
+    let imported = file
+        .dependencies
+        .iter()
+        .find(|dependency| dependency.rsplit("::").next() == Some(first.as_str()));
+
+    match imported {
+        Some(dependency) => Some(
+            std::iter::once(dependency.clone())
+                .chain(segments[1..].iter().cloned())
+                .collect::<Vec<_>>()
+                .join("::"),
+        ),
+        None if segments.len() > 1
+            && first.starts_with(|c: char| c.is_ascii_lowercase())
+            && !["self", "super"].contains(&first.as_str()) =>
+        {
+            Some(segments.join("::"))
+        }
+        None => None,
+    }
+}
+
+/// Returns the signatures of the `pub` functions and methods declared in a file.
+pub fn public_signatures(ast: &syn::File) -> Vec<&Signature> {
+    let mut visitor = PublicSignaturesVisitor { signatures: vec![] };
+    visitor.visit_file(ast);
+    visitor.signatures
+}
+
+struct PublicSignaturesVisitor<'ast> {
+    signatures: Vec<&'ast Signature>,
+}
+
+impl<'ast> Visit<'ast> for PublicSignaturesVisitor<'ast> {
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        if matches!(node.vis, Visibility::Public(_)) {
+            self.signatures.push(&node.sig);
+        }
+        visit::visit_item_fn(self, node);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        if matches!(node.vis, Visibility::Public(_)) {
+            self.signatures.push(&node.sig);
+        }
+        visit::visit_impl_item_fn(self, node);
+    }
+}
+
+/// Returns every type path in a signature, generic arguments included.
+pub fn signature_type_paths(signature: &Signature) -> Vec<&TypePath> {
+    let mut visitor = TypePathsVisitor { type_paths: vec![] };
+    visitor.visit_signature(signature);
+    visitor.type_paths
+}
+
+struct TypePathsVisitor<'ast> {
+    type_paths: Vec<&'ast TypePath>,
+}
+
+impl<'ast> Visit<'ast> for TypePathsVisitor<'ast> {
+    fn visit_type_path(&mut self, node: &'ast TypePath) {
+        self.type_paths.push(node);
+        visit::visit_type_path(self, node);
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::IsChild;
+    use super::{
+        classify_dependency, collapse_to_modules, external_crate_of, public_signatures,
+        DependencyKind, IsChild,
+    };
+    use crate::rust_file::RustFile;
 
     #[test]
     #[should_panic(expected = "Module cannot be an empty string")]
@@ -52,6 +252,89 @@ mod tests {
         assert!(!String::from("modulesubstring").is_child_of("module"));
     }
 
+    #[test]
+    fn test_classify_dependency() {
+        let file = RustFile::from_content(
+            "src/lib.rs",
+            "my_crate",
+            "mod model;\nuse crate::domain::billing;\n",
+        );
+
+        let cases = [
+            ("std::fs", DependencyKind::Std),
+            ("core::fmt", DependencyKind::Std),
+            ("alloc::vec::Vec", DependencyKind::Std),
+            ("serde::Serialize", DependencyKind::External),
+            ("stdx::io", DependencyKind::External),
+            ("my_crate::domain", DependencyKind::Internal),
+            ("crate::domain", DependencyKind::Internal),
+            ("model::Order", DependencyKind::Internal),
+            ("billing::Invoice", DependencyKind::Internal),
+        ];
+        for (dependency, kind) in cases {
+            assert_eq!(
+                classify_dependency(dependency, &file),
+                kind,
+                "{}",
+                dependency
+            );
+        }
+    }
+
+    #[test]
+    fn test_external_crate_of() {
+        let file = RustFile::from_content("src/lib.rs", "my_crate", "mod model;\n");
+
+        assert_eq!(external_crate_of("serde::Serialize", &file), Some("serde"));
+        assert_eq!(external_crate_of("model::Order", &file), None);
+        assert_eq!(external_crate_of("T::Item", &file), None);
+        assert_eq!(external_crate_of("std::fs", &file), None);
+    }
+
+    #[test]
+    fn test_collapse_to_modules() {
+        let dependencies: Vec<String> = [
+            "my_crate::domain::foo::A",
+            "my_crate::domain::foo::B",
+            "my_crate::domain::bar::C",
+            "my_crate::domain::foo::A",
+            "serde",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        assert_eq!(
+            collapse_to_modules(&dependencies),
+            vec!["my_crate::domain::foo", "my_crate::domain::bar::C", "serde"]
+        );
+    }
+
+    #[test]
+    fn test_public_signatures() {
+        let ast = syn::parse_str(
+            r#"
+            pub fn public_function() {}
+            fn private_function() {}
+            pub(crate) fn crate_function() {}
+
+            pub struct Service;
+
+            impl Service {
+                pub fn public_method(&self) {}
+                fn private_method(&self) {}
+            }
+            "#,
+        )
+        .unwrap();
+
+        let names: Vec<String> = public_signatures(&ast)
+            .iter()
+            .map(|sig| sig.ident.to_string())
+            .collect();
+
+        assert_eq!(names, vec!["public_function", "public_method"]);
+    }
+
     #[test]
     fn test_edge_cases() {
         assert!(!"mod".is_child_of("module::child"));
@@ -0,0 +1,229 @@
+use crate::error::ArkitectError;
+use crate::rust_file::{manifest_of, CrateNameCache, RustFile};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// The name of the cache file inside the cache directory.
+const CACHE_FILE: &str = "dependencies.json";
+
+/// The version of rust_arkitect the cache was written by: dependencies extracted by another one
+/// may differ, so its entries are dropped.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The dependencies extracted from each file on previous runs, stored as JSON in a cache directory
+/// so that the files left unchanged since are not parsed again, unless a rule needs their syntax
+/// tree.
+///
+/// An entry is reused while the modification times of its file and of the `Cargo.toml` of its
+/// crate are unchanged. The whole cache is dropped when it was written by another version of
+/// rust_arkitect or with other features enabled.
+pub struct DependencyCache {
+    path: PathBuf,
+    content: CacheContent,
+    files_parsed: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheContent {
+    #[serde(default)]
+    version: String,
+    features: Option<Vec<String>>,
+    files: BTreeMap<String, CachedFile>,
+}
+
+impl CacheContent {
+    fn empty(features: Option<Vec<String>>) -> Self {
+        Self {
+            version: VERSION.to_string(),
+            features,
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+/// What the rules not reading the syntax tree need to know about a file.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedFile {
+    modified: Modified,
+    relative_path: String,
+    logical_path: String,
+    dependencies: Vec<String>,
+    dependency_lines: HashMap<String, usize>,
+    reexports: Vec<String>,
+    reexport_origins: HashMap<String, String>,
+    is_test: bool,
+    test_dependencies: Vec<String>,
+    suppressed_rules: Vec<String>,
+    local_modules: Vec<String>,
+    external_crates: Vec<String>,
+}
+
+/// Modification times, in nanoseconds since the Unix epoch, of a file and of the `Cargo.toml` of
+/// its crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Modified {
+    file: u64,
+    manifest: Option<u64>,
+}
+
+impl Modified {
+    fn of(path: &str) -> Result<Self, Box<dyn Error>> {
+        let modified = |path: &Path| -> Result<u64, Box<dyn Error>> {
+            Ok(fs::metadata(path)?
+                .modified()?
+                .duration_since(UNIX_EPOCH)?
+                .as_nanos() as u64)
+        };
+
+        Ok(Self {
+            file: modified(Path::new(path))?,
+            manifest: manifest_of(path)
+                .map(|manifest| modified(&manifest))
+                .transpose()?,
+        })
+    }
+}
+
+impl DependencyCache {
+    /// Opens the cache stored in `cache_dir`, empty when there is none, it can't be read or it was
+    /// written by another version.
+    pub fn open(cache_dir: &Path) -> Self {
+        let path = cache_dir.join(CACHE_FILE);
+        let content = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                warn!("Ignoring invalid cache {}: {}", path.display(), e);
+                CacheContent::default()
+            }),
+            Err(_) => CacheContent::default(),
+        };
+        let content = if content.version == VERSION {
+            content
+        } else {
+            CacheContent::empty(content.features)
+        };
+
+        Self {
+            path,
+            content,
+            files_parsed: 0,
+        }
+    }
+
+    /// Writes the cache to its directory, creating it when missing.
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string(&self.content)?)?;
+
+        Ok(())
+    }
+
+    /// How many files were parsed since the cache was opened, the others were read from it.
+    pub fn files_parsed(&self) -> usize {
+        self.files_parsed
+    }
+
+    /// Forgets every entry when they were extracted with other `features` enabled.
+    pub(crate) fn use_features(&mut self, features: &Option<Vec<String>>) {
+        if &self.content.features != features {
+            self.content = CacheContent::empty(features.clone());
+        }
+    }
+
+    /// Forgets the files that are no longer part of the project.
+    pub(crate) fn retain_files(&mut self, paths: &[String]) {
+        let paths: HashSet<&str> = paths.iter().map(String::as_str).collect();
+        self.content
+            .files
+            .retain(|path, _| paths.contains(path.as_str()));
+    }
+
+    /// The file at `path`, read from the cache when neither it nor its manifest were modified
+    /// since it was stored and `needs_syntax_tree` says the cached file, whose `ast` is empty, is
+    /// enough. Otherwise the file is parsed, and stored.
+    pub(crate) fn file(
+        &mut self,
+        path: &str,
+        crate_names: &mut CrateNameCache,
+        needs_syntax_tree: impl FnOnce(&RustFile) -> bool,
+    ) -> Result<RustFile, ArkitectError> {
+        let io_error = |e: Box<dyn Error>| ArkitectError::Io {
+            path: path.replace('\\', "/"),
+            reason: e.to_string(),
+        };
+        let modified = Modified::of(path).map_err(io_error)?;
+
+        if let Some(cached) = self.content.files.get(path) {
+            if cached.modified == modified {
+                let file = cached.to_rust_file(path);
+                if !needs_syntax_tree(&file) {
+                    return Ok(file);
+                }
+            }
+        }
+
+        let file = RustFile::try_from_file_system_with_features(
+            path,
+            crate_names,
+            self.content.features.as_deref(),
+        )?;
+        self.files_parsed += 1;
+        self.content
+            .files
+            .insert(path.to_string(), CachedFile::of(&file, modified));
+
+        Ok(file)
+    }
+}
+
+impl CachedFile {
+    fn of(file: &RustFile, modified: Modified) -> Self {
+        Self {
+            modified,
+            relative_path: file.relative_path.clone(),
+            logical_path: file.logical_path.clone(),
+            dependencies: file.dependencies.clone(),
+            dependency_lines: file.dependency_lines.clone(),
+            reexports: file.reexports.clone(),
+            reexport_origins: file.reexport_origins().clone(),
+            is_test: file.is_test,
+            test_dependencies: file.test_dependencies.clone(),
+            suppressed_rules: file.suppressed_rules.clone(),
+            local_modules: file.local_modules.clone(),
+            external_crates: file.external_crates.clone(),
+        }
+    }
+
+    /// The file stored at `path`, with an empty syntax tree.
+    fn to_rust_file(&self, path: &str) -> RustFile {
+        let segment = |segment: Option<&str>| segment.unwrap_or_default().to_string();
+
+        RustFile {
+            path: path.replace('\\', "/"),
+            relative_path: self.relative_path.clone(),
+            module_name: segment(self.logical_path.rsplit("::").next()),
+            crate_name: segment(self.logical_path.split("::").next()),
+            logical_path: self.logical_path.clone(),
+            dependencies: self.dependencies.clone(),
+            dependency_lines: self.dependency_lines.clone(),
+            reexports: self.reexports.clone(),
+            reexport_origins: self.reexport_origins.clone().into(),
+            is_test: self.is_test,
+            test_dependencies: self.test_dependencies.clone(),
+            suppressed_rules: self.suppressed_rules.clone(),
+            local_modules: self.local_modules.clone(),
+            external_crates: self.external_crates.clone(),
+            ast: syn::File {
+                shebang: None,
+                attrs: vec![],
+                items: vec![],
+            },
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod architectural_rules;
 pub mod arkitect;
+pub mod config;
 pub mod project;
@@ -1,216 +1,416 @@
+use crate::builtin_rules::configured::{Configured, DependencyRule};
+use crate::builtin_rules::crate_may_depend_on_crates::CrateMayDependOnCrates;
+use crate::builtin_rules::doc_links_must_resolve::DocLinksMustResolve;
+use crate::builtin_rules::error_types_must_implement_error::ErrorTypesMustImplementError;
+use crate::builtin_rules::forbid_external_crates::ForbidExternalCratesRule;
+use crate::builtin_rules::library_must_not_depend_on_binaries::LibraryMustNotDependOnBinaries;
 use crate::builtin_rules::may_depend_on::MayDependOnRule;
+use crate::builtin_rules::may_depend_on_except::MayDependOnExceptRule;
+use crate::builtin_rules::may_depend_on_only_via::MayDependOnOnlyViaRule;
+use crate::builtin_rules::modules_must_be_stable::ModulesMustBeStable;
+use crate::builtin_rules::must_depend_on::MustDependOnRule;
+use crate::builtin_rules::must_depend_on_abstractions::MustDependOnAbstractionsRule;
+use crate::builtin_rules::must_form_a_dag::MustFormADag;
+use crate::builtin_rules::must_have_module_doc::MustHaveModuleDocRule;
+use crate::builtin_rules::must_hide_internals::MustHideInternals;
+use crate::builtin_rules::must_not_access_fields_of::MustNotAccessFieldsOfRule;
+use crate::builtin_rules::must_not_contain_attribute::MustNotContainAttributeRule;
 use crate::builtin_rules::must_not_depend_on::MustNotDependOnRule;
 use crate::builtin_rules::must_not_depend_on_anything::MustNotDependOnAnythingRule;
+use crate::builtin_rules::must_not_exceed_external_crates::MustNotExceedExternalCrates;
+use crate::builtin_rules::must_not_exceed_module_depth::MustNotExceedModuleDepthRule;
+use crate::builtin_rules::must_not_exceed_public_items::MustNotExceedPublicItemsRule;
 use crate::builtin_rules::must_not_have_circular_dependencies::MustNotHaveCircularDependencies;
+use crate::builtin_rules::must_not_have_duplicate_modules::MustNotHaveDuplicateModules;
+use crate::builtin_rules::must_not_have_orphan_modules::MustNotHaveOrphanModules;
+use crate::builtin_rules::must_not_have_unused_imports::MustNotHaveUnusedImportsRule;
+use crate::builtin_rules::must_not_reexport_from::MustNotReexportFromRule;
+use crate::builtin_rules::must_not_reexport_globs::MustNotReexportGlobsRule;
+use crate::builtin_rules::must_not_use_async::MustNotUseAsyncRule;
+use crate::builtin_rules::must_not_use_debug_macros::{
+    MustNotUseDebugMacrosRule, DEFAULT_DEBUG_MACROS,
+};
+use crate::builtin_rules::must_not_use_deprecated::MustNotUseDeprecatedRule;
+use crate::builtin_rules::must_reexport_through_facade::MustReexportThroughFacade;
+use crate::builtin_rules::must_reside_in::MustResideInRule;
+use crate::builtin_rules::must_respect_coupling_limits::{
+    CouplingLimit, MustRespectCouplingLimits,
+};
+use crate::builtin_rules::must_respect_maturity_tiers::MustRespectMaturityTiers;
+use crate::builtin_rules::must_respect_tier_order::MustRespectTierOrder;
+use crate::builtin_rules::must_use_single_error_type::MustUseSingleErrorType;
+use crate::builtin_rules::public_api_must_match_snapshot::PublicApiMustMatchSnapshot;
+use crate::builtin_rules::public_signatures_may_expose_external::PublicSignaturesMayExposeExternalRule;
+use crate::builtin_rules::public_signatures_must_not_expose::PublicSignaturesMustNotExposeRule;
+use crate::builtin_rules::public_signatures_must_not_use::PublicSignaturesMustNotUseRule;
+use crate::builtin_rules::subject::Subject;
+use crate::builtin_rules::test_code_may_depend_on::TestCodeMayDependOnRule;
+use crate::builtin_rules::tests_must_be_isolated::TestsMustBeIsolatedRule;
 use crate::dsl::arkitect::Rules;
-use crate::rule::Rule;
+use crate::rule::{LocatedViolation, ProjectRule, Rule, RuleDescription, Severity};
+use crate::rust_file::RustFile;
+use crate::rust_project::RustProject;
+use crate::styling::Styling;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::marker::PhantomData;
 
 pub struct Begin;
 pub struct SubjectDefined;
 pub struct ProjectDefined;
 pub struct ProjectBegin;
+pub struct ProjectSubjectDefined;
 pub struct RulesDefined;
+pub struct LayersDefined;
+
+/// How the list passed to `layers_in_order` is ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerOrder {
+    /// The first layer is the innermost one (e.g. `domain`): it may not depend on any other layer.
+    BottomUp,
+    /// The first layer is the outermost one (e.g. `infrastructure`): it may depend on every other layer.
+    TopDown,
+}
 
 pub struct ArchitecturalRules<State> {
     state: PhantomData<State>,
-    current_subject: Option<String>,
+    current_subject: Option<Subject>,
+    /// External paths allowed to every rule restricting the subject's dependencies.
+    default_external_dependencies: Vec<String>,
+    may_depend_on_rules: Vec<MayDependOnEntry>,
+    /// The list the last rule was added to, which `as_warning` amends.
+    last_rule: Option<RuleList>,
     rules: Rules,
 }
 
-pub trait SubjectInjectableRuleBuilder {
-    fn for_subject(&self, subject: &str) -> Box<dyn Rule>;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleList {
+    Module,
+    Project,
 }
 
-impl ArchitecturalRules<Begin> {
-    pub fn define() -> Self {
-        Self {
-            state: PhantomData,
-            current_subject: None,
-            rules: Rules {
-                module_rules: vec![],
-                project_rules: vec![],
-            },
+/// A `may_depend_on` among the module rules, kept typed so that `except` and
+/// `with_transitive_allowances` can amend it.
+struct MayDependOnEntry {
+    /// Position among the module rules.
+    index: usize,
+    rule: Configured<MayDependOnRule>,
+    denied_dependencies: Vec<String>,
+}
+
+impl MayDependOnEntry {
+    fn to_rule(&self) -> Box<dyn Rule> {
+        if self.denied_dependencies.is_empty() {
+            Box::new(self.rule.clone())
+        } else {
+            Box::new(MayDependOnExceptRule {
+                rule: self.rule.clone(),
+                denied_dependencies: self.denied_dependencies.clone(),
+            })
         }
     }
+}
 
-    pub fn rules_for_crate(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
-        ArchitecturalRules {
-            state: PhantomData,
-            current_subject: Some(String::from(crate_name)),
-            rules: self.rules,
+pub trait SubjectInjectableRuleBuilder {
+    fn for_subject(&self, subject: &str) -> Box<dyn Rule>;
+
+    /// Builds the rule for the current subject, which is a regex when set by
+    /// `rules_for_modules_matching`. Defaults to `for_subject` for module paths and panics for
+    /// regexes: override it, matching files with `Subject::matches`, to support them.
+    fn for_subject_matching(&self, subject: &Subject) -> Box<dyn Rule> {
+        match subject {
+            Subject::Prefix(subject) => self.for_subject(subject),
+            Subject::Regex(_) => panic!("Rule builder doesn't support regex subject '{}'", subject),
         }
     }
+}
 
-    pub fn rules_for_module(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
-        ArchitecturalRules {
-            state: PhantomData,
-            current_subject: Some(String::from(crate_name)),
-            rules: self.rules,
-        }
+/// Builds a `ForbidExternalCratesRule` for the current subject:
+/// `.it(ForbidExternalCrates::except(&["serde", "tokio"]))`.
+pub struct ForbidExternalCrates {
+    allowed_crates: Vec<String>,
+}
+
+impl ForbidExternalCrates {
+    pub fn except(crates: &[&str]) -> Box<dyn SubjectInjectableRuleBuilder> {
+        Box::new(Self {
+            allowed_crates: crates.iter().map(|&c| c.to_string()).collect(),
+        })
     }
+}
 
-    pub fn rules_for_project(self) -> ArchitecturalRules<ProjectBegin> {
-        ArchitecturalRules {
-            state: PhantomData,
-            current_subject: None,
-            rules: self.rules,
-        }
+impl SubjectInjectableRuleBuilder for ForbidExternalCrates {
+    fn for_subject(&self, subject: &str) -> Box<dyn Rule> {
+        self.for_subject_matching(&Subject::from(subject))
+    }
+
+    fn for_subject_matching(&self, subject: &Subject) -> Box<dyn Rule> {
+        Box::new(ForbidExternalCratesRule {
+            subject: subject.clone(),
+            allowed_crates: self.allowed_crates.clone(),
+        })
     }
 }
 
-impl ArchitecturalRules<ProjectBegin> {
-    pub fn it_must_not_have_circular_dependencies(
-        self,
-        max_depth: usize,
-    ) -> ArchitecturalRules<ProjectDefined> {
-        let rule = Box::new(MustNotHaveCircularDependencies { max_depth });
+/// Builds a `MustDependOnRule` for the current subject:
+/// `.it(MustDependOn::new("crate::telemetry"))`.
+pub struct MustDependOn {
+    required_dependency: String,
+}
 
-        let mut project_rules = self.rules.project_rules;
-        project_rules.push(rule);
+impl MustDependOn {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(required_dependency: &str) -> Box<dyn SubjectInjectableRuleBuilder> {
+        Box::new(Self {
+            required_dependency: required_dependency.to_string(),
+        })
+    }
+}
 
-        ArchitecturalRules {
-            state: PhantomData,
-            current_subject: self.current_subject,
-            rules: Rules {
-                project_rules,
-                ..self.rules
-            },
-        }
+impl SubjectInjectableRuleBuilder for MustDependOn {
+    fn for_subject(&self, subject: &str) -> Box<dyn Rule> {
+        self.for_subject_matching(&Subject::from(subject))
+    }
+
+    fn for_subject_matching(&self, subject: &Subject) -> Box<dyn Rule> {
+        Box::new(MustDependOnRule {
+            subject: subject.clone(),
+            required_dependency: self.required_dependency.clone(),
+        })
     }
 }
 
-impl ArchitecturalRules<ProjectDefined> {
-    pub fn rules_for_crate(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
-        ArchitecturalRules {
-            state: PhantomData,
-            current_subject: Some(String::from(crate_name)),
-            rules: self.rules,
-        }
+/// Builds a `MustHaveModuleDocRule` for the current subject:
+/// `.it(MustHaveModuleDoc::non_empty())`.
+pub struct MustHaveModuleDoc {
+    allow_empty: bool,
+}
+
+impl MustHaveModuleDoc {
+    /// Files must start with a module doc that isn't blank.
+    pub fn non_empty() -> Box<dyn SubjectInjectableRuleBuilder> {
+        Box::new(Self { allow_empty: false })
     }
 
-    pub fn rules_for_module(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
-        ArchitecturalRules {
-            state: PhantomData,
-            current_subject: Some(String::from(crate_name)),
-            rules: self.rules,
-        }
+    /// Files must start with a module doc, even a blank `//!`.
+    pub fn allowing_empty() -> Box<dyn SubjectInjectableRuleBuilder> {
+        Box::new(Self { allow_empty: true })
+    }
+}
+
+impl SubjectInjectableRuleBuilder for MustHaveModuleDoc {
+    fn for_subject(&self, subject: &str) -> Box<dyn Rule> {
+        self.for_subject_matching(&Subject::from(subject))
     }
 
-    pub fn build(self) -> Rules {
-        self.rules
+    fn for_subject_matching(&self, subject: &Subject) -> Box<dyn Rule> {
+        Box::new(MustHaveModuleDocRule {
+            subject: subject.clone(),
+            allow_empty: self.allow_empty,
+        })
     }
 }
 
-impl ArchitecturalRules<SubjectDefined> {
-    pub fn it_may_depend_on(self, dependencies: &[&str]) -> ArchitecturalRules<RulesDefined> {
-        let rule = Box::new(MayDependOnRule {
-            subject: self.current_subject.clone().unwrap(),
-            allowed_dependencies: dependencies.iter().map(|&s| s.to_string()).collect(),
-        });
+/// Builds a `MustNotAccessFieldsOfRule` for the current subject:
+/// `.it(MustNotAccessFieldsOf::components(&["crate::billing"]))`.
+pub struct MustNotAccessFieldsOf {
+    forbidden_components: Vec<String>,
+}
 
-        let mut rules = self.rules.module_rules;
-        rules.push(rule);
+impl MustNotAccessFieldsOf {
+    pub fn components(components: &[&str]) -> Box<dyn SubjectInjectableRuleBuilder> {
+        Box::new(Self {
+            forbidden_components: components.iter().map(|&c| c.to_string()).collect(),
+        })
+    }
+}
 
-        ArchitecturalRules {
-            state: PhantomData,
-            current_subject: self.current_subject,
-            rules: Rules {
-                module_rules: rules,
-                ..self.rules
-            },
-        }
+impl SubjectInjectableRuleBuilder for MustNotAccessFieldsOf {
+    fn for_subject(&self, subject: &str) -> Box<dyn Rule> {
+        self.for_subject_matching(&Subject::from(subject))
     }
 
-    pub fn it_must_not_depend_on(self, dependencies: &[&str]) -> ArchitecturalRules<RulesDefined> {
-        let rule = Box::new(MustNotDependOnRule {
-            subject: self.current_subject.clone().unwrap(),
-            forbidden_dependencies: dependencies.iter().map(|&s| s.to_string()).collect(),
-        });
+    fn for_subject_matching(&self, subject: &Subject) -> Box<dyn Rule> {
+        Box::new(MustNotAccessFieldsOfRule {
+            subject: subject.clone(),
+            forbidden_components: self.forbidden_components.clone(),
+        })
+    }
+}
 
-        let mut rules = self.rules.module_rules;
-        rules.push(rule);
+/// Builds a `MustNotContainAttributeRule` for the current subject:
+/// `.it(MustNotContainAttribute::new("#[derive(Serialize)]"))`.
+pub struct MustNotContainAttribute {
+    attribute: String,
+}
 
-        ArchitecturalRules {
-            state: PhantomData,
-            current_subject: self.current_subject,
-            rules: Rules {
-                module_rules: rules,
-                ..self.rules
-            },
-        }
+impl MustNotContainAttribute {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(attribute: &str) -> Box<dyn SubjectInjectableRuleBuilder> {
+        MustNotContainAttributeRule::parse_pattern(attribute);
+
+        Box::new(Self {
+            attribute: attribute.to_string(),
+        })
     }
+}
 
-    pub fn it_must_not_depend_on_anything(self) -> ArchitecturalRules<RulesDefined> {
-        let rule = Box::new(MustNotDependOnAnythingRule {
-            subject: self.current_subject.clone().unwrap(),
-            allowed_external_dependencies: vec![],
-        });
+impl SubjectInjectableRuleBuilder for MustNotContainAttribute {
+    fn for_subject(&self, subject: &str) -> Box<dyn Rule> {
+        self.for_subject_matching(&Subject::from(subject))
+    }
 
-        let mut rules = self.rules.module_rules;
-        rules.push(rule);
+    fn for_subject_matching(&self, subject: &Subject) -> Box<dyn Rule> {
+        Box::new(MustNotContainAttributeRule {
+            subject: subject.clone(),
+            attribute: self.attribute.clone(),
+        })
+    }
+}
 
-        ArchitecturalRules {
-            state: PhantomData,
-            current_subject: self.current_subject,
-            rules: Rules {
-                module_rules: rules,
-                ..self.rules
-            },
-        }
+/// Builds a `MustNotUseDebugMacrosRule` for the current subject:
+/// `.it(MustNotUseDebugMacros::dbg_only())`.
+pub struct MustNotUseDebugMacros {
+    forbidden_macros: Vec<String>,
+}
+
+impl MustNotUseDebugMacros {
+    /// Forbids `dbg!`.
+    pub fn dbg_only() -> Box<dyn SubjectInjectableRuleBuilder> {
+        Self::named(DEFAULT_DEBUG_MACROS)
     }
 
-    pub fn it(
-        self,
-        rule: Box<dyn SubjectInjectableRuleBuilder>,
-    ) -> ArchitecturalRules<RulesDefined> {
-        let rule = rule.for_subject(self.current_subject.as_ref().unwrap());
+    /// Forbids the given macros, named without `!`, e.g. `&["dbg", "println", "eprintln"]`.
+    pub fn named(macros: &[&str]) -> Box<dyn SubjectInjectableRuleBuilder> {
+        Box::new(Self {
+            forbidden_macros: macros.iter().map(|&m| m.to_string()).collect(),
+        })
+    }
+}
 
-        let mut rules = self.rules.module_rules;
-        rules.push(rule);
+impl SubjectInjectableRuleBuilder for MustNotUseDebugMacros {
+    fn for_subject(&self, subject: &str) -> Box<dyn Rule> {
+        self.for_subject_matching(&Subject::from(subject))
+    }
 
-        ArchitecturalRules {
-            state: PhantomData,
-            current_subject: self.current_subject,
-            rules: Rules {
-                module_rules: rules,
-                ..self.rules
-            },
-        }
+    fn for_subject_matching(&self, subject: &Subject) -> Box<dyn Rule> {
+        Box::new(MustNotUseDebugMacrosRule {
+            subject: subject.clone(),
+            forbidden_macros: self.forbidden_macros.clone(),
+        })
     }
 }
 
-impl ArchitecturalRules<RulesDefined> {
-    pub fn and_it_may_depend_on(self, dependencies: &[&str]) -> ArchitecturalRules<RulesDefined> {
-        let rule = Box::new(MayDependOnRule {
-            subject: self.current_subject.clone().unwrap(),
-            allowed_dependencies: dependencies.iter().map(|&s| s.to_string()).collect(),
-        });
+/// Builds a `MustNotUseAsyncRule` for the current subject: `.it(MustNotUseAsync::new())`.
+pub struct MustNotUseAsync;
 
-        let mut rules = self.rules.module_rules;
-        rules.push(rule);
+impl MustNotUseAsync {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> Box<dyn SubjectInjectableRuleBuilder> {
+        Box::new(Self)
+    }
+}
 
-        ArchitecturalRules {
-            state: PhantomData,
-            current_subject: self.current_subject,
-            rules: Rules {
-                module_rules: rules,
-                ..self.rules
-            },
-        }
+impl SubjectInjectableRuleBuilder for MustNotUseAsync {
+    fn for_subject(&self, subject: &str) -> Box<dyn Rule> {
+        self.for_subject_matching(&Subject::from(subject))
     }
 
-    pub fn and_must_not_depend_on(self, dependencies: &[&str]) -> ArchitecturalRules<RulesDefined> {
-        let rule = Box::new(MustNotDependOnRule {
-            subject: self.current_subject.clone().unwrap(),
-            forbidden_dependencies: dependencies.iter().map(|&s| s.to_string()).collect(),
-        });
+    fn for_subject_matching(&self, subject: &Subject) -> Box<dyn Rule> {
+        Box::new(MustNotUseAsyncRule {
+            subject: subject.clone(),
+        })
+    }
+}
+
+/// A module rule whose violations are reported as warnings.
+struct WarningRule(Box<dyn Rule>);
+
+impl Display for WarningRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Rule for WarningRule {
+    fn apply(&self, file: &RustFile) -> Result<(), String> {
+        self.0.apply(file)
+    }
+
+    fn render(&self, styling: &Styling) -> String {
+        self.0.render(styling)
+    }
+
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        self.0.apply_located(file)
+    }
+
+    fn is_applicable(&self, file: &RustFile) -> bool {
+        self.0.is_applicable(file)
+    }
+
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn describe(&self) -> RuleDescription {
+        self.0.describe()
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        self.0.needs_syntax_tree()
+    }
+}
+
+/// A project rule whose violations are reported as warnings.
+struct WarningProjectRule(Box<dyn ProjectRule>);
+
+impl Display for WarningProjectRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ProjectRule for WarningProjectRule {
+    fn apply(&self, project: &RustProject) -> Result<(), String> {
+        self.0.apply(project)
+    }
+
+    fn render(&self, styling: &Styling) -> String {
+        self.0.render(styling)
+    }
+
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn describe(&self) -> RuleDescription {
+        self.0.describe()
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn needs_syntax_tree(&self) -> bool {
+        self.0.needs_syntax_tree()
+    }
+}
 
+impl<State> ArchitecturalRules<State> {
+    fn with_module_rule(self, rule: Box<dyn Rule>) -> ArchitecturalRules<RulesDefined> {
         let mut rules = self.rules.module_rules;
         rules.push(rule);
 
         ArchitecturalRules {
             state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: Some(RuleList::Module),
             current_subject: self.current_subject,
             rules: Rules {
                 module_rules: rules,
@@ -219,48 +419,100 @@ impl ArchitecturalRules<RulesDefined> {
         }
     }
 
-    pub fn and_it_must_not_depend_on_anything(self) -> ArchitecturalRules<RulesDefined> {
-        let rule = Box::new(MustNotDependOnAnythingRule {
-            subject: self.current_subject.clone().unwrap(),
-            allowed_external_dependencies: vec![],
+    fn with_may_depend_on_rule(
+        mut self,
+        rule: Configured<MayDependOnRule>,
+    ) -> ArchitecturalRules<RulesDefined> {
+        self.may_depend_on_rules.push(MayDependOnEntry {
+            index: self.rules.module_rules.len(),
+            rule: rule.clone(),
+            denied_dependencies: vec![],
         });
 
-        let mut rules = self.rules.module_rules;
-        rules.push(rule);
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// Replaces the module rule at `index` with `rule`, keeping the severity of the replaced one.
+    fn replace_module_rule(&mut self, index: usize, rule: Box<dyn Rule>) {
+        let replaced = &mut self.rules.module_rules[index];
+        *replaced = match replaced.severity() {
+            Severity::Warning => Box::new(WarningRule(rule)),
+            Severity::Error => rule,
+        };
+    }
+
+    fn with_project_rule<Next>(self, rule: Box<dyn ProjectRule>) -> ArchitecturalRules<Next> {
+        let mut project_rules = self.rules.project_rules;
+        project_rules.push(rule);
 
         ArchitecturalRules {
             state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: Some(RuleList::Project),
             current_subject: self.current_subject,
             rules: Rules {
-                module_rules: rules,
+                project_rules,
                 ..self.rules
             },
         }
     }
 
-    pub fn and_it(
-        self,
-        rule: Box<dyn SubjectInjectableRuleBuilder>,
-    ) -> ArchitecturalRules<RulesDefined> {
-        let rule = rule.for_subject(self.current_subject.as_ref().unwrap());
+    fn subject(&self) -> Subject {
+        self.current_subject.clone().unwrap()
+    }
 
-        let mut rules = self.rules.module_rules;
-        rules.push(rule);
+    /// Applies `rule` to the current subject, which may be a regex.
+    fn for_current_subject<R: DependencyRule>(&self, rule: R) -> Configured<R> {
+        rule.matching(self.subject())
+    }
 
-        ArchitecturalRules {
+    /// `dependencies` followed by the default external dependencies not already listed.
+    fn with_default_externals(&self, dependencies: &[&str]) -> Vec<String> {
+        let mut allowed: Vec<String> = dependencies.iter().map(|&s| s.to_string()).collect();
+        for external in &self.default_external_dependencies {
+            if !allowed.contains(external) {
+                allowed.push(external.clone());
+            }
+        }
+
+        allowed
+    }
+}
+
+impl ArchitecturalRules<Begin> {
+    pub fn define() -> Self {
+        Self {
             state: PhantomData,
-            current_subject: self.current_subject,
+            default_external_dependencies: vec![],
+            may_depend_on_rules: vec![],
+            last_rule: None,
+            current_subject: None,
             rules: Rules {
-                module_rules: rules,
-                ..self.rules
+                module_rules: vec![],
+                project_rules: vec![],
+                baseline: None,
             },
         }
     }
 
+    /// External paths every subject may depend on, e.g. `&["std"]`, added to the allowed
+    /// dependencies of the `may_depend_on`, `must_not_depend_on_anything` and `layers` rules
+    /// defined afterwards. Rules can still allow other paths of their own.
+    pub fn default_external_dependencies(self, dependencies: &[&str]) -> Self {
+        Self {
+            default_external_dependencies: dependencies.iter().map(|&s| s.to_string()).collect(),
+            ..self
+        }
+    }
+
     pub fn rules_for_crate(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
         ArchitecturalRules {
             state: PhantomData,
-            current_subject: Some(String::from(crate_name)),
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(Subject::from(crate_name)),
             rules: self.rules,
         }
     }
@@ -268,21 +520,1301 @@ impl ArchitecturalRules<RulesDefined> {
     pub fn rules_for_module(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
         ArchitecturalRules {
             state: PhantomData,
-            current_subject: Some(String::from(crate_name)),
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(Subject::from(crate_name)),
             rules: self.rules,
         }
     }
 
-    pub fn build(self) -> Rules {
-        self.rules
+    /// Targets every module matching `pattern`, e.g. `my_crate::api_v\d+`. Panics if the regex is invalid.
+    pub fn rules_for_modules_matching(self, pattern: &str) -> ArchitecturalRules<SubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(regex_subject(pattern)),
+            rules: self.rules,
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::rust_file::RustFile;
-    use std::fmt::{Display, Formatter};
+    pub fn rules_for_project(self) -> ArchitecturalRules<ProjectBegin> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: None,
+            rules: self.rules,
+        }
+    }
+
+    /// Defines a layered architecture, listing the layers from the innermost to the outermost:
+    /// `layers(&["domain", "application", "infrastructure"])` lets `infrastructure` depend on
+    /// `application` and `domain`, `application` on `domain`, and `domain` on nothing.
+    ///
+    /// Emits one `MayDependOnRule` per layer.
+    pub fn layers(self, layers: &[&str]) -> ArchitecturalRules<LayersDefined> {
+        self.layers_in_order(layers, LayerOrder::BottomUp)
+    }
+
+    /// Like `layers`, with an explicit direction for the list of layers.
+    pub fn layers_in_order(
+        self,
+        layers: &[&str],
+        order: LayerOrder,
+    ) -> ArchitecturalRules<LayersDefined> {
+        let mut bottom_up: Vec<&str> = layers.to_vec();
+        if order == LayerOrder::TopDown {
+            bottom_up.reverse();
+        }
+
+        let layer_rules: Vec<Configured<MayDependOnRule>> = bottom_up
+            .iter()
+            .enumerate()
+            .map(|(i, layer)| {
+                Configured::new(MayDependOnRule::new(
+                    *layer,
+                    self.with_default_externals(&bottom_up[..i]),
+                ))
+            })
+            .collect();
+        let mut module_rules = self.rules.module_rules;
+        let mut may_depend_on_rules = self.may_depend_on_rules;
+        for rule in layer_rules {
+            may_depend_on_rules.push(MayDependOnEntry {
+                index: module_rules.len(),
+                rule: rule.clone(),
+                denied_dependencies: vec![],
+            });
+            module_rules.push(Box::new(rule));
+        }
+
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules,
+            last_rule: Some(RuleList::Module),
+            current_subject: None,
+            rules: Rules {
+                module_rules,
+                ..self.rules
+            },
+        }
+    }
+}
+
+impl ArchitecturalRules<LayersDefined> {
+    pub fn rules_for_crate(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(Subject::from(crate_name)),
+            rules: self.rules,
+        }
+    }
+
+    pub fn rules_for_module(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(Subject::from(crate_name)),
+            rules: self.rules,
+        }
+    }
+
+    /// Targets every module matching `pattern`, e.g. `my_crate::api_v\d+`. Panics if the regex is invalid.
+    pub fn rules_for_modules_matching(self, pattern: &str) -> ArchitecturalRules<SubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(regex_subject(pattern)),
+            rules: self.rules,
+        }
+    }
+
+    pub fn build(self) -> Rules {
+        self.rules
+    }
+}
+
+impl ArchitecturalRules<ProjectBegin> {
+    pub fn it_must_not_have_circular_dependencies(
+        self,
+        max_depth: usize,
+    ) -> ArchitecturalRules<ProjectDefined> {
+        let rule = Box::new(MustNotHaveCircularDependencies { max_depth });
+
+        let mut project_rules = self.rules.project_rules;
+        project_rules.push(rule);
+
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: Some(RuleList::Project),
+            current_subject: self.current_subject,
+            rules: Rules {
+                project_rules,
+                ..self.rules
+            },
+        }
+    }
+
+    /// Like `it_must_not_have_circular_dependencies`, following `pub use` re-exports to their
+    /// origin, so a cycle hidden behind a facade module is reported.
+    pub fn it_must_not_have_circular_dependencies_through_reexports(
+        self,
+        max_depth: usize,
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(
+            MustNotHaveCircularDependencies { max_depth }.through_reexports(),
+        ))
+    }
+
+    /// Forbids modules from depending on modules with a lower maturity tier,
+    /// e.g. `&[("my_crate::stable", 2), ("my_crate::alpha", 0)]`.
+    pub fn it_must_respect_maturity_tiers(
+        self,
+        tiers: &[(&str, usize)],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(maturity_tiers_rule(tiers)))
+    }
+
+    /// Forbids dependencies from a tier to a higher one, listing the tiers from the lowest,
+    /// e.g. `&[&["my_crate::domain"], &["my_crate::application"], &["my_crate::cli"]]`.
+    pub fn it_must_respect_tier_order(
+        self,
+        tiers: &[&[&str]],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        let rule = MustRespectTierOrder {
+            tiers: tiers
+                .iter()
+                .map(|prefixes| prefixes.iter().map(|&s| s.to_string()).collect())
+                .collect(),
+        };
+
+        self.with_project_rule(Box::new(rule))
+    }
+
+    /// Modules outside `component` may depend on it but not on its `private_submodules`,
+    /// e.g. `("crate::billing", &["internal"])`.
+    pub fn it_must_hide_internals_of(
+        self,
+        component: &str,
+        private_submodules: &[&str],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustHideInternals {
+            component: component.to_string(),
+            private_submodules: private_submodules.iter().map(|&s| s.to_string()).collect(),
+        }))
+    }
+
+    /// The `pub` items of the `internal_submodules` of `component` must be re-exported by the
+    /// component root, e.g. `("crate::domain", &["internal"])`.
+    pub fn it_must_reexport_through_facade(
+        self,
+        component: &str,
+        internal_submodules: &[&str],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustReexportThroughFacade {
+            component: component.to_string(),
+            internal_submodules: internal_submodules.iter().map(|&s| s.to_string()).collect(),
+        }))
+    }
+
+    /// The files of `crate_name` may depend on no other crate of the project than
+    /// `allowed_crates`, e.g. `("application", &["domain"])` in a workspace.
+    pub fn it_crate_may_depend_on_crates(
+        self,
+        crate_name: &str,
+        allowed_crates: &[&str],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(CrateMayDependOnCrates {
+            crate_name: crate_name.to_string(),
+            allowed_crates: allowed_crates.iter().map(|&s| s.to_string()).collect(),
+        }))
+    }
+
+    /// Each crate may use at most `max_external_crates` distinct external crates.
+    pub fn it_must_not_exceed_external_crates(
+        self,
+        max_external_crates: usize,
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustNotExceedExternalCrates {
+            max_external_crates,
+        }))
+    }
+
+    /// Components given as `(name, location)` pairs, e.g. `("domain", "crate::domain")`, must
+    /// not be coupled with more components than their limit, e.g.
+    /// `&[("application", CouplingLimit { max_efferent: Some(2), ..Default::default() })]`.
+    pub fn it_must_respect_coupling_limits(
+        self,
+        components: &[(&str, &str)],
+        limits: &[(&str, CouplingLimit)],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(coupling_limits_rule(components, limits)))
+    }
+
+    /// Intra-doc links rooted at `crate::` must point to existing modules and items.
+    pub fn it_doc_links_must_resolve(self) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(DocLinksMustResolve))
+    }
+
+    /// The modules under `subtree` (e.g. `crate::domain`) must not depend on each other in
+    /// cycles, at full module granularity. Cycles involving other modules are ignored.
+    pub fn it_must_form_a_dag(self, subtree: &str) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustFormADag {
+            subtree: subtree.to_string(),
+        }))
+    }
+
+    /// No two files may define the same module, e.g. `foo.rs` and `foo/mod.rs`.
+    pub fn it_must_not_have_duplicate_modules(self) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustNotHaveDuplicateModules))
+    }
+
+    /// Library code must not depend on the modules of `src/bin/`, `examples/`, `tests/` or
+    /// `benches/`; scan them with `Project::with_targets`.
+    pub fn it_library_must_not_depend_on_binaries(self) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(LibraryMustNotDependOnBinaries))
+    }
+
+    /// Every scanned module must be depended on by another one, except the `roots`
+    /// (e.g. `&["lib", "main", "bin"]`). Test modules are not reported.
+    pub fn it_must_not_have_orphan_modules(
+        self,
+        roots: &[&str],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustNotHaveOrphanModules::new(roots)))
+    }
+
+    /// Public items listed in the snapshot at `snapshot_path` must not be removed or changed.
+    /// The check fails when the snapshot is missing: run with `ARKITECT_UPDATE_SNAPSHOTS=1` to
+    /// write it, or to accept the changes.
+    pub fn public_api_must_match_snapshot(
+        self,
+        snapshot_path: &str,
+    ) -> ArchitecturalRules<ProjectDefined> {
+        let rule = PublicApiMustMatchSnapshot {
+            snapshot_path: snapshot_path.to_string(),
+        };
+
+        self.with_project_rule(Box::new(rule))
+    }
+
+    /// Selects the modules under `module` for the project rules that follow, e.g. `must_be_stable`.
+    pub fn modules_under(self, module: &str) -> ArchitecturalRules<ProjectSubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(Subject::from(module)),
+            rules: self.rules,
+        }
+    }
+}
+
+impl ArchitecturalRules<ProjectSubjectDefined> {
+    /// Each selected module must not depend on more modules than depend on it.
+    pub fn must_be_stable(self) -> ArchitecturalRules<ProjectDefined> {
+        self.must_be_stable_with_margin(0)
+    }
+
+    /// Each selected module's fan-out may exceed its fan-in by at most `margin`.
+    pub fn must_be_stable_with_margin(self, margin: usize) -> ArchitecturalRules<ProjectDefined> {
+        let rule = ModulesMustBeStable {
+            root: self.subject().to_string(),
+            margin,
+        };
+
+        self.with_project_rule(Box::new(rule))
+    }
+}
+
+impl ArchitecturalRules<ProjectDefined> {
+    /// Reports violations of the last project rule as warnings, which don't fail the check.
+    pub fn as_warning(mut self) -> Self {
+        if let Some(rule) = self.rules.project_rules.pop() {
+            self.rules
+                .project_rules
+                .push(Box::new(WarningProjectRule(rule)));
+        }
+
+        self
+    }
+
+    /// Forbids modules from depending on modules with a lower maturity tier,
+    /// e.g. `&[("my_crate::stable", 2), ("my_crate::alpha", 0)]`.
+    pub fn and_it_must_respect_maturity_tiers(
+        self,
+        tiers: &[(&str, usize)],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(maturity_tiers_rule(tiers)))
+    }
+
+    /// Forbids dependencies from a tier to a higher one, listing the tiers from the lowest,
+    /// e.g. `&[&["my_crate::domain"], &["my_crate::application"], &["my_crate::cli"]]`.
+    pub fn and_it_must_respect_tier_order(
+        self,
+        tiers: &[&[&str]],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        let rule = MustRespectTierOrder {
+            tiers: tiers
+                .iter()
+                .map(|prefixes| prefixes.iter().map(|&s| s.to_string()).collect())
+                .collect(),
+        };
+
+        self.with_project_rule(Box::new(rule))
+    }
+
+    /// Modules outside `component` may depend on it but not on its `private_submodules`,
+    /// e.g. `("crate::billing", &["internal"])`.
+    pub fn and_it_must_hide_internals_of(
+        self,
+        component: &str,
+        private_submodules: &[&str],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustHideInternals {
+            component: component.to_string(),
+            private_submodules: private_submodules.iter().map(|&s| s.to_string()).collect(),
+        }))
+    }
+
+    /// The `pub` items of the `internal_submodules` of `component` must be re-exported by the
+    /// component root, e.g. `("crate::domain", &["internal"])`.
+    pub fn and_it_must_reexport_through_facade(
+        self,
+        component: &str,
+        internal_submodules: &[&str],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustReexportThroughFacade {
+            component: component.to_string(),
+            internal_submodules: internal_submodules.iter().map(|&s| s.to_string()).collect(),
+        }))
+    }
+
+    /// The files of `crate_name` may depend on no other crate of the project than
+    /// `allowed_crates`, e.g. `("application", &["domain"])` in a workspace.
+    pub fn and_it_crate_may_depend_on_crates(
+        self,
+        crate_name: &str,
+        allowed_crates: &[&str],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(CrateMayDependOnCrates {
+            crate_name: crate_name.to_string(),
+            allowed_crates: allowed_crates.iter().map(|&s| s.to_string()).collect(),
+        }))
+    }
+
+    /// Each crate may use at most `max_external_crates` distinct external crates.
+    pub fn and_it_must_not_exceed_external_crates(
+        self,
+        max_external_crates: usize,
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustNotExceedExternalCrates {
+            max_external_crates,
+        }))
+    }
+
+    /// Components given as `(name, location)` pairs, e.g. `("domain", "crate::domain")`, must
+    /// not be coupled with more components than their limit, e.g.
+    /// `&[("application", CouplingLimit { max_efferent: Some(2), ..Default::default() })]`.
+    pub fn and_it_must_respect_coupling_limits(
+        self,
+        components: &[(&str, &str)],
+        limits: &[(&str, CouplingLimit)],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(coupling_limits_rule(components, limits)))
+    }
+
+    /// Intra-doc links rooted at `crate::` must point to existing modules and items.
+    pub fn and_it_doc_links_must_resolve(self) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(DocLinksMustResolve))
+    }
+
+    /// The modules under `subtree` (e.g. `crate::domain`) must not depend on each other in
+    /// cycles, at full module granularity. Cycles involving other modules are ignored.
+    pub fn and_it_must_form_a_dag(self, subtree: &str) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustFormADag {
+            subtree: subtree.to_string(),
+        }))
+    }
+
+    /// No two files may define the same module, e.g. `foo.rs` and `foo/mod.rs`.
+    pub fn and_it_must_not_have_duplicate_modules(self) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustNotHaveDuplicateModules))
+    }
+
+    /// Library code must not depend on the modules of `src/bin/`, `examples/`, `tests/` or
+    /// `benches/`; scan them with `Project::with_targets`.
+    pub fn and_it_library_must_not_depend_on_binaries(self) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(LibraryMustNotDependOnBinaries))
+    }
+
+    /// Every scanned module must be depended on by another one, except the `roots`
+    /// (e.g. `&["lib", "main", "bin"]`). Test modules are not reported.
+    pub fn and_it_must_not_have_orphan_modules(
+        self,
+        roots: &[&str],
+    ) -> ArchitecturalRules<ProjectDefined> {
+        self.with_project_rule(Box::new(MustNotHaveOrphanModules::new(roots)))
+    }
+
+    /// Public items listed in the snapshot at `snapshot_path` must not be removed or changed.
+    /// The check fails when the snapshot is missing: run with `ARKITECT_UPDATE_SNAPSHOTS=1` to
+    /// write it, or to accept the changes.
+    pub fn and_public_api_must_match_snapshot(
+        self,
+        snapshot_path: &str,
+    ) -> ArchitecturalRules<ProjectDefined> {
+        let rule = PublicApiMustMatchSnapshot {
+            snapshot_path: snapshot_path.to_string(),
+        };
+
+        self.with_project_rule(Box::new(rule))
+    }
+
+    pub fn rules_for_crate(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(Subject::from(crate_name)),
+            rules: self.rules,
+        }
+    }
+
+    pub fn rules_for_module(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(Subject::from(crate_name)),
+            rules: self.rules,
+        }
+    }
+
+    /// Targets every module matching `pattern`, e.g. `my_crate::api_v\d+`. Panics if the regex is invalid.
+    pub fn rules_for_modules_matching(self, pattern: &str) -> ArchitecturalRules<SubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(regex_subject(pattern)),
+            rules: self.rules,
+        }
+    }
+
+    pub fn build(self) -> Rules {
+        self.rules
+    }
+
+    /// Selects the modules under `module` for the project rules that follow, e.g. `must_be_stable`.
+    pub fn modules_under(self, module: &str) -> ArchitecturalRules<ProjectSubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(Subject::from(module)),
+            rules: self.rules,
+        }
+    }
+}
+
+impl ArchitecturalRules<SubjectDefined> {
+    pub fn it_may_depend_on(self, dependencies: &[&str]) -> ArchitecturalRules<RulesDefined> {
+        let rule = self.for_current_subject(MayDependOnRule::new(
+            self.subject().to_string(),
+            self.with_default_externals(dependencies),
+        ));
+
+        self.with_may_depend_on_rule(rule)
+    }
+
+    pub fn it_must_not_depend_on(self, dependencies: &[&str]) -> ArchitecturalRules<RulesDefined> {
+        let rule = Box::new(self.for_current_subject(MustNotDependOnRule::new(
+            self.subject().to_string(),
+            dependencies.iter().map(|&s| s.to_string()).collect(),
+        )));
+
+        let mut rules = self.rules.module_rules;
+        rules.push(rule);
+
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: Some(RuleList::Module),
+            current_subject: self.current_subject,
+            rules: Rules {
+                module_rules: rules,
+                ..self.rules
+            },
+        }
+    }
+
+    /// Dependency inversion: the subject must not depend on the `concretes`, e.g.
+    /// `&["crate::infrastructure"]`, but on the traits of the `ports`, e.g.
+    /// `&["crate::domain::ports"]`.
+    pub fn it_must_depend_on_abstractions(
+        self,
+        ports: &[&str],
+        concretes: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustDependOnAbstractionsRule {
+            subject: self.subject(),
+            ports: ports.iter().map(|&s| s.to_string()).collect(),
+            concretes: concretes.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    pub fn it_must_not_depend_on_anything(self) -> ArchitecturalRules<RulesDefined> {
+        self.it_must_not_depend_on_anything_except(&[])
+    }
+
+    /// Like `it_must_not_depend_on_anything`, allowing the given external paths,
+    /// e.g. `&["std::fmt"]`.
+    pub fn it_must_not_depend_on_anything_except(
+        self,
+        external_dependencies: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = self.for_current_subject(MustNotDependOnAnythingRule::new(
+            self.subject().to_string(),
+            self.with_default_externals(external_dependencies),
+        ));
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// Like `it_must_not_depend_on_anything`, allowing `std`, `core` and `alloc`.
+    pub fn it_must_not_depend_on_anything_but_std(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = self
+            .for_current_subject(MustNotDependOnAnythingRule::new(
+                self.subject().to_string(),
+                self.with_default_externals(&[]),
+            ))
+            .allow_std();
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// Dependencies on `target` must go through one of `segments`,
+    /// e.g. `("my_crate::domain", &["api", "ports"])` allows `my_crate::domain::api::X` only.
+    pub fn it_may_depend_on_only_via(
+        self,
+        target: &str,
+        segments: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = MayDependOnOnlyViaRule {
+            subject: self.subject(),
+            target: target.to_string(),
+            segments: segments.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject's public functions must not take or return the given types,
+    /// e.g. `&["Vec", "std::collections::HashMap"]`.
+    pub fn it_public_signatures_must_not_use(
+        self,
+        types: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = PublicSignaturesMustNotUseRule {
+            subject: self.subject(),
+            forbidden_types: types.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject's public functions must not take or return types owned by the given
+    /// components, e.g. `&["crate::infrastructure"]`, even if the subject may depend on them.
+    pub fn it_public_signatures_must_not_expose(
+        self,
+        components: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = PublicSignaturesMustNotExposeRule {
+            subject: self.subject(),
+            forbidden_components: components.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject's public functions may take or return types of the given external crates only,
+    /// e.g. `&["uuid"]`, so consumers aren't forced to depend on the others.
+    pub fn it_public_signatures_may_expose_external(
+        self,
+        crates: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = PublicSignaturesMayExposeExternalRule {
+            subject: self.subject(),
+            allowed_external: crates.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// Every name imported with `use` from the crate itself must be referenced in the file.
+    pub fn it_must_not_have_unused_imports(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustNotHaveUnusedImportsRule {
+            subject: self.subject(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject must not re-export (`pub use`) anything from `modules`.
+    pub fn it_must_not_reexport_from(self, modules: &[&str]) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustNotReexportFromRule {
+            subject: self.subject(),
+            forbidden_modules: modules.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject must not re-export with wildcards (`pub use crate::internal::*`).
+    pub fn it_must_not_reexport_globs(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustNotReexportGlobsRule {
+            subject: self.subject(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// Warns about dependencies on deprecated modules, suggesting their replacement if any,
+    /// e.g. `&[("my_crate::legacy_db", Some("my_crate::storage"))]`.
+    pub fn it_must_not_use_deprecated(
+        self,
+        modules: &[(&str, Option<&str>)],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = deprecated_modules_rule(self.subject(), modules);
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject's modules must not be nested deeper than `max_depth` path segments, the crate
+    /// included: `my_crate::domain::orders` has depth 3.
+    pub fn it_must_not_exceed_module_depth(
+        self,
+        max_depth: usize,
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustNotExceedModuleDepthRule {
+            subject: self.subject(),
+            max_depth,
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// Each of the subject's files may declare at most `max_public_items` `pub` functions,
+    /// structs, enums, traits and constants.
+    pub fn it_must_not_exceed_public_items(
+        self,
+        max_public_items: usize,
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustNotExceedPublicItemsRule {
+            subject: self.subject(),
+            max_public_items,
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject's files must be located under `directory` (e.g. `src/infrastructure`), from the
+    /// root of their crate.
+    pub fn it_must_reside_in(self, directory: &str) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustResideInRule::with_directory(self.subject(), directory.to_string());
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The public functions of the subject must all return the same `Result` error type.
+    pub fn it_must_use_single_error_type(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustUseSingleErrorType {
+            subject: self.subject(),
+        };
+
+        self.with_project_rule(Box::new(rule))
+    }
+
+    /// The subject's `pub` types named `*Error` must implement `std::error::Error`, anywhere in
+    /// the project.
+    pub fn it_error_types_must_implement_error(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = ErrorTypesMustImplementError {
+            subject: self.subject(),
+        };
+
+        self.with_project_rule(Box::new(rule))
+    }
+
+    /// The subject's test code, under `tests/` or `#[cfg(test)]`, may only depend on
+    /// `dependencies` and the subject, e.g. `&["crate::testing"]`. Production code is unaffected.
+    pub fn it_test_code_may_depend_on(
+        self,
+        dependencies: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = TestCodeMayDependOnRule {
+            subject: self.subject(),
+            allowed_dependencies: dependencies.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// `#[test]` functions must live under `tests/` or inside a `#[cfg(test)]` module.
+    pub fn it_tests_must_be_isolated(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = TestsMustBeIsolatedRule::new(self.subject());
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    pub fn it(
+        self,
+        rule: Box<dyn SubjectInjectableRuleBuilder>,
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = rule.for_subject_matching(&self.subject());
+
+        let mut rules = self.rules.module_rules;
+        rules.push(rule);
+
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: Some(RuleList::Module),
+            current_subject: self.current_subject,
+            rules: Rules {
+                module_rules: rules,
+                ..self.rules
+            },
+        }
+    }
+}
+
+impl ArchitecturalRules<RulesDefined> {
+    /// Reports violations of the last rule as warnings, which don't fail the check.
+    pub fn as_warning(mut self) -> Self {
+        match self.last_rule {
+            Some(RuleList::Module) => {
+                if let Some(rule) = self.rules.module_rules.pop() {
+                    self.rules.module_rules.push(Box::new(WarningRule(rule)));
+                }
+            }
+            Some(RuleList::Project) => {
+                if let Some(rule) = self.rules.project_rules.pop() {
+                    self.rules
+                        .project_rules
+                        .push(Box::new(WarningProjectRule(rule)));
+                }
+            }
+            None => {}
+        }
+
+        self
+    }
+
+    /// Denies `dependencies` within the allowances of the preceding `may_depend_on`, e.g.
+    /// `.it_may_depend_on(&["my_crate::shared"]).except(&["my_crate::shared::legacy"])`: denials
+    /// win over allowances. Panics if the last rule isn't a `may_depend_on`.
+    pub fn except(mut self, dependencies: &[&str]) -> Self {
+        let last = match self.last_rule {
+            Some(RuleList::Module) => self.rules.module_rules.len().checked_sub(1),
+            _ => None,
+        };
+        let entry = self
+            .may_depend_on_rules
+            .iter_mut()
+            .find(|entry| Some(entry.index) == last)
+            .unwrap_or_else(|| panic!("except must follow it_may_depend_on"));
+
+        entry
+            .denied_dependencies
+            .extend(dependencies.iter().map(|&s| s.to_string()));
+        let (index, rule) = (entry.index, entry.to_rule());
+        self.replace_module_rule(index, rule);
+
+        self
+    }
+
+    /// Adds another `MayDependOnRule` for the subject, checked independently of the previous ones:
+    /// a dependency must be allowed by each of them. Use `and_may_depend_on` to extend the
+    /// allowances of the previous one instead.
+    pub fn and_it_may_depend_on(self, dependencies: &[&str]) -> ArchitecturalRules<RulesDefined> {
+        let rule = self.for_current_subject(MayDependOnRule::new(
+            self.subject().to_string(),
+            self.with_default_externals(dependencies),
+        ));
+
+        self.with_may_depend_on_rule(rule)
+    }
+
+    /// Adds `dependencies` to the allowances of the subject's last `may_depend_on`, e.g.
+    /// `.it_may_depend_on(&["my_crate::domain"]).and_may_depend_on(&["my_crate::shared"])`.
+    /// Panics if the subject has no `may_depend_on`.
+    pub fn and_may_depend_on(self, dependencies: &[&str]) -> Self {
+        self.extend_may_depend_on(dependencies, "and_may_depend_on")
+    }
+
+    /// Adds the external paths `dependencies`, e.g. `&["serde"]`, to the allowances of the
+    /// subject's last `may_depend_on`. Panics if the subject has no `may_depend_on`.
+    pub fn and_allow_external_dependencies(self, dependencies: &[&str]) -> Self {
+        self.extend_may_depend_on(dependencies, "and_allow_external_dependencies")
+    }
+
+    fn extend_may_depend_on(mut self, dependencies: &[&str], method: &str) -> Self {
+        let subject = self.subject().to_string();
+        let entry = self
+            .may_depend_on_rules
+            .iter_mut()
+            .rev()
+            .find(|entry| entry.rule.subject().to_string() == subject)
+            .unwrap_or_else(|| panic!("{} must follow it_may_depend_on", method));
+
+        let dependencies: Vec<String> = dependencies.iter().map(|&s| s.to_string()).collect();
+        entry.rule.rule_mut().allow(&dependencies);
+        let (index, rule) = (entry.index, entry.to_rule());
+        self.replace_module_rule(index, rule);
+
+        self
+    }
+
+    pub fn and_must_not_depend_on(self, dependencies: &[&str]) -> ArchitecturalRules<RulesDefined> {
+        let rule = Box::new(self.for_current_subject(MustNotDependOnRule::new(
+            self.subject().to_string(),
+            dependencies.iter().map(|&s| s.to_string()).collect(),
+        )));
+
+        let mut rules = self.rules.module_rules;
+        rules.push(rule);
+
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: Some(RuleList::Module),
+            current_subject: self.current_subject,
+            rules: Rules {
+                module_rules: rules,
+                ..self.rules
+            },
+        }
+    }
+
+    /// Dependency inversion: the subject must not depend on the `concretes`, e.g.
+    /// `&["crate::infrastructure"]`, but on the traits of the `ports`, e.g.
+    /// `&["crate::domain::ports"]`.
+    pub fn and_it_must_depend_on_abstractions(
+        self,
+        ports: &[&str],
+        concretes: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustDependOnAbstractionsRule {
+            subject: self.subject(),
+            ports: ports.iter().map(|&s| s.to_string()).collect(),
+            concretes: concretes.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    pub fn and_it_must_not_depend_on_anything(self) -> ArchitecturalRules<RulesDefined> {
+        self.and_it_must_not_depend_on_anything_except(&[])
+    }
+
+    /// Like `and_it_must_not_depend_on_anything`, allowing the given external paths,
+    /// e.g. `&["std::fmt"]`.
+    pub fn and_it_must_not_depend_on_anything_except(
+        self,
+        external_dependencies: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = self.for_current_subject(MustNotDependOnAnythingRule::new(
+            self.subject().to_string(),
+            self.with_default_externals(external_dependencies),
+        ));
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// Like `and_it_must_not_depend_on_anything`, allowing `std`, `core` and `alloc`.
+    pub fn and_it_must_not_depend_on_anything_but_std(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = self
+            .for_current_subject(MustNotDependOnAnythingRule::new(
+                self.subject().to_string(),
+                self.with_default_externals(&[]),
+            ))
+            .allow_std();
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// Dependencies on `target` must go through one of `segments`,
+    /// e.g. `("my_crate::domain", &["api", "ports"])` allows `my_crate::domain::api::X` only.
+    pub fn and_it_may_depend_on_only_via(
+        self,
+        target: &str,
+        segments: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = MayDependOnOnlyViaRule {
+            subject: self.subject(),
+            target: target.to_string(),
+            segments: segments.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject's public functions must not take or return the given types,
+    /// e.g. `&["Vec", "std::collections::HashMap"]`.
+    pub fn and_it_public_signatures_must_not_use(
+        self,
+        types: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = PublicSignaturesMustNotUseRule {
+            subject: self.subject(),
+            forbidden_types: types.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject's public functions must not take or return types owned by the given
+    /// components, e.g. `&["crate::infrastructure"]`, even if the subject may depend on them.
+    pub fn and_it_public_signatures_must_not_expose(
+        self,
+        components: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = PublicSignaturesMustNotExposeRule {
+            subject: self.subject(),
+            forbidden_components: components.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject's public functions may take or return types of the given external crates only,
+    /// e.g. `&["uuid"]`, so consumers aren't forced to depend on the others.
+    pub fn and_it_public_signatures_may_expose_external(
+        self,
+        crates: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = PublicSignaturesMayExposeExternalRule {
+            subject: self.subject(),
+            allowed_external: crates.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// Every name imported with `use` from the crate itself must be referenced in the file.
+    pub fn and_it_must_not_have_unused_imports(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustNotHaveUnusedImportsRule {
+            subject: self.subject(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject must not re-export (`pub use`) anything from `modules`.
+    pub fn and_it_must_not_reexport_from(
+        self,
+        modules: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustNotReexportFromRule {
+            subject: self.subject(),
+            forbidden_modules: modules.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject must not re-export with wildcards (`pub use crate::internal::*`).
+    pub fn and_it_must_not_reexport_globs(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustNotReexportGlobsRule {
+            subject: self.subject(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// Warns about dependencies on deprecated modules, suggesting their replacement if any,
+    /// e.g. `&[("my_crate::legacy_db", Some("my_crate::storage"))]`.
+    pub fn and_it_must_not_use_deprecated(
+        self,
+        modules: &[(&str, Option<&str>)],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = deprecated_modules_rule(self.subject(), modules);
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject's modules must not be nested deeper than `max_depth` path segments, the crate
+    /// included: `my_crate::domain::orders` has depth 3.
+    pub fn and_it_must_not_exceed_module_depth(
+        self,
+        max_depth: usize,
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustNotExceedModuleDepthRule {
+            subject: self.subject(),
+            max_depth,
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// Each of the subject's files may declare at most `max_public_items` `pub` functions,
+    /// structs, enums, traits and constants.
+    pub fn and_it_must_not_exceed_public_items(
+        self,
+        max_public_items: usize,
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustNotExceedPublicItemsRule {
+            subject: self.subject(),
+            max_public_items,
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The subject's files must be located under `directory` (e.g. `src/infrastructure`), from the
+    /// root of their crate.
+    pub fn and_it_must_reside_in(self, directory: &str) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustResideInRule::with_directory(self.subject(), directory.to_string());
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// The public functions of the subject must all return the same `Result` error type.
+    pub fn and_it_must_use_single_error_type(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = MustUseSingleErrorType {
+            subject: self.subject(),
+        };
+
+        self.with_project_rule(Box::new(rule))
+    }
+
+    /// The subject's `pub` types named `*Error` must implement `std::error::Error`, anywhere in
+    /// the project.
+    pub fn and_it_error_types_must_implement_error(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = ErrorTypesMustImplementError {
+            subject: self.subject(),
+        };
+
+        self.with_project_rule(Box::new(rule))
+    }
+
+    /// The subject's test code, under `tests/` or `#[cfg(test)]`, may only depend on
+    /// `dependencies` and the subject, e.g. `&["crate::testing"]`. Production code is unaffected.
+    pub fn and_it_test_code_may_depend_on(
+        self,
+        dependencies: &[&str],
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = TestCodeMayDependOnRule {
+            subject: self.subject(),
+            allowed_dependencies: dependencies.iter().map(|&s| s.to_string()).collect(),
+        };
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    /// `#[test]` functions must live under `tests/` or inside a `#[cfg(test)]` module.
+    pub fn and_it_tests_must_be_isolated(self) -> ArchitecturalRules<RulesDefined> {
+        let rule = TestsMustBeIsolatedRule::new(self.subject());
+
+        self.with_module_rule(Box::new(rule))
+    }
+
+    pub fn and_it(
+        self,
+        rule: Box<dyn SubjectInjectableRuleBuilder>,
+    ) -> ArchitecturalRules<RulesDefined> {
+        let rule = rule.for_subject_matching(&self.subject());
+
+        let mut rules = self.rules.module_rules;
+        rules.push(rule);
+
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: Some(RuleList::Module),
+            current_subject: self.current_subject,
+            rules: Rules {
+                module_rules: rules,
+                ..self.rules
+            },
+        }
+    }
+
+    pub fn rules_for_crate(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(Subject::from(crate_name)),
+            rules: self.rules,
+        }
+    }
+
+    pub fn rules_for_module(self, crate_name: &str) -> ArchitecturalRules<SubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(Subject::from(crate_name)),
+            rules: self.rules,
+        }
+    }
+
+    /// Targets every module matching `pattern`, e.g. `my_crate::api_v\d+`. Panics if the regex is invalid.
+    pub fn rules_for_modules_matching(self, pattern: &str) -> ArchitecturalRules<SubjectDefined> {
+        ArchitecturalRules {
+            state: PhantomData,
+            default_external_dependencies: self.default_external_dependencies,
+            may_depend_on_rules: self.may_depend_on_rules,
+            last_rule: self.last_rule,
+            current_subject: Some(regex_subject(pattern)),
+            rules: self.rules,
+        }
+    }
+
+    /// Extends every `it_may_depend_on` defined so far with what its allowed components may
+    /// depend on, transitively: if `application` may depend on `domain` and `domain` on
+    /// `errors`, `application` may depend on `errors` too. Call it right before `build`.
+    ///
+    /// Only subjects given as module paths are followed: a regex subject gains the allowances of
+    /// the modules it may depend on, but nothing is inherited from it.
+    pub fn with_transitive_allowances(mut self) -> Self {
+        let mut graph: HashMap<String, Vec<String>> = HashMap::new();
+        for entry in &self.may_depend_on_rules {
+            if let Subject::Prefix(subject) = entry.rule.subject() {
+                graph
+                    .entry(subject.clone())
+                    .or_default()
+                    .extend(entry.rule.rule().allowed_dependencies.iter().cloned());
+            }
+        }
+
+        let mut extended = vec![];
+        for entry in &mut self.may_depend_on_rules {
+            let transitive = transitive_allowances(&graph, &entry.rule);
+            if !transitive.is_empty() {
+                entry.rule.rule_mut().allow(&transitive);
+                extended.push((entry.index, entry.to_rule()));
+            }
+        }
+        for (index, rule) in extended {
+            self.replace_module_rule(index, rule);
+        }
+
+        self
+    }
+
+    pub fn build(self) -> Rules {
+        self.rules
+    }
+}
+
+/// The dependencies reachable from the allowances of `rule` over the `graph` of subjects, other
+/// than the subject itself and the dependencies it already allows.
+fn transitive_allowances(
+    graph: &HashMap<String, Vec<String>>,
+    rule: &Configured<MayDependOnRule>,
+) -> Vec<String> {
+    let subject = rule.subject().to_string();
+    let allowed_dependencies = &rule.rule().allowed_dependencies;
+    let mut allowed: Vec<&String> = allowed_dependencies.iter().collect();
+    let mut next = 0;
+    while next < allowed.len() {
+        for dependency in graph.get(allowed[next]).into_iter().flatten() {
+            if *dependency != subject && !allowed.contains(&dependency) {
+                allowed.push(dependency);
+            }
+        }
+        next += 1;
+    }
+
+    allowed[allowed_dependencies.len()..]
+        .iter()
+        .map(|&dependency| dependency.clone())
+        .collect()
+}
+
+fn regex_subject(pattern: &str) -> Subject {
+    Subject::regex(pattern).unwrap_or_else(|e| panic!("Invalid module regex '{}': {}", pattern, e))
+}
+
+fn coupling_limits_rule(
+    components: &[(&str, &str)],
+    limits: &[(&str, CouplingLimit)],
+) -> MustRespectCouplingLimits {
+    MustRespectCouplingLimits {
+        components: components
+            .iter()
+            .map(|(name, location)| (name.to_string(), location.to_string()))
+            .collect(),
+        limits: limits
+            .iter()
+            .map(|(name, limit)| (name.to_string(), *limit))
+            .collect(),
+    }
+}
+
+fn deprecated_modules_rule(
+    subject: Subject,
+    modules: &[(&str, Option<&str>)],
+) -> MustNotUseDeprecatedRule {
+    MustNotUseDeprecatedRule {
+        subject,
+        deprecated_modules: modules
+            .iter()
+            .map(|(module, _)| module.to_string())
+            .collect(),
+        replacements: modules
+            .iter()
+            .filter_map(|(module, replacement)| {
+                Some((module.to_string(), (*replacement)?.to_string()))
+            })
+            .collect(),
+    }
+}
+
+fn maturity_tiers_rule(tiers: &[(&str, usize)]) -> MustRespectMaturityTiers {
+    MustRespectMaturityTiers {
+        tiers: tiers
+            .iter()
+            .map(|(prefix, tier)| (prefix.to_string(), *tier))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::RuleDescription;
+    use crate::rust_file::RustFile;
 
     #[test]
     fn test_define_rules_for_crate() {
@@ -329,6 +1861,392 @@ mod tests {
         assert_eq!(rules.len(), 2);
     }
 
+    #[test]
+    fn test_with_custom_rules_after_must_not_depend_on_anything() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("domain")
+                .it_must_not_depend_on_anything()
+                .and_it(MustNotContainAttribute::new("#[a]"))
+            .build();
+
+        assert_eq!(rules.len(), 2);
+    }
+
+    #[test]
+    fn test_forbid_external_crates() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_crate::api")
+                .it(ForbidExternalCrates::except(&["serde", "tokio"]))
+            .build();
+
+        let description = &rules.describe()[0];
+        assert_eq!(description.kind, "forbid_external_crates");
+        assert_eq!(description.subject.as_deref(), Some("my_crate::api"));
+        assert_eq!(description.allowed, vec!["serde", "tokio"]);
+    }
+
+    #[test]
+    fn test_must_depend_on() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_crate::handlers")
+                .it(MustDependOn::new("crate::telemetry"))
+            .build();
+
+        let description = &rules.describe()[0];
+        assert_eq!(description.kind, "must_depend_on");
+        assert_eq!(description.subject.as_deref(), Some("my_crate::handlers"));
+        assert_eq!(description.allowed, vec!["crate::telemetry"]);
+    }
+
+    #[test]
+    fn test_transitive_allowances() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_app::application")
+                .it_may_depend_on(&["my_app::domain", "std::fmt"])
+                .as_warning()
+            .rules_for_module("my_app::domain")
+                .it_may_depend_on(&["my_app::errors"])
+            .rules_for_module("my_app::errors")
+                .it_may_depend_on(&["thiserror"])
+                .and_must_not_depend_on(&["my_app::application"])
+            .with_transitive_allowances()
+            .build();
+
+        let allowed: Vec<Vec<String>> = rules.describe().into_iter().map(|d| d.allowed).collect();
+        assert_eq!(
+            allowed,
+            vec![
+                vec!["my_app::domain", "std::fmt", "my_app::errors", "thiserror"],
+                vec!["my_app::errors", "thiserror"],
+                vec!["thiserror"],
+                vec![],
+            ]
+        );
+        assert_eq!(rules.module_rules[0].severity(), Severity::Warning);
+
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_app::application",
+            "use crate::errors::AppError;",
+        );
+        assert!(rules.module_rules[0].apply(&file).is_ok());
+    }
+
+    #[test]
+    fn test_transitive_allowances_skip_regex_subjects() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_app::application")
+                .it_may_depend_on(&[r"my_app::api_v\d+"])
+            .rules_for_modules_matching(r"my_app::api_v\d+")
+                .it_may_depend_on(&["my_app::domain"])
+            .rules_for_module("my_app::cli")
+                .it_may_depend_on(&["my_app::application"])
+            .with_transitive_allowances()
+            .build();
+
+        let allowed: Vec<Vec<String>> = rules.describe().into_iter().map(|d| d.allowed).collect();
+        assert_eq!(
+            allowed,
+            vec![
+                vec![r"my_app::api_v\d+"],
+                vec!["my_app::domain"],
+                vec!["my_app::application", r"my_app::api_v\d+"],
+            ]
+        );
+        assert_eq!(
+            rules.describe()[1].subject.as_deref(),
+            Some(r"my_app::api_v\d+")
+        );
+        let versioned = RustFile::from_content("src/api_v2.rs", "my_app::api_v2", "");
+        assert!(rules.module_rules[1].is_applicable(&versioned));
+    }
+
+    #[test]
+    fn test_allowances_are_strict_by_default() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_app::application")
+                .it_may_depend_on(&["my_app::domain"])
+            .rules_for_module("my_app::domain")
+                .it_may_depend_on(&["my_app::errors"])
+            .build();
+
+        assert_eq!(rules.describe()[0].allowed, vec!["my_app::domain"]);
+    }
+
+    #[test]
+    fn test_must_not_depend_on_anything_except() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_app::domain")
+                .it_must_not_depend_on_anything_except(&["std::fmt"])
+            .rules_for_module("my_app::errors")
+                .it_may_depend_on(&["thiserror"])
+                .and_it_must_not_depend_on_anything_except(&["thiserror"])
+            .build();
+
+        assert_eq!(rules.describe()[0].allowed, vec!["std::fmt"]);
+        assert_eq!(rules.describe()[2].allowed, vec!["thiserror"]);
+
+        let allowed =
+            RustFile::from_content("src/domain.rs", "my_app::domain", "use std::fmt::Display;");
+        assert!(rules.module_rules[0].apply(&allowed).is_ok());
+
+        let forbidden = RustFile::from_content(
+            "src/domain.rs",
+            "my_app::domain",
+            "use std::fmt::Display; use std::collections::HashMap;",
+        );
+        assert!(rules.module_rules[0].apply(&forbidden).is_err());
+    }
+
+    #[test]
+    fn test_must_not_depend_on_anything_but_std() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_app::domain")
+                .it_must_not_depend_on_anything_but_std()
+            .rules_for_module("my_app::errors")
+                .it_may_depend_on(&["thiserror"])
+                .and_it_must_not_depend_on_anything_but_std()
+            .build();
+
+        let file =
+            |content: &str| RustFile::from_content("src/domain.rs", "my_app::domain", content);
+        assert!(rules.module_rules[0]
+            .apply(&file(
+                "use std::fmt::Display; use core::mem; use alloc::vec::Vec;"
+            ))
+            .is_ok());
+        assert!(rules.module_rules[0]
+            .apply(&file("use serde::Serialize;"))
+            .is_err());
+        assert_eq!(rules.len(), 3);
+    }
+
+    #[test]
+    fn test_may_depend_on_except() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_app::application")
+                .it_may_depend_on(&["my_app::shared"])
+                .except(&["my_app::shared::legacy"])
+            .rules_for_module("my_app::infrastructure")
+                .it_may_depend_on(&["my_app::shared"])
+                .as_warning()
+                .except(&["my_app::shared::legacy"])
+                .except(&["my_app::shared::internal"])
+            .build();
+
+        assert_eq!(rules.describe()[0].kind, "may_depend_on_except");
+        assert_eq!(rules.describe()[0].allowed, vec!["my_app::shared"]);
+        assert_eq!(
+            rules.describe()[1].forbidden,
+            vec!["my_app::shared::legacy", "my_app::shared::internal"]
+        );
+        assert_eq!(rules.module_rules[1].severity(), Severity::Warning);
+
+        let file = |content: &str| {
+            RustFile::from_content("src/application.rs", "my_app::application", content)
+        };
+        assert!(rules.module_rules[0]
+            .apply(&file("use crate::shared::money::Money;"))
+            .is_ok());
+        assert!(rules.module_rules[0]
+            .apply(&file("use crate::shared::legacy::Gateway;"))
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "except must follow it_may_depend_on")]
+    fn test_except_without_may_depend_on() {
+        ArchitecturalRules::define()
+            .rules_for_module("my_app::application")
+            .it_must_not_depend_on(&["my_app::infrastructure"])
+            .except(&["my_app::infrastructure::ports"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "except must follow it_may_depend_on")]
+    fn test_except_after_project_rule() {
+        ArchitecturalRules::define()
+            .rules_for_module("my_app::application")
+            .it_may_depend_on(&["my_app::shared"])
+            .and_it_must_use_single_error_type()
+            .except(&["my_app::shared::legacy"]);
+    }
+
+    #[test]
+    fn test_as_warning_after_project_rule() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_app::application")
+                .it_may_depend_on(&["my_app::shared"])
+                .and_it_must_use_single_error_type()
+                .as_warning()
+            .rules_for_module("my_app::domain")
+                .it_error_types_must_implement_error()
+                .as_warning()
+                .and_must_not_depend_on(&["my_app::infrastructure"])
+            .build();
+
+        assert_eq!(rules.module_rules[0].severity(), Severity::Error);
+        assert_eq!(rules.module_rules[1].severity(), Severity::Error);
+        assert_eq!(rules.project_rules[0].severity(), Severity::Warning);
+        assert_eq!(rules.project_rules[1].severity(), Severity::Warning);
+    }
+
+    #[test]
+    fn test_and_may_depend_on_extends_the_allowances() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_app::application")
+                .it_may_depend_on(&["my_app::domain"])
+                .except(&["my_app::domain::legacy"])
+                .and_must_not_depend_on(&["my_app::infrastructure"])
+                .and_may_depend_on(&["my_app::shared"])
+                .and_allow_external_dependencies(&["serde"])
+            .build();
+
+        assert_eq!(rules.module_rules.len(), 2);
+        assert_eq!(
+            rules.describe()[0].allowed,
+            vec!["my_app::domain", "my_app::shared", "serde"]
+        );
+
+        let file = |content: &str| {
+            RustFile::from_content("src/application.rs", "my_app::application", content)
+        };
+        assert!(rules.module_rules[0]
+            .apply(&file(
+                "use my_app::domain::User; use my_app::shared::Money; use serde::Serialize;"
+            ))
+            .is_ok());
+        assert!(rules.module_rules[0]
+            .apply(&file("use my_app::domain::legacy::Gateway;"))
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "and_may_depend_on must follow it_may_depend_on")]
+    fn test_and_may_depend_on_without_may_depend_on() {
+        ArchitecturalRules::define()
+            .rules_for_module("my_app::application")
+            .it_must_not_depend_on(&["my_app::infrastructure"])
+            .and_may_depend_on(&["my_app::shared"]);
+    }
+
+    #[test]
+    fn test_default_external_dependencies() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .default_external_dependencies(&["std"])
+            .rules_for_module("my_app::domain")
+                .it_must_not_depend_on_anything()
+            .rules_for_module("my_app::application")
+                .it_may_depend_on(&["my_app::domain", "serde"])
+            .build();
+
+        assert_eq!(rules.describe()[0].allowed, vec!["std"]);
+        assert_eq!(
+            rules.describe()[1].allowed,
+            vec!["my_app::domain", "serde", "std"]
+        );
+
+        let domain =
+            RustFile::from_content("src/domain.rs", "my_app::domain", "use std::fmt::Display;");
+        let application = RustFile::from_content(
+            "src/application.rs",
+            "my_app::application",
+            "use std::fmt::Display; use serde::Serialize; use my_app::domain::Order;",
+        );
+        assert!(rules.module_rules[0].apply(&domain).is_ok());
+        assert!(rules.module_rules[1].apply(&application).is_ok());
+    }
+
+    #[test]
+    fn test_must_not_use_deprecated() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_crate("my_crate")
+                .it_must_not_use_deprecated(&[
+                    ("my_crate::legacy_db", Some("my_crate::storage")),
+                    ("my_crate::old_auth", None),
+                ])
+            .build();
+
+        let description = &rules.describe()[0];
+        assert_eq!(description.kind, "must_not_use_deprecated");
+        assert_eq!(
+            description.forbidden,
+            vec!["my_crate::legacy_db", "my_crate::old_auth"]
+        );
+        assert_eq!(rules.module_rules[0].severity(), Severity::Warning);
+    }
+
+    #[test]
+    fn test_must_not_exceed_module_depth() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_crate::domain")
+                .it_must_not_exceed_module_depth(4)
+                .and_it_must_not_exceed_public_items(10)
+            .rules_for_module("my_crate::infrastructure")
+                .it_may_depend_on(&["my_crate::domain"])
+                .and_it_must_not_exceed_module_depth(3)
+            .build();
+
+        let kinds: Vec<String> = rules.describe().into_iter().map(|d| d.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "must_not_exceed_module_depth",
+                "must_not_exceed_public_items",
+                "may_depend_on",
+                "must_not_exceed_module_depth"
+            ]
+        );
+        assert_eq!(
+            rules.module_rules[0].to_string(),
+            MustNotExceedModuleDepthRule {
+                subject: "my_crate::domain".into(),
+                max_depth: 4
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_must_not_contain_attribute() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_crate::domain")
+                .it(MustNotContainAttribute::new("#[derive(Serialize)]"))
+            .build();
+
+        let description = &rules.describe()[0];
+        assert_eq!(description.kind, "must_not_contain_attribute");
+        assert_eq!(description.forbidden, vec!["derive(Serialize)"]);
+
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            "#[derive(Clone, Serialize)]\npub struct Order;\n",
+        );
+        assert!(rules.module_rules[0].apply(&file).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid attribute")]
+    fn test_must_not_contain_invalid_attribute() {
+        MustNotContainAttribute::new("#[derive(]");
+    }
+
     #[test]
     fn test_complex_rule_set() {
         #[rustfmt::skip]
@@ -344,49 +2262,364 @@ mod tests {
         assert_eq!(rules.len(), 3);
     }
 
-    #[allow(dead_code)]
-    struct MustNotContainAttributeRule {
-        subject: String,
-        attribute: String,
+    #[test]
+    fn test_define_location_rules() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_crate::infrastructure")
+                .it_must_reside_in("src/infrastructure")
+                .and_it_may_depend_on(&["my_crate::domain"])
+            .rules_for_module("my_crate::domain")
+                .it_must_not_depend_on_anything()
+                .and_it_must_reside_in("src/domain")
+                .and_it_tests_must_be_isolated()
+                .and_it_test_code_may_depend_on(&["crate::testing"])
+            .rules_for_crate("my_crate")
+                .it_tests_must_be_isolated()
+            .rules_for_module("my_crate::tests")
+                .it_test_code_may_depend_on(&["my_crate::domain"])
+            .rules_for_module("my_crate::api")
+                .it_must_use_single_error_type()
+            .rules_for_module("my_crate::cli")
+                .it_tests_must_be_isolated()
+                .and_it_must_use_single_error_type()
+                .and_it_error_types_must_implement_error()
+            .build();
+
+        assert_eq!(rules.len(), 12);
+        assert_eq!(rules.project_rules.len(), 3);
     }
 
-    struct MustNotContainAttribute {
-        attribute: String,
+    #[test]
+    fn test_define_reexport_rules() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_crate::api")
+                .it_must_not_reexport_from(&["my_crate::infrastructure"])
+            .rules_for_module("my_crate::application")
+                .it_may_depend_on(&["my_crate::domain"])
+                .and_it_must_not_reexport_from(&["my_crate::domain"])
+                .and_it_must_not_have_unused_imports()
+                .and_it_may_depend_on_only_via("my_crate::domain", &["api"])
+            .rules_for_crate("my_crate")
+                .it_must_not_have_unused_imports()
+            .rules_for_module("my_crate::cli")
+                .it_may_depend_on_only_via("my_crate::application", &["api", "ports"])
+            .rules_for_module("my_crate::api")
+                .it_public_signatures_must_not_use(&["Vec", "std::collections::HashMap"])
+                .and_it_public_signatures_must_not_use(&["String"])
+                .and_it_public_signatures_may_expose_external(&["uuid"])
+            .rules_for_module("my_crate::model")
+                .it_public_signatures_may_expose_external(&[])
+                .and_it_public_signatures_must_not_expose(&["my_crate::infrastructure"])
+            .rules_for_module("my_crate::application")
+                .it_public_signatures_must_not_expose(&["crate::infrastructure"])
+                .and_it_must_not_reexport_globs()
+            .rules_for_crate("my_crate")
+                .it_must_not_reexport_globs()
+            .build();
+
+        assert_eq!(rules.len(), 15);
     }
 
-    impl MustNotContainAttribute {
-        fn new(attribute: &str) -> Box<dyn SubjectInjectableRuleBuilder> {
-            Box::new(MustNotContainAttribute {
-                attribute: attribute.to_string(),
-            })
-        }
+    #[test]
+    fn test_define_project_rules() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_project()
+                .it_must_not_have_circular_dependencies(3)
+                .and_it_must_respect_maturity_tiers(&[("my_crate::stable", 2), ("my_crate::alpha", 0)])
+                .modules_under("my_crate::domain").must_be_stable()
+                .modules_under("my_crate::stable").must_be_stable_with_margin(2)
+                .and_public_api_must_match_snapshot("api.snap")
+                .and_it_must_not_have_orphan_modules(&["lib", "main"])
+                .and_it_doc_links_must_resolve()
+                .and_it_must_respect_tier_order(&[&["my_crate::domain"], &["my_crate::cli"]])
+                .and_it_must_not_exceed_external_crates(10)
+                .and_it_must_hide_internals_of("my_crate::billing", &["internal"])
+                .and_it_must_reexport_through_facade("my_crate::domain", &["internal"])
+                .and_it_must_not_have_duplicate_modules()
+                .and_it_library_must_not_depend_on_binaries()
+                .and_it_must_form_a_dag("crate::domain")
+                .and_it_must_respect_coupling_limits(
+                    &[("domain", "crate::domain"), ("application", "crate::application")],
+                    &[("application", CouplingLimit { max_efferent: Some(2), ..Default::default() })],
+                )
+                .and_it_crate_may_depend_on_crates("application", &["domain"])
+            .rules_for_module("my_crate::alpha")
+                .it_must_not_depend_on_anything()
+            .build();
+
+        assert_eq!(rules.len(), 17);
+        assert_eq!(rules.project_rules.len(), 16);
+
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_project()
+                .public_api_must_match_snapshot("api.snap")
+            .build();
+
+        assert_eq!(rules.project_rules.len(), 1);
+
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_project()
+                .it_must_not_have_orphan_modules(&["lib"])
+            .build();
+
+        assert_eq!(rules.project_rules.len(), 1);
+
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_project()
+                .it_doc_links_must_resolve()
+            .build();
+
+        assert_eq!(rules.project_rules.len(), 1);
+
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_project()
+                .it_must_respect_tier_order(&[&["my_crate::domain"], &["my_crate::cli"]])
+            .build();
+
+        assert_eq!(rules.project_rules.len(), 1);
     }
 
-    impl Display for MustNotContainAttributeRule {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            write!(f, "Example Rule")
-        }
+    #[test]
+    fn test_describe_rules() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_project()
+                .it_must_not_have_circular_dependencies(3)
+            .rules_for_module("my_crate::application")
+                .it_may_depend_on(&["my_crate::domain"])
+                .and_it_must_not_reexport_from(&["my_crate::infrastructure"])
+            .build();
+
+        assert_eq!(
+            rules.describe(),
+            vec![
+                RuleDescription {
+                    id: "module:0".to_string(),
+                    kind: "may_depend_on".to_string(),
+                    subject: Some("my_crate::application".to_string()),
+                    allowed: vec!["my_crate::domain".to_string()],
+                    forbidden: vec![],
+                },
+                RuleDescription {
+                    id: "module:1".to_string(),
+                    kind: "must_not_reexport_from".to_string(),
+                    subject: Some("my_crate::application".to_string()),
+                    allowed: vec![],
+                    forbidden: vec!["my_crate::infrastructure".to_string()],
+                },
+                RuleDescription {
+                    id: "project:0".to_string(),
+                    kind: "must_not_have_circular_dependencies".to_string(),
+                    subject: None,
+                    allowed: vec![],
+                    forbidden: vec![],
+                },
+            ]
+        );
     }
 
-    impl Rule for MustNotContainAttributeRule {
-        fn apply(&self, _file: &RustFile) -> Result<(), String> {
-            Ok(())
-        }
+    #[test]
+    fn test_define_layers() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .layers(&["my_crate::domain", "my_crate::application", "my_crate::infrastructure"])
+            .rules_for_module("my_crate::domain")
+                .it_must_reside_in("src/domain")
+            .build();
 
-        fn is_applicable(&self, _file: &RustFile) -> bool {
-            true
-        }
+        assert_eq!(rules.len(), 4);
+    }
+
+    #[test]
+    fn test_builtin_rules_have_distinct_names() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_project()
+                .it_must_not_have_circular_dependencies(3)
+                .and_it_must_respect_maturity_tiers(&[("my_crate::stable", 1)])
+                .modules_under("my_crate::domain").must_be_stable()
+                .and_public_api_must_match_snapshot("api.snap")
+                .and_it_must_not_have_orphan_modules(&["lib"])
+                .and_it_doc_links_must_resolve()
+                .and_it_must_respect_tier_order(&[&["my_crate::domain"]])
+                .and_it_must_not_exceed_external_crates(10)
+                .and_it_must_hide_internals_of("my_crate::billing", &["internal"])
+            .rules_for_module("my_crate::api")
+                .it_may_depend_on(&["my_crate::domain"])
+                .and_must_not_depend_on(&["my_crate::infrastructure"])
+                .and_it_must_not_depend_on_anything()
+                .and_it_may_depend_on_only_via("my_crate::domain", &["ports"])
+                .and_it_must_reside_in("src/api")
+                .and_it_tests_must_be_isolated()
+                .and_it_must_not_reexport_from(&["my_crate::infrastructure"])
+                .and_it_must_not_have_unused_imports()
+                .and_it_public_signatures_must_not_use(&["Vec"])
+                .and_it_public_signatures_may_expose_external(&["uuid"])
+                .and_it(ForbidExternalCrates::except(&["serde"]))
+                .and_it(MustDependOn::new("my_crate::telemetry"))
+                .and_it(MustNotAccessFieldsOf::components(&["my_crate::billing"]))
+                .and_it(MustHaveModuleDoc::non_empty())
+                .and_it_must_use_single_error_type()
+            .build();
+
+        let names: Vec<&str> = rules
+            .module_rules
+            .iter()
+            .map(|rule| rule.name())
+            .chain(rules.project_rules.iter().map(|rule| rule.name()))
+            .collect();
+        let distinct: std::collections::HashSet<&str> = names.iter().copied().collect();
+
+        assert!(names
+            .iter()
+            .all(|name| !name.is_empty() && *name != "custom"));
+        assert_eq!(distinct.len(), names.len());
+    }
+
+    #[test]
+    fn test_rules_for_modules_matching() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_modules_matching(r"my_crate::api_v\d+")
+                .it_must_not_depend_on(&["my_crate::infrastructure"])
+            .build();
+
+        let versioned = RustFile::from_content(
+            "src/api_v2/handlers.rs",
+            "my_crate::api_v2::handlers",
+            "use crate::infrastructure::db::Connection;",
+        );
+        let unversioned = RustFile::from_content(
+            "src/api/handlers.rs",
+            "my_crate::api::handlers",
+            "use crate::infrastructure::db::Connection;",
+        );
+
+        let rule = &rules.module_rules[0];
+        assert!(rule.is_applicable(&versioned));
+        assert!(rule.apply(&versioned).is_err());
+        assert!(!rule.is_applicable(&unversioned));
+    }
+
+    #[test]
+    fn test_injected_rules_keep_regex_subjects() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_modules_matching(r"my_crate::api_v\d+")
+                .it(MustNotUseAsync::new())
+            .build();
+
+        let versioned = RustFile::from_content(
+            "src/api_v2/handlers.rs",
+            "my_crate::api_v2::handlers",
+            "pub async fn handle() {}",
+        );
+
+        assert!(rules.module_rules[0].is_applicable(&versioned));
+        assert!(rules.module_rules[0].apply(&versioned).is_err());
     }
 
-    impl SubjectInjectableRuleBuilder for MustNotContainAttribute {
+    /// A builder written against the string-based `for_subject` only.
+    struct MustNotUseAsyncIn;
+
+    impl SubjectInjectableRuleBuilder for MustNotUseAsyncIn {
         fn for_subject(&self, subject: &str) -> Box<dyn Rule> {
-            Box::new(MustNotContainAttributeRule {
-                subject: subject.to_string(),
-                attribute: self.attribute.clone(),
+            Box::new(MustNotUseAsyncRule {
+                subject: Subject::from(subject),
             })
         }
     }
 
+    #[test]
+    fn test_string_based_builders_get_module_subjects() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_crate::domain")
+                .it(Box::new(MustNotUseAsyncIn))
+            .build();
+
+        assert_eq!(
+            rules.module_rules[0].to_string(),
+            "my_crate::domain must not use async code"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't support regex subject")]
+    fn test_string_based_builders_reject_regex_subjects() {
+        ArchitecturalRules::define()
+            .rules_for_modules_matching(r"my_crate::api_v\d+")
+            .it(Box::new(MustNotUseAsyncIn));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid module regex")]
+    fn test_rules_for_modules_matching_invalid_regex() {
+        ArchitecturalRules::define().rules_for_modules_matching("my_crate::(");
+    }
+
+    #[test]
+    fn test_must_not_use_debug_macros() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_crate::domain")
+                .it(MustNotUseDebugMacros::dbg_only())
+                .and_it(MustNotUseDebugMacros::named(&["println", "eprintln"]))
+            .build();
+
+        let file =
+            RustFile::from_content("src/domain.rs", "my_crate::domain", "fn f() { dbg!(1); }");
+        assert_eq!(rules.describe()[0].forbidden, vec!["dbg"]);
+        assert!(rules.module_rules[0].apply(&file).is_err());
+        assert!(rules.module_rules[1].apply(&file).is_ok());
+    }
+
+    #[test]
+    fn test_must_depend_on_abstractions() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_crate::application")
+                .it_must_depend_on_abstractions(&["crate::domain::ports"], &["crate::infrastructure"])
+            .rules_for_module("my_crate::cli")
+                .it_may_depend_on(&["my_crate::application"])
+                .and_it_must_depend_on_abstractions(&["crate::domain::ports"], &["crate::infrastructure"])
+            .build();
+
+        let file = RustFile::from_content(
+            "src/application.rs",
+            "my_crate::application",
+            "use crate::infrastructure::Db;",
+        );
+        assert_eq!(rules.describe()[0].allowed, vec!["crate::domain::ports"]);
+        assert_eq!(rules.describe()[0].forbidden, vec!["crate::infrastructure"]);
+        assert!(rules.module_rules[0].apply(&file).is_err());
+        assert_eq!(rules.len(), 3);
+    }
+
+    #[test]
+    fn test_must_not_use_async() {
+        #[rustfmt::skip]
+        let rules = ArchitecturalRules::define()
+            .rules_for_module("my_crate::domain")
+                .it(MustNotUseAsync::new())
+            .build();
+
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            "pub async fn load() {}",
+        );
+        assert_eq!(rules.describe()[0].kind, "must_not_use_async");
+        assert!(rules.module_rules[0].apply(&file).is_err());
+    }
+
     #[test]
     fn test_subject_injection() {
         #[rustfmt::skip]
@@ -1,10 +1,17 @@
 use crate::dsl::project::Project;
-use crate::engine::Engine;
-use crate::rule::{ProjectRule, Rule};
+use crate::engine::{unmatched_subjects_violation, Engine};
+use crate::error::ArkitectError;
+use crate::report::{ExplainReport, ParseDiagnostic, Report, RuleCheck, Stats};
+use crate::rule::{ProjectRule, Rule, RuleDescription, Severity};
+use crate::styling::{ColorMode, Styling, Theme};
+use log::{error, info, warn};
+use std::path::Path;
 
 pub struct Rules {
     pub module_rules: Vec<Box<dyn Rule>>,
     pub project_rules: Vec<Box<dyn ProjectRule>>,
+    /// Overrides the `Arkitect` baseline for this rule set, see `with_baseline`.
+    pub(crate) baseline: Option<usize>,
 }
 
 impl Rules {
@@ -15,6 +22,7 @@ impl Rules {
         Rules {
             module_rules,
             project_rules,
+            baseline: None,
         }
     }
 
@@ -24,17 +32,120 @@ impl Rules {
         Rules {
             module_rules,
             project_rules,
+            baseline: None,
+        }
+    }
+
+    /// Tolerates up to `baseline` violations of this rule set, whatever the `Arkitect` baseline.
+    pub fn with_baseline(self, baseline: usize) -> Self {
+        Self {
+            baseline: Some(baseline),
+            ..self
+        }
+    }
+
+    pub fn baseline(&self) -> Option<usize> {
+        self.baseline
+    }
+
+    /// Combines two rule sets, e.g. one per bounded context, into one checked in a single pass.
+    /// Baselines add up when both sets have one, otherwise the one set is kept.
+    pub fn merge(mut self, other: Rules) -> Self {
+        self.module_rules.extend(other.module_rules);
+        self.project_rules.extend(other.project_rules);
+
+        Self {
+            baseline: match (self.baseline, other.baseline) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            },
+            ..self
         }
     }
 
     pub fn len(&self) -> usize {
         self.module_rules.len() + self.project_rules.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Describes every rule, module rules first, with ids like `module:0` and `project:0`.
+    pub fn describe(&self) -> Vec<RuleDescription> {
+        let module_rules = self
+            .module_rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| RuleDescription {
+                id: format!("module:{}", i),
+                ..rule.describe()
+            });
+        let project_rules =
+            self.project_rules
+                .iter()
+                .enumerate()
+                .map(|(i, rule)| RuleDescription {
+                    id: format!("project:{}", i),
+                    ..rule.describe()
+                });
+
+        module_rules.chain(project_rules).collect()
+    }
 }
 
+/// The violations found by `Arkitect::complies_with_details`, measured against the baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComplianceDetails {
+    pub all_violations: Vec<String>,
+    /// The files that couldn't be parsed and were not analyzed.
+    pub diagnostics: Vec<ParseDiagnostic>,
+    pub baseline: usize,
+    /// Whether there are more violations than the baseline tolerates.
+    pub exceeds_baseline: bool,
+    /// How many files and rules were checked, and how long it took.
+    pub stats: Stats,
+}
+
+/// The outcome of `Arkitect::run`, for checks running outside of the test harness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOutcome {
+    pub violations: Vec<String>,
+    pub warnings: Vec<String>,
+    pub diagnostics: Vec<ParseDiagnostic>,
+    /// Why the project couldn't be scanned, in which case the check fails.
+    pub error: Option<ArkitectError>,
+    /// Whether the project was scanned and the violations are within the baseline.
+    pub passed: bool,
+}
+
+impl RunOutcome {
+    /// `0` when the check passed, `1` otherwise, e.g. for `std::process::exit`.
+    pub fn exit_code(&self) -> i32 {
+        if self.passed {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+/// Called with the number of files scanned so far and the total.
+type ProgressCallback = dyn Fn(usize, usize);
+type ViolationCallback = dyn Fn(&RuleCheck);
+
 pub struct Arkitect {
     project: Project,
     baseline: usize,
+    only_files: Option<Vec<String>>,
+    all_matching_rules: bool,
+    require_matching_subjects: bool,
+    on_progress: Option<Box<ProgressCallback>>,
+    on_violation: Option<Box<ViolationCallback>>,
+    color_mode: ColorMode,
+    theme: Theme,
+    /// Set by `ensure_that_file`: `only_files` holds the file and project rules are rejected.
+    single_file: bool,
 }
 
 impl Arkitect {
@@ -46,18 +157,313 @@ impl Arkitect {
         Self { baseline, ..self }
     }
 
-    pub fn complies_with(&mut self, rules: Rules) -> Result<Vec<String>, Vec<String>> {
-        let violations = Engine::new(
+    /// Only checks `paths` (relative to the project root), e.g. the files changed in a pull request.
+    ///
+    /// Module rules are applied to those files alone. Project rules, like
+    /// `must_not_have_circular_dependencies`, need the whole dependency graph and are skipped:
+    /// run a full check to enforce them.
+    pub fn only_files(self, paths: &[&str]) -> Self {
+        Self {
+            only_files: Some(paths.iter().map(|path| path.to_string()).collect()),
+            ..self
+        }
+    }
+
+    /// Applies every rule whose subject matches a file. By default, when allow-lists of the same
+    /// kind target nested subjects, e.g. `may_depend_on` for both `my_crate::domain` and
+    /// `my_crate::domain::models`, only the most specific one applies to the files it covers.
+    /// Prohibitions and rules with a regex subject always apply.
+    pub fn all_matching_rules_apply(self) -> Self {
+        Self {
+            all_matching_rules: true,
+            ..self
+        }
+    }
+
+    /// Fails the check when the subject of a module rule matches none of the scanned files, e.g.
+    /// `rules_for_module("my_crate::convrsion")`, which would otherwise pass without checking
+    /// anything. Ignored when checking `only_files`, as a subject may match none of them.
+    pub fn subjects_must_match_files(self) -> Self {
+        Self {
+            require_matching_subjects: true,
+            ..self
+        }
+    }
+
+    /// Calls `on_progress(scanned, total)` each time the module rules have been applied to a file,
+    /// e.g. to render a progress bar.
+    pub fn on_progress(self, on_progress: impl Fn(usize, usize) + 'static) -> Self {
+        Self {
+            on_progress: Some(Box::new(on_progress)),
+            ..self
+        }
+    }
+
+    /// Colors rules and violations always, never, or only on a terminal without `NO_COLOR`
+    /// (`ColorMode::Auto`, the default).
+    pub fn with_color_mode(self, color_mode: ColorMode) -> Self {
+        Self { color_mode, ..self }
+    }
+
+    /// Replaces the colors of subjects, forbidden dependencies and paths.
+    pub fn with_theme(self, theme: Theme) -> Self {
+        Self { theme, ..self }
+    }
+
+    /// Calls `on_violation` with each violated check as soon as it is found, before the
+    /// analysis completes.
+    pub fn on_violation(self, on_violation: impl Fn(&RuleCheck) + 'static) -> Self {
+        Self {
+            on_violation: Some(Box::new(on_violation)),
+            ..self
+        }
+    }
+
+    /// Runs the analysis, returning the violations along with the files that couldn't be parsed.
+    pub fn analyze(&mut self, rules: Rules) -> Report {
+        self.try_analyze(rules).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `analyze`, returning an error when the project can't be scanned.
+    pub fn try_analyze(&mut self, rules: Rules) -> Result<Report, ArkitectError> {
+        if self.single_file {
+            self.check_single_file(&rules)?;
+        }
+
+        let engine = Engine::new(
+            self.project.project_root.as_str(),
+            &rules.module_rules,
+            &rules.project_rules,
+        )
+        .with_ignored_directories(&self.project.ignored_directories)
+        .with_source_dirs(&self.project.source_dirs)
+        .with_targets(self.project.include_targets)
+        .with_members(self.project.members.clone(), &self.project.excluded_members)
+        .with_features(self.project.features.clone())
+        .with_cache_dir(self.project.cache_dir.as_deref())
+        .with_all_matching_rules(self.all_matching_rules)
+        .with_matching_subjects_required(self.require_matching_subjects)
+        .with_progress(self.on_progress.as_deref())
+        .with_violation_callback(self.on_violation.as_deref())
+        .with_styling(self.styling());
+
+        match &self.only_files {
+            Some(files) => engine.with_only_files(files),
+            None => engine,
+        }
+        .try_compute_violations()
+    }
+
+    fn styling(&self) -> Styling {
+        Styling {
+            mode: self.color_mode,
+            theme: self.theme,
+        }
+    }
+
+    /// Fails when the file given to `ensure_that_file` is missing or project rules are given.
+    fn check_single_file(&self, rules: &Rules) -> Result<(), ArkitectError> {
+        let path = self
+            .only_files
+            .as_ref()
+            .and_then(|files| files.first())
+            .cloned()
+            .unwrap_or_default();
+
+        if !Path::new(&path).is_file() {
+            return Err(ArkitectError::PathNotFound {
+                path,
+                reason: "not a file".to_string(),
+            });
+        }
+
+        if !rules.project_rules.is_empty() {
+            return Err(ArkitectError::ProjectRulesOnSingleFile {
+                path,
+                rules: rules
+                    .project_rules
+                    .iter()
+                    .map(|rule| rule.name().to_string())
+                    .collect(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Lists, per module rule, the files it applies to and the ones it doesn't, without
+    /// applying any rule. Useful when a rule unexpectedly doesn't fire.
+    pub fn explain(&mut self, rules: Rules) -> ExplainReport {
+        let report = Engine::new(
             self.project.project_root.as_str(),
             &rules.module_rules,
             &rules.project_rules,
         )
+        .with_ignored_directories(&self.project.ignored_directories)
+        .with_source_dirs(&self.project.source_dirs)
+        .with_targets(self.project.include_targets)
+        .with_members(self.project.members.clone(), &self.project.excluded_members)
+        .with_features(self.project.features.clone())
+        .with_all_matching_rules(self.all_matching_rules)
+        .with_styling(self.styling())
+        .with_dry_run()
         .compute_violations();
 
-        if violations.len() <= self.baseline {
-            Ok(violations)
+        ExplainReport {
+            rules: report.traces,
+            scanned_files: report.scanned_files,
+            diagnostics: report.diagnostics,
+        }
+    }
+
+    /// Checks several rule sets walking and parsing the project once, returning one result per set.
+    /// Each set is compared against its own baseline, or the `Arkitect` one when it has none.
+    pub fn complies_with_all(
+        &mut self,
+        rule_sets: Vec<Rules>,
+    ) -> Vec<Result<Vec<String>, Vec<String>>> {
+        let mut baselines = Vec::new();
+        let mut subjects: Vec<Vec<String>> = Vec::new();
+        let mut module_rule_sets = Vec::new();
+        let mut project_rule_sets = Vec::new();
+        let mut all_rules = Rules::from(vec![], vec![]);
+
+        for (set, rules) in rule_sets.into_iter().enumerate() {
+            baselines.push(self.baseline_of(&rules));
+            subjects.push(
+                rules
+                    .module_rules
+                    .iter()
+                    .filter_map(|rule| rule.describe().subject)
+                    .collect(),
+            );
+            module_rule_sets.extend(rules.module_rules.iter().map(|_| set));
+            project_rule_sets.extend(rules.project_rules.iter().map(|_| set));
+            all_rules.module_rules.extend(rules.module_rules);
+            all_rules.project_rules.extend(rules.project_rules);
+        }
+
+        let report = self.analyze(all_rules);
+
+        if !report.diagnostics.is_empty() {
+            warn!(
+                "{} files couldn't be parsed and were not analyzed",
+                report.diagnostics.len()
+            );
+        }
+
+        let mut violations: Vec<Vec<String>> = vec![vec![]; baselines.len()];
+        for check in report.checks {
+            if check.severity != Severity::Error {
+                continue;
+            }
+            let Some(violation) = check.violation else {
+                continue;
+            };
+            if check.index == RuleCheck::ENGINE_CHECK {
+                // The subjects matching no file concern the sets declaring them
+                for (set, set_subjects) in subjects.iter().enumerate() {
+                    let unmatched: Vec<&str> = report
+                        .unmatched_subjects
+                        .iter()
+                        .filter(|subject| set_subjects.contains(subject))
+                        .map(String::as_str)
+                        .collect();
+                    if !unmatched.is_empty() {
+                        violations[set].push(unmatched_subjects_violation(&unmatched));
+                    }
+                }
+                continue;
+            }
+            let set = match check.path {
+                Some(_) => module_rule_sets[check.index],
+                None => project_rule_sets[check.index],
+            };
+            violations[set].push(violation);
+        }
+
+        violations
+            .into_iter()
+            .zip(baselines)
+            .map(|(violations, baseline)| {
+                if violations.len() <= baseline {
+                    Ok(violations)
+                } else {
+                    Err(violations)
+                }
+            })
+            .collect()
+    }
+
+    /// The baseline of the rule set, or the `Arkitect` one when it has none.
+    fn baseline_of(&self, rules: &Rules) -> usize {
+        rules.baseline.unwrap_or(self.baseline)
+    }
+
+    pub fn complies_with(&mut self, rules: Rules) -> Result<Vec<String>, Vec<String>> {
+        let details = self.complies_with_details(rules);
+
+        if details.exceeds_baseline {
+            Err(details.all_violations)
         } else {
-            Err(violations)
+            Ok(details.all_violations)
+        }
+    }
+
+    /// Runs the check without panicking, e.g. from the `main` of a binary or a build script:
+    /// `std::process::exit(arkitect.run(rules).exit_code())`.
+    pub fn run(&mut self, rules: Rules) -> RunOutcome {
+        let baseline = self.baseline_of(&rules);
+        let report = match self.try_analyze(rules) {
+            Ok(report) => report,
+            Err(e) => {
+                error!("{}", e);
+                return RunOutcome {
+                    violations: vec![],
+                    warnings: vec![],
+                    diagnostics: vec![],
+                    error: Some(e),
+                    passed: false,
+                };
+            }
+        };
+
+        if !report.violations.is_empty() || !report.warnings.is_empty() {
+            info!("{}", report.summary());
+        }
+
+        RunOutcome {
+            passed: report.violations.len() <= baseline,
+            violations: report.violations,
+            warnings: report.warnings,
+            diagnostics: report.diagnostics,
+            error: None,
+        }
+    }
+
+    /// Like `complies_with`, returning the violations even when they are within the baseline,
+    /// e.g. to track the remaining debt without failing.
+    pub fn complies_with_details(&mut self, rules: Rules) -> ComplianceDetails {
+        let baseline = self.baseline_of(&rules);
+        let report = self.analyze(rules);
+
+        if !report.diagnostics.is_empty() {
+            warn!(
+                "{} files couldn't be parsed and were not analyzed",
+                report.diagnostics.len()
+            );
+        }
+
+        if !report.violations.is_empty() || !report.warnings.is_empty() {
+            info!("{}", report.summary());
+        }
+
+        ComplianceDetails {
+            exceeds_baseline: report.violations.len() > baseline,
+            all_violations: report.violations,
+            diagnostics: report.diagnostics,
+            baseline,
+            stats: report.stats,
         }
     }
 }
@@ -65,8 +471,90 @@ impl Arkitect {
 impl Arkitect {
     pub fn ensure_that(project: Project) -> Arkitect {
         Arkitect {
+            baseline: project.baseline.unwrap_or(0),
+            only_files: None,
+            all_matching_rules: false,
+            require_matching_subjects: false,
+            on_progress: None,
+            on_violation: None,
+            color_mode: ColorMode::default(),
+            theme: Theme::default(),
+            single_file: false,
             project,
-            baseline: 0,
         }
     }
+
+    /// Checks the module rules against the file at `path` alone, e.g. `src/domain/order.rs`,
+    /// without walking the project it belongs to. Project rules need the whole project: the
+    /// analysis fails when any is given.
+    pub fn ensure_that_file(path: &str) -> Arkitect {
+        let project = Project {
+            project_root: String::new(),
+            ignored_directories: vec![],
+            source_dirs: vec![],
+            baseline: None,
+            include_targets: false,
+            members: None,
+            excluded_members: vec![],
+            packages: vec![],
+            features: None,
+            cache_dir: None,
+        };
+
+        Arkitect {
+            only_files: Some(vec![path.to_string()]),
+            single_file: true,
+            ..Self::ensure_that(project)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtin_rules::may_depend_on::MayDependOnRule;
+    use crate::builtin_rules::must_not_depend_on_anything::MustNotDependOnAnythingRule;
+    use crate::builtin_rules::must_not_have_circular_dependencies::MustNotHaveCircularDependencies;
+    use crate::rust_file::FILES_READ;
+
+    fn sample_rule_sets() -> Vec<Rules> {
+        let conversion = Rules::from_module_rules(vec![Box::new(MayDependOnRule::new(
+            "sample_project::conversion",
+            vec!["sample_project::conversion".to_string()],
+        ))]);
+        let policy_management = Rules::from(
+            vec![Box::new(MustNotDependOnAnythingRule::new(
+                "sample_project::policy_management",
+                vec![],
+            ))],
+            vec![Box::new(MustNotHaveCircularDependencies { max_depth: 10 })],
+        );
+
+        vec![conversion, policy_management]
+    }
+
+    #[test]
+    fn test_complies_with_all_traverses_the_project_once() {
+        let mut arkitect = Arkitect::ensure_that(Project::from_path("examples/sample_project"));
+
+        FILES_READ.with(|count| count.set(0));
+        let separate: Vec<_> = sample_rule_sets()
+            .into_iter()
+            .map(|rules| arkitect.complies_with(rules))
+            .collect();
+        let separate_reads = FILES_READ.with(|count| count.get());
+
+        FILES_READ.with(|count| count.set(0));
+        let mut rule_sets = sample_rule_sets().into_iter();
+        let conversion = rule_sets.next().unwrap().with_baseline(1);
+        let results = arkitect.complies_with_all(vec![conversion, rule_sets.next().unwrap()]);
+        let combined_reads = FILES_READ.with(|count| count.get());
+
+        assert_eq!(combined_reads * 2, separate_reads);
+
+        // The conversion application depends on `contracts`: a violation, within its baseline of 1
+        assert_eq!(separate[0].as_ref().unwrap_err().len(), 1);
+        assert_eq!(results[0].as_ref().unwrap().len(), 1);
+        assert_eq!(results[1], separate[1]);
+    }
 }
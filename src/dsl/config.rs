@@ -0,0 +1,217 @@
+use crate::builtin_rules::may_depend_on::MayDependOnRule;
+use crate::builtin_rules::must_not_depend_on::MustNotDependOnRule;
+use crate::builtin_rules::must_not_depend_on_anything::MustNotDependOnAnythingRule;
+use crate::builtin_rules::must_not_have_circular_dependencies::MustNotHaveCircularDependencies;
+use crate::dsl::architectural_rules::{ArchitecturalRules, Begin};
+use crate::dsl::arkitect::Rules;
+use crate::rule::{ProjectRule, Rule};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// The content of an `arkitect.toml`:
+///
+/// ```toml
+/// [components]
+/// domain = "my_crate::domain"
+/// application = "my_crate::application"
+///
+/// [[rules]]
+/// component = "application"
+/// may_depend_on = ["domain"]
+/// allow_external = ["std::fmt"]
+///
+/// [[rules]]
+/// component = "domain"
+/// must_not_depend_on_anything = true
+///
+/// [project]
+/// max_cycle_depth = 3
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    /// Component names mapped to their location, a crate or a module path.
+    #[serde(default)]
+    components: BTreeMap<String, String>,
+    #[serde(default)]
+    rules: Vec<ComponentRules>,
+    #[serde(default)]
+    project: ProjectConfig,
+}
+
+/// The rules of a component, relations are given as component names.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ComponentRules {
+    component: String,
+    may_depend_on: Option<Vec<String>>,
+    #[serde(default)]
+    must_not_depend_on: Vec<String>,
+    #[serde(default)]
+    must_not_depend_on_anything: bool,
+    /// Paths outside of the components the component may depend on, e.g. `std::fmt`.
+    #[serde(default)]
+    allow_external: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProjectConfig {
+    max_cycle_depth: Option<usize>,
+}
+
+impl Config {
+    fn location_of(&self, component: &str) -> Result<String, String> {
+        self.components
+            .get(component)
+            .cloned()
+            .ok_or_else(|| format!("Unknown component [{}] in arkitect config", component))
+    }
+
+    fn locations_of(&self, components: &[String]) -> Result<Vec<String>, String> {
+        components.iter().map(|c| self.location_of(c)).collect()
+    }
+
+    fn into_rules(self) -> Result<Rules, String> {
+        let mut module_rules: Vec<Box<dyn Rule>> = vec![];
+
+        for rules in &self.rules {
+            let subject = self.location_of(&rules.component)?;
+
+            if let Some(may_depend_on) = &rules.may_depend_on {
+                let mut allowed_dependencies = self.locations_of(may_depend_on)?;
+                allowed_dependencies.extend(rules.allow_external.iter().cloned());
+                module_rules.push(Box::new(MayDependOnRule::new(
+                    subject.clone(),
+                    allowed_dependencies,
+                )));
+            }
+
+            if !rules.must_not_depend_on.is_empty() {
+                module_rules.push(Box::new(MustNotDependOnRule::new(
+                    subject.clone(),
+                    self.locations_of(&rules.must_not_depend_on)?,
+                )));
+            }
+
+            if rules.must_not_depend_on_anything {
+                module_rules.push(Box::new(MustNotDependOnAnythingRule::new(
+                    subject,
+                    rules.allow_external.clone(),
+                )));
+            }
+        }
+
+        let mut project_rules: Vec<Box<dyn ProjectRule>> = vec![];
+        if let Some(max_depth) = self.project.max_cycle_depth {
+            project_rules.push(Box::new(MustNotHaveCircularDependencies { max_depth }));
+        }
+
+        Ok(Rules::from(module_rules, project_rules))
+    }
+}
+
+impl ArchitecturalRules<Begin> {
+    /// Loads the rules defined in a TOML file such as `arkitect.toml`, building the same rules as
+    /// the DSL. Fails if the file can't be read or parsed, or references an unknown component.
+    pub fn from_config(path: impl AsRef<Path>) -> Result<Rules, String> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+        Self::from_config_str(&content)
+    }
+
+    /// Like `from_config`, with the TOML given as a string.
+    pub fn from_config_str(content: &str) -> Result<Rules, String> {
+        toml::from_str::<Config>(content)
+            .map_err(|e| format!("Invalid arkitect config: {}", e))?
+            .into_rules()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::temp_project;
+
+    const CONFIG: &str = r#"
+        [components]
+        domain = "my_crate::domain"
+        application = "my_crate::application"
+        infrastructure = "my_crate::infrastructure"
+
+        [[rules]]
+        component = "application"
+        may_depend_on = ["domain"]
+        allow_external = ["std::fmt"]
+
+        [[rules]]
+        component = "domain"
+        must_not_depend_on_anything = true
+
+        [[rules]]
+        component = "infrastructure"
+        may_depend_on = ["domain", "application"]
+        must_not_depend_on = ["application"]
+
+        [project]
+        max_cycle_depth = 3
+    "#;
+
+    #[test]
+    fn test_from_config() {
+        let root = temp_project(&[("arkitect.toml", CONFIG)]);
+        let path = root.path().join("arkitect.toml");
+
+        let rules = ArchitecturalRules::from_config(&path).unwrap();
+
+        assert_eq!(rules.len(), 5);
+        let kinds: Vec<String> = rules.describe().into_iter().map(|d| d.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "may_depend_on",
+                "must_not_depend_on_anything",
+                "may_depend_on",
+                "must_not_depend_on",
+                "must_not_have_circular_dependencies",
+            ]
+        );
+
+        let description = &rules.describe()[0];
+        assert_eq!(
+            description.subject.as_deref(),
+            Some("my_crate::application")
+        );
+        assert_eq!(description.allowed, vec!["my_crate::domain", "std::fmt"]);
+    }
+
+    #[test]
+    fn test_unknown_components_are_rejected() {
+        let result = ArchitecturalRules::from_config_str(
+            r#"
+            [components]
+            domain = "my_crate::domain"
+
+            [[rules]]
+            component = "domain"
+            must_not_depend_on = ["infrastructure"]
+            "#,
+        );
+
+        assert_eq!(
+            result.err(),
+            Some("Unknown component [infrastructure] in arkitect config".to_string())
+        );
+    }
+
+    #[test]
+    fn test_missing_config_file() {
+        let result = ArchitecturalRules::from_config("/nonexistent/arkitect.toml");
+
+        assert!(result.err().unwrap().starts_with("Failed to read"));
+    }
+}
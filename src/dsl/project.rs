@@ -1,98 +1,302 @@
-use std::path::Path;
+use crate::error::ArkitectError;
+use crate::rust_project::{
+    git_changed_files, ArkitectOptions, CargoMetadata, CargoPackage, RustProject,
+    DEFAULT_IGNORED_DIRECTORIES,
+};
+use std::path::{Path, PathBuf};
 use std::{env, fs};
 
 pub struct Project {
     pub project_root: String,
+    /// Directory names skipped at any depth while scanning, `target` and `.git` by default.
+    pub ignored_directories: Vec<String>,
+    /// Directories scanned besides each crate's source directory, relative to `project_root`.
+    pub source_dirs: Vec<String>,
+    /// Baseline used by `Arkitect` unless `with_baseline` is called.
+    pub baseline: Option<usize>,
+    /// Whether project rules also see the `bin`, `examples`, `tests` and `benches` targets.
+    pub include_targets: bool,
+    /// The workspace members to scan, all of them when `None`.
+    pub members: Option<Vec<String>>,
+    /// Workspace members left out of the scan.
+    pub excluded_members: Vec<String>,
+    /// The members resolved by `cargo metadata`, empty unless built by `from_cargo_metadata`.
+    pub packages: Vec<CargoPackage>,
+    /// The Cargo features considered enabled, all code is analyzed when `None`.
+    pub features: Option<Vec<String>>,
+    /// Where the dependencies extracted from the files are cached between runs, nothing is
+    /// cached when `None`.
+    pub cache_dir: Option<PathBuf>,
 }
 
 impl Project {
+    /// Creates a Project applying the `[workspace/package.metadata.arkitect]` options of its `Cargo.toml`.
+    fn rooted_at(project_root: String) -> Result<Project, ArkitectError> {
+        let options = ArkitectOptions::from_manifest(Path::new(&project_root)).map_err(|e| {
+            ArkitectError::InvalidManifest {
+                path: Path::new(&project_root)
+                    .join("Cargo.toml")
+                    .display()
+                    .to_string(),
+                reason: format!("invalid arkitect metadata: {}", e),
+            }
+        })?;
+
+        Ok(Project {
+            project_root,
+            ignored_directories: options.ignored_directories.unwrap_or_else(|| {
+                DEFAULT_IGNORED_DIRECTORIES
+                    .iter()
+                    .map(|dir| dir.to_string())
+                    .collect()
+            }),
+            source_dirs: options.source_dirs,
+            baseline: options.baseline,
+            include_targets: options.include_targets,
+            members: options.members,
+            excluded_members: options.excluded_members,
+            packages: vec![],
+            features: options.features,
+            cache_dir: None,
+        })
+    }
+
+    /// Also scans the `bin`, `examples`, `tests` and `benches` targets, not only `src`.
+    pub fn with_targets(self) -> Project {
+        Project {
+            include_targets: true,
+            ..self
+        }
+    }
+
+    /// Replaces the default ignored directories (`target`, `.git`) with the given names.
+    pub fn with_ignored_directories(self, directories: &[&str]) -> Project {
+        Project {
+            ignored_directories: directories.iter().map(|dir| dir.to_string()).collect(),
+            ..self
+        }
+    }
+
+    /// Analyzes the code as compiled with only `features` enabled: items behind a `#[cfg]` on
+    /// other features, such as a feature-gated `use`, are left out.
+    pub fn with_features(self, features: &[&str]) -> Project {
+        Project {
+            features: Some(features.iter().map(|feature| feature.to_string()).collect()),
+            ..self
+        }
+    }
+
+    /// Caches the dependencies extracted from the files in `cache_dir`, e.g. `target/arkitect`
+    /// (relative to the project root or absolute), so that the next runs don't parse the files
+    /// left unchanged, unless a rule needs their syntax tree.
+    pub fn with_cache_dir(self, cache_dir: &str) -> Project {
+        Project {
+            cache_dir: Some(Path::new(&self.project_root).join(cache_dir)),
+            ..self
+        }
+    }
+
+    /// Only scans these workspace members, given as path (`crates/billing`) or directory name.
+    pub fn members(self, members: &[&str]) -> Project {
+        Project {
+            members: Some(members.iter().map(|member| member.to_string()).collect()),
+            ..self
+        }
+    }
+
+    /// Leaves these workspace members out of the scan, given as path or directory name.
+    pub fn exclude_members(self, members: &[&str]) -> Project {
+        Project {
+            excluded_members: members.iter().map(|member| member.to_string()).collect(),
+            ..self
+        }
+    }
+
+    /// The `.rs` files changed since `base` (`HEAD` when `None`) according to `git diff`, relative
+    /// to the project root, to check with `Arkitect::only_files`. Files in ignored directories are
+    /// left out, and so are untracked files. Fails when the project isn't in a git repository.
+    pub fn changed_git_files(&self, base: Option<&str>) -> Result<Vec<String>, String> {
+        let files = git_changed_files(Path::new(&self.project_root), base.unwrap_or("HEAD"))?;
+
+        self.scanned_rust_files(files)
+    }
+
+    /// Keeps the `.rs` files among `files`, relative to the project root, that a scan of the
+    /// project would visit: the ones in its source directories, outside ignored directories.
+    fn scanned_rust_files(&self, files: Vec<String>) -> Result<Vec<String>, String> {
+        let options = ArkitectOptions {
+            source_dirs: self.source_dirs.clone(),
+            ignored_directories: Some(self.ignored_directories.clone()),
+            baseline: None,
+            include_targets: self.include_targets,
+            members: self.members.clone(),
+            excluded_members: self.excluded_members.clone(),
+            features: self.features.clone(),
+        };
+        let source_dirs = RustProject::source_dirs(&self.project_root, &options)
+            .map_err(|e| format!("Failed to find the source directories: {}", e))?;
+        let root = Path::new(&self.project_root);
+
+        Ok(files
+            .into_iter()
+            .filter(|file| {
+                let path = Path::new(file);
+                path.extension().is_some_and(|ext| ext == "rs")
+                    && source_dirs
+                        .iter()
+                        .any(|dir| root.join(path).starts_with(dir))
+                    && !path.components().any(|component| {
+                        self.ignored_directories
+                            .iter()
+                            .any(|ignored| component.as_os_str() == ignored.as_str())
+                    })
+            })
+            .collect())
+    }
+
     pub fn from_path(absolute_path: &str) -> Project {
+        Self::try_from_path(absolute_path).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `from_path`, returning an error when the path isn't a crate or a workspace.
+    pub fn try_from_path(absolute_path: &str) -> Result<Project, ArkitectError> {
         let path = Path::new(absolute_path);
-        if !path.exists() {
-            panic!("The provided path '{}' does not exist.", absolute_path);
-        }
+        fs::metadata(path).map_err(|e| ArkitectError::PathNotFound {
+            path: absolute_path.to_string(),
+            reason: e.to_string(),
+        })?;
 
         let cargo_toml_path = path.join("Cargo.toml");
 
         if !cargo_toml_path.exists() {
-            panic!(
-                "The provided path '{}' does not contain a `Cargo.toml` file.",
-                absolute_path
-            );
+            return Err(ArkitectError::NotAProject {
+                path: absolute_path.to_string(),
+                reason: "it does not contain a `Cargo.toml` file".to_string(),
+            });
         }
 
-        if let Ok(contents) = fs::read_to_string(&cargo_toml_path) {
-            if !contents.contains("[package]") && !contents.contains("[workspace]") {
-                panic!(
-                    "`Cargo.toml` at '{}' is invalid: it must contain `[package]` or `[workspace]`.",
-                    cargo_toml_path.display()
-                );
-            }
-        } else {
-            panic!(
-                "Failed to read the `Cargo.toml` file at '{}'.",
-                cargo_toml_path.display()
-            );
+        let invalid_manifest = |reason: String| ArkitectError::InvalidManifest {
+            path: cargo_toml_path.display().to_string(),
+            reason,
+        };
+        let contents =
+            fs::read_to_string(&cargo_toml_path).map_err(|e| invalid_manifest(e.to_string()))?;
+        if !contents.contains("[package]") && !contents.contains("[workspace]") {
+            return Err(invalid_manifest(
+                "it must contain `[package]` or `[workspace]`".to_string(),
+            ));
         }
 
-        Project {
-            project_root: absolute_path.to_string(),
-        }
+        Project::rooted_at(absolute_path.to_string())
+    }
+
+    /// Creates a Project for the current crate's workspace as resolved by `cargo metadata`,
+    /// scanning exactly the members Cargo knows about.
+    pub fn from_cargo_metadata() -> Project {
+        Self::try_from_cargo_metadata().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `from_cargo_metadata`, returning an error when the workspace can't be resolved.
+    pub fn try_from_cargo_metadata() -> Result<Project, ArkitectError> {
+        Project::try_from_cargo_metadata_at(&cargo_manifest_dir()?)
+    }
+
+    /// Like `from_cargo_metadata`, for the workspace containing `manifest_dir`.
+    pub fn from_cargo_metadata_at(manifest_dir: &str) -> Project {
+        Self::try_from_cargo_metadata_at(manifest_dir).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `from_cargo_metadata_at`, returning an error when the workspace can't be resolved.
+    pub fn try_from_cargo_metadata_at(manifest_dir: &str) -> Result<Project, ArkitectError> {
+        let metadata = CargoMetadata::read(Path::new(manifest_dir)).map_err(|reason| {
+            ArkitectError::NotAProject {
+                path: manifest_dir.to_string(),
+                reason,
+            }
+        })?;
+
+        let members = metadata
+            .members
+            .iter()
+            .filter_map(|package| {
+                package
+                    .manifest_dir
+                    .strip_prefix(&metadata.workspace_root)
+                    .ok()
+                    .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+            })
+            .collect();
+
+        Ok(Project {
+            members: Some(members),
+            packages: metadata.members,
+            ..Project::rooted_at(metadata.workspace_root.to_string_lossy().into_owned())?
+        })
     }
 
     /// Creates a Project rooted at the crate's directory.
     pub fn from_current_crate() -> Project {
-        let cargo_manifest_dir =
-            env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+        Self::try_from_current_crate().unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        Project {
-            project_root: cargo_manifest_dir,
-        }
+    /// Like `from_current_crate`, returning an error when the crate's manifest is invalid.
+    pub fn try_from_current_crate() -> Result<Project, ArkitectError> {
+        Project::rooted_at(cargo_manifest_dir()?)
     }
 
     /// Creates a Project rooted at the workspace's root directory.
     /// Panics if the current crate is not part of a workspace.
     pub fn from_current_workspace() -> Project {
-        let cargo_manifest_dir =
-            env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+        Self::try_from_current_workspace().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `from_current_workspace`, returning an error when the current crate is not part of
+    /// a workspace.
+    pub fn try_from_current_workspace() -> Result<Project, ArkitectError> {
+        let cargo_manifest_dir = cargo_manifest_dir()?;
 
         let crate_path = Path::new(&cargo_manifest_dir);
 
         if Self::is_workspace_root(crate_path) {
-            return Project {
-                project_root: cargo_manifest_dir,
-            };
+            return Project::rooted_at(cargo_manifest_dir);
         }
 
         if let Some(parent_path) = crate_path.parent() {
             if Self::is_workspace_root(parent_path) {
-                return Project {
-                    project_root: parent_path.to_string_lossy().into_owned(),
-                };
+                return Project::rooted_at(parent_path.to_string_lossy().into_owned());
             }
         }
 
-        panic!("Current crate is not part of a workspace or workspace root not found.");
+        Err(ArkitectError::NotAProject {
+            path: cargo_manifest_dir,
+            reason: "the current crate is not part of a workspace or workspace root not found"
+                .to_string(),
+        })
     }
 
     /// Method that creates a Project determining whether the current context is a workspace or crate.
     pub fn new() -> Project {
-        let cargo_manifest_dir =
-            env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+        Self::try_new().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `new`, returning an error when `CARGO_MANIFEST_DIR` is missing or the manifest is
+    /// invalid.
+    pub fn try_new() -> Result<Project, ArkitectError> {
+        let cargo_manifest_dir = cargo_manifest_dir()?;
 
         let crate_path = Path::new(&cargo_manifest_dir);
 
         if Self::is_workspace_root(crate_path) {
-            return Self::from_current_workspace();
+            return Self::try_from_current_workspace();
         }
 
         if let Some(parent_path) = crate_path.parent() {
             if Self::is_workspace_root(parent_path) {
-                return Self::from_current_workspace();
+                return Self::try_from_current_workspace();
             }
         }
 
-        Self::from_current_crate()
+        Self::try_from_current_crate()
     }
 
     /// Checks if the given path is a workspace root by inspecting its `Cargo.toml`.
@@ -111,31 +315,204 @@ impl Project {
 
     /// Creates a Project from a path relative to the given file.
     pub fn from_relative_path(current_file: &str, relative_path: &str) -> Project {
-        let current_dir = Path::new(current_file)
-            .parent()
-            .expect("Failed to get parent directory");
+        Self::try_from_relative_path(current_file, relative_path)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `from_relative_path`, returning an error when the path can't be resolved.
+    pub fn try_from_relative_path(
+        current_file: &str,
+        relative_path: &str,
+    ) -> Result<Project, ArkitectError> {
+        let current_dir =
+            Path::new(current_file)
+                .parent()
+                .ok_or_else(|| ArkitectError::NoParentDirectory {
+                    path: current_file.to_string(),
+                })?;
 
         let derived_path = current_dir.join(relative_path);
 
-        let absolute_path = derived_path.canonicalize().unwrap_or_else(|e| {
-            panic!(
-                "Failed to resolve absolute path:\n\
-                 - Current file: '{}'\n\
-                 - Relative path: '{}'\n\
-                 - Derived path (before resolving): '{}'\n\
-                 Cause: {}",
-                current_file,
-                relative_path,
-                derived_path.display(),
-                e
-            )
-        });
+        let absolute_path =
+            derived_path
+                .canonicalize()
+                .map_err(|e| ArkitectError::PathNotFound {
+                    path: derived_path.display().to_string(),
+                    reason: format!("{} ('{}' relative to '{}')", e, relative_path, current_file),
+                })?;
 
-        Project {
-            project_root: absolute_path
-                .to_str()
-                .expect("Failed to convert path to string")
-                .to_string(),
-        }
+        let absolute_path = absolute_path
+            .to_str()
+            .ok_or_else(|| ArkitectError::NonUtf8Path {
+                path: absolute_path.display().to_string(),
+            })?;
+
+        Project::rooted_at(absolute_path.to_string())
+    }
+}
+
+/// The directory of the crate being tested, set by Cargo.
+fn cargo_manifest_dir() -> Result<String, ArkitectError> {
+    env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| ArkitectError::Environment("CARGO_MANIFEST_DIR".to_string()))
+}
+
+impl Default for Project {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::temp_project;
+    use crate::rust_project::RustProject;
+
+    #[test]
+    fn test_changed_git_files_are_filtered_to_scanned_sources() {
+        let project = Project::from_path("examples/metadata_project");
+
+        let files = project
+            .scanned_rust_files(vec![
+                "src/lib.rs".to_string(),
+                "src/domain/order.rs".to_string(),
+                "generated/api.rs".to_string(),
+                "build.rs".to_string(),
+                "benches/orders.rs".to_string(),
+                "README.md".to_string(),
+                "src/target/out.rs".to_string(),
+                "Cargo.toml".to_string(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            files,
+            vec!["src/lib.rs", "src/domain/order.rs", "generated/api.rs"]
+        );
+    }
+
+    #[test]
+    fn test_changed_git_files_outside_a_repository() {
+        // A `.git` file pointing nowhere stops git from finding a repository in the parent
+        // directories, wherever the temporary directory is created
+        let root = temp_project(&[
+            ("Cargo.toml", "[package]\nname = \"not_a_git_repo\"\n"),
+            (".git", "gitdir: nonexistent\n"),
+        ]);
+
+        let project = Project::from_path(root.path().to_str().unwrap());
+
+        assert!(project
+            .changed_git_files(None)
+            .unwrap_err()
+            .starts_with("git diff failed"));
+    }
+
+    #[test]
+    fn test_try_from_path_with_nonexistent_path() {
+        let result = Project::try_from_path("/nonexistent/rust_arkitect_project");
+
+        assert!(matches!(
+            result.err(),
+            Some(ArkitectError::PathNotFound { path, .. }) if path == "/nonexistent/rust_arkitect_project"
+        ));
+    }
+
+    #[test]
+    fn test_try_from_path_without_manifest() {
+        let result = Project::try_from_path("examples");
+
+        assert!(matches!(
+            result.err(),
+            Some(ArkitectError::NotAProject { .. })
+        ));
+    }
+
+    #[test]
+    fn test_try_from_relative_path_with_nonexistent_path() {
+        let result = Project::try_from_relative_path(file!(), "../../nonexistent");
+
+        assert!(matches!(
+            result.err(),
+            Some(ArkitectError::PathNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_try_from_relative_path_without_parent_directory() {
+        let result = Project::try_from_relative_path("", "examples/sample_project");
+
+        assert_eq!(
+            result.err(),
+            Some(ArkitectError::NoParentDirectory {
+                path: "".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_new_in_the_current_crate() {
+        let project = Project::try_new().unwrap();
+
+        assert_eq!(project.project_root, env!("CARGO_MANIFEST_DIR"));
+    }
+
+    #[test]
+    fn test_metadata_options_are_applied() {
+        let project = Project::from_path("examples/metadata_project");
+
+        assert_eq!(project.source_dirs, vec!["generated".to_string()]);
+        assert_eq!(project.baseline, Some(1));
+        assert!(!project.include_targets);
+        assert_eq!(project.ignored_directories, vec!["target", ".git"]);
+    }
+
+    #[test]
+    fn test_members_are_resolved_by_cargo_metadata() {
+        let root = temp_project(&[
+            ("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\nresolver = \"2\"\n"),
+            (
+                "crates/billing/Cargo.toml",
+                "[package]\nname = \"billing-core\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+            ),
+            ("crates/billing/src/lib.rs", "pub struct Invoice;\n"),
+            (
+                "crates/orders/Cargo.toml",
+                "[package]\nname = \"orders\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[lib]\nname = \"orders_api\"\n",
+            ),
+            ("crates/orders/src/lib.rs", "use billing_core::Invoice;\n"),
+        ]);
+
+        let project = Project::from_cargo_metadata_at(root.path().to_str().unwrap());
+
+        let mut members = project.members.clone().unwrap();
+        members.sort();
+        assert_eq!(members, vec!["crates/billing", "crates/orders"]);
+
+        let mut names: Vec<(&str, &str)> = project
+            .packages
+            .iter()
+            .map(|package| (package.name.as_str(), package.crate_name.as_str()))
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![("billing-core", "billing_core"), ("orders", "orders_api")]
+        );
+
+        let options = ArkitectOptions {
+            members: project.members.clone(),
+            ..Default::default()
+        };
+        let rust_project =
+            RustProject::from_directory_with_options(&project.project_root, &options).unwrap();
+        let mut modules: Vec<&str> = rust_project
+            .files
+            .iter()
+            .map(|f| f.logical_path.as_str())
+            .collect();
+        modules.sort();
+        assert_eq!(modules, vec!["billing_core", "orders_api"]);
     }
 }
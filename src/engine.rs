@@ -1,18 +1,65 @@
-use crate::rule::{ProjectRule, Rule};
-use crate::rust_file::RustFile;
-use crate::rust_project::RustProject;
-use ansi_term::Color::RGB;
-use ansi_term::Style;
-use log::{debug, error, info};
+use crate::builtin_rules::utils::IsChild;
+use crate::dependency_cache::DependencyCache;
+use crate::error::ArkitectError;
+use crate::report::{ParseDiagnostic, Report, RuleCheck, RuleTrace, Stats};
+use crate::rule::{ProjectRule, Rule, Severity};
+use crate::rust_file::{CrateNameCache, RustFile};
+use crate::rust_project::{
+    workspace_members, ArkitectOptions, RustProject, DEFAULT_IGNORED_DIRECTORIES,
+};
+use crate::styling::Styling;
+use log::{debug, error, info, warn};
+use std::cmp::Reverse;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 use toml::Value;
 
+/// The name of the check enabled by `Engine::with_matching_subjects_required`.
+const SUBJECTS_MUST_MATCH_FILES: &str = "subjects_must_match_files";
+
+/// The module rules listing what their subject may depend on. Among them, a rule for a nested
+/// subject overrides the ones of the same kind for the enclosing subjects. The other rules are
+/// prohibitions, which are cumulative.
+const ALLOW_LIST_RULES: &[&str] = &[
+    "may_depend_on",
+    "may_depend_on_except",
+    "test_code_may_depend_on",
+    "public_signatures_may_expose_external",
+];
+
 pub(crate) struct Engine<'a> {
     absolute_path: &'a str,
     rules: &'a [Box<dyn Rule>],
+    /// The subject of each module rule, as described by the rule.
+    subjects: Vec<Option<String>>,
+    /// For each module rule, the rules overriding it on the files they apply to, most specific
+    /// first. See `overriding_rules`.
+    overriding: Vec<Vec<usize>>,
     project_rules: &'a [Box<dyn ProjectRule>],
-    violations: Vec<String>,
+    all_matching_rules: bool,
+    ignored_directories: Vec<String>,
+    source_dirs: Vec<String>,
+    include_targets: bool,
+    members: Option<Vec<String>>,
+    excluded_members: Vec<String>,
+    features: Option<Vec<String>>,
+    cache: Option<DependencyCache>,
+    diagnostics: Vec<ParseDiagnostic>,
+    checks: Vec<RuleCheck>,
+    traces: Vec<RuleTrace>,
+    scanned_files: Vec<String>,
+    dry_run: bool,
+    only_files: Option<Vec<PathBuf>>,
+    on_progress: Option<&'a dyn Fn(usize, usize)>,
+    on_violation: Option<&'a dyn Fn(&RuleCheck)>,
+    crate_names: CrateNameCache,
+    stats: Stats,
+    require_matching_subjects: bool,
+    /// Whether each module rule was applicable to at least one file.
+    matched: Vec<bool>,
+    unmatched_subjects: Vec<String>,
+    styling: Styling,
 }
 
 impl<'a> Engine<'a> {
@@ -21,121 +68,650 @@ impl<'a> Engine<'a> {
         rules: &'a [Box<dyn Rule>],
         project_rules: &'a [Box<dyn ProjectRule>],
     ) -> Self {
+        let subjects: Vec<Option<String>> =
+            rules.iter().map(|rule| rule.describe().subject).collect();
+
         Self {
             absolute_path,
             rules,
+            overriding: overriding_rules(rules, &subjects),
+            subjects,
             project_rules,
-            violations: Default::default(),
+            all_matching_rules: false,
+            ignored_directories: DEFAULT_IGNORED_DIRECTORIES
+                .iter()
+                .map(|dir| dir.to_string())
+                .collect(),
+            source_dirs: Default::default(),
+            include_targets: false,
+            members: None,
+            excluded_members: Default::default(),
+            features: None,
+            cache: None,
+            diagnostics: Default::default(),
+            checks: Default::default(),
+            traces: rules
+                .iter()
+                .map(|rule| RuleTrace {
+                    rule: rule.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            scanned_files: Default::default(),
+            dry_run: false,
+            only_files: None,
+            on_progress: None,
+            on_violation: None,
+            crate_names: Default::default(),
+            stats: Default::default(),
+            require_matching_subjects: false,
+            matched: vec![false; rules.len()],
+            unmatched_subjects: vec![],
+            styling: Styling::default(),
+        }
+    }
+
+    /// Renders the styled parts of rules and violations with `styling`.
+    pub(crate) fn with_styling(self, styling: Styling) -> Self {
+        Self { styling, ..self }
+    }
+
+    /// Replaces the directory names skipped while walking the project (`target` and `.git` by default).
+    pub(crate) fn with_ignored_directories(self, ignored_directories: &[String]) -> Self {
+        Self {
+            ignored_directories: ignored_directories.to_vec(),
+            ..self
         }
     }
 
-    pub(crate) fn compute_violations(mut self) -> Vec<String> {
-        let project =
-            RustProject::from_directory(self.absolute_path).expect("Could not build RustProject");
+    /// Scans `source_dirs` (relative to the project root) besides the crates' sources.
+    pub(crate) fn with_source_dirs(self, source_dirs: &[String]) -> Self {
+        Self {
+            source_dirs: source_dirs.to_vec(),
+            ..self
+        }
+    }
+
+    /// Also scans the `bin`, `examples`, `tests` and `benches` targets for the project rules.
+    pub(crate) fn with_targets(self, include_targets: bool) -> Self {
+        Self {
+            include_targets,
+            ..self
+        }
+    }
+
+    /// Restricts a workspace walk to `members` when set, leaving out `excluded_members`.
+    pub(crate) fn with_members(
+        self,
+        members: Option<Vec<String>>,
+        excluded_members: &[String],
+    ) -> Self {
+        Self {
+            members,
+            excluded_members: excluded_members.to_vec(),
+            ..self
+        }
+    }
+
+    /// Leaves out the items disabled by a `#[cfg]` on features other than `features`, when set.
+    pub(crate) fn with_features(self, features: Option<Vec<String>>) -> Self {
+        Self { features, ..self }
+    }
+
+    /// Reads the files left unchanged since the last run from the dependency cache stored in
+    /// `cache_dir`, when set and no rule applicable to them needs their syntax tree.
+    pub(crate) fn with_cache_dir(self, cache_dir: Option<&Path>) -> Self {
+        Self {
+            cache: cache_dir.map(DependencyCache::open),
+            ..self
+        }
+    }
 
-        self.project_rules.iter().for_each(|rule| {
-            debug!("🟢 Rule {} applied", rule);
-            match rule.apply(&project) {
-                Ok(_) => info!("\u{2705} Rule {} respected", rule),
+    /// Applies every rule matching a file, instead of only the one with the most specific subject
+    /// among the rules of the same kind.
+    pub(crate) fn with_all_matching_rules(self, all_matching_rules: bool) -> Self {
+        Self {
+            all_matching_rules,
+            ..self
+        }
+    }
+
+    /// Fails when the subject of a module rule matches none of the scanned files, e.g. because
+    /// of a typo, instead of letting the rule pass without checking anything.
+    pub(crate) fn with_matching_subjects_required(self, require_matching_subjects: bool) -> Self {
+        Self {
+            require_matching_subjects,
+            ..self
+        }
+    }
+
+    /// Only traces which files each module rule applies to, without applying any rule.
+    pub(crate) fn with_dry_run(self) -> Self {
+        Self {
+            dry_run: true,
+            ..self
+        }
+    }
+
+    /// Only applies the module rules to `files` (relative to the project root or absolute) instead
+    /// of walking the project. Project rules need the whole dependency graph, so they are skipped.
+    pub(crate) fn with_only_files(self, files: &[String]) -> Self {
+        let root = Path::new(self.absolute_path);
+        Self {
+            only_files: Some(files.iter().map(|file| root.join(file)).collect()),
+            ..self
+        }
+    }
+
+    /// Calls `on_progress(scanned, total)` after each file the module rules are applied to.
+    pub(crate) fn with_progress(self, on_progress: Option<&'a dyn Fn(usize, usize)>) -> Self {
+        Self {
+            on_progress,
+            ..self
+        }
+    }
+
+    /// Calls `on_violation` with each violated check, as soon as it is found.
+    pub(crate) fn with_violation_callback(
+        self,
+        on_violation: Option<&'a dyn Fn(&RuleCheck)>,
+    ) -> Self {
+        Self {
+            on_violation,
+            ..self
+        }
+    }
+
+    fn options(&self) -> ArkitectOptions {
+        ArkitectOptions {
+            source_dirs: self.source_dirs.clone(),
+            ignored_directories: Some(self.ignored_directories.clone()),
+            baseline: None,
+            include_targets: self.include_targets,
+            members: self.members.clone(),
+            excluded_members: self.excluded_members.clone(),
+            features: self.features.clone(),
+        }
+    }
+
+    pub(crate) fn compute_violations(self) -> Report {
+        self.try_compute_violations()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `compute_violations`, returning an error when the project can't be scanned.
+    pub(crate) fn try_compute_violations(mut self) -> Result<Report, ArkitectError> {
+        let start = Instant::now();
+        if let Some(cache) = &mut self.cache {
+            cache.use_features(&self.features);
+        }
+        if let Some(files) = self.only_files.take() {
+            info!("Checking {} files only, project rules skipped", files.len());
+            let (files, skipped): (Vec<PathBuf>, Vec<PathBuf>) =
+                files.into_iter().partition(|file| {
+                    file.extension().is_some_and(|ext| ext == "rs") && file.is_file()
+                });
+            for file in skipped {
+                debug!("Skipping '{}', not an existing Rust file", file.display());
+            }
+            self.apply_rules_to_all(files);
+            return Ok(self.into_report(start));
+        }
+
+        let project_rules = if self.dry_run {
+            &[]
+        } else {
+            self.project_rules
+        };
+        let parsing = Instant::now();
+        let options = self.options();
+        let project = match &mut self.cache {
+            Some(cache) if !project_rules.iter().any(|rule| rule.needs_syntax_tree()) => {
+                RustProject::from_directory_cached(self.absolute_path, &options, cache)
+            }
+            _ => RustProject::from_directory_with_options(self.absolute_path, &options).inspect(
+                |project| {
+                    self.stats.files_parsed += project.files.len();
+                },
+            ),
+        }
+        .map_err(|e| ArkitectError::NotAProject {
+            path: self.absolute_path.to_string(),
+            reason: e.to_string(),
+        })?;
+        self.stats.parsing += parsing.elapsed();
+        project_rules.iter().enumerate().for_each(|(index, rule)| {
+            let description = rule.render(&self.styling);
+            debug!("🟢 Rule {} applied", description);
+            let evaluation = Instant::now();
+            let result = rule.apply(&project);
+            self.stats.evaluation += evaluation.elapsed();
+            self.stats.rules_evaluated += 1;
+            let violation = match result {
+                Ok(_) => {
+                    info!("\u{2705} Rule {} respected", description);
+                    None
+                }
                 Err(e) => {
-                    error!("🟥 Rule {} violated: {}", rule, e);
-                    self.violations.push(e)
+                    log_violation(
+                        &self.styling,
+                        &description,
+                        rule.name(),
+                        rule.severity(),
+                        &e,
+                    );
+                    Some(e)
                 }
-            }
+            };
+            self.record_check(RuleCheck {
+                rule: rule.to_string(),
+                rule_name: rule.name().to_string(),
+                index,
+                severity: rule.severity(),
+                path: None,
+                line: None,
+                violation,
+            });
         });
 
         // TODO: sfruttare il fatto che project ha già parsato tutti i file
+        let mut files = Vec::new();
         if is_workspace(self.absolute_path).is_ok() {
             info!("Workspace found: {}", self.absolute_path);
-            self.validate_workspace(self.absolute_path);
-        } else if is_crate(self.absolute_path).is_ok() {
-            info!("Crate found: {}", self.absolute_path);
-            self.validate_dir(self.absolute_path);
+            self.collect_workspace_files(self.absolute_path, &mut files)?;
+            // Only the members are walked in a workspace, a crate is walked from its root
+            for source_dir in &self.source_dirs {
+                let source_path = Path::new(self.absolute_path).join(source_dir);
+                if source_path.is_dir() {
+                    self.collect_dir_files(&source_path, &mut files)?;
+                }
+            }
+        } else if let Err(reason) = is_crate(self.absolute_path) {
+            return Err(ArkitectError::NotAProject {
+                path: self.absolute_path.to_string(),
+                reason,
+            });
         } else {
-            panic!(
-                "The path '{}' is not a workspace or crate",
-                self.absolute_path
+            info!("Crate found: {}", self.absolute_path);
+            self.collect_dir_files(Path::new(self.absolute_path), &mut files)?;
+        }
+        if let Some(cache) = &mut self.cache {
+            let paths: Vec<String> = files
+                .iter()
+                .map(|file| file.to_string_lossy().into_owned())
+                .chain(project.files.iter().map(|file| file.path.clone()))
+                .collect();
+            cache.retain_files(&paths);
+        }
+        self.apply_rules_to_all(files);
+        if self.require_matching_subjects && !self.dry_run {
+            self.check_matching_subjects();
+        }
+
+        Ok(self.into_report(start))
+    }
+
+    fn apply_rules_to_all(&mut self, files: Vec<PathBuf>) {
+        let total = files.len();
+        for (scanned, file) in files.into_iter().enumerate() {
+            self.apply_rules(file);
+            if let Some(on_progress) = self.on_progress {
+                on_progress(scanned + 1, total);
+            }
+        }
+    }
+
+    /// Records a project-level check failing with the subjects no applicable rule had.
+    fn check_matching_subjects(&mut self) {
+        let mut unmatched: Vec<&str> = Vec::new();
+        for (index, subject) in self.subjects.iter().enumerate() {
+            let Some(subject) = subject else {
+                continue;
+            };
+            let matched = (0..self.rules.len())
+                .any(|other| self.matched[other] && self.subjects[other].as_ref() == Some(subject));
+            if !matched && !unmatched.contains(&subject.as_str()) {
+                debug!(
+                    "Rule {} matches no file",
+                    self.rules[index].render(&self.styling)
+                );
+                unmatched.push(subject);
+            }
+        }
+
+        let rule = "Subjects must match at least one file".to_string();
+        let violation = (!unmatched.is_empty()).then(|| unmatched_subjects_violation(&unmatched));
+        self.unmatched_subjects = unmatched.into_iter().map(String::from).collect();
+        if let Some(violation) = &violation {
+            log_violation(
+                &self.styling,
+                &rule,
+                SUBJECTS_MUST_MATCH_FILES,
+                Severity::Error,
+                violation,
             );
         }
 
-        self.violations
+        self.record_check(RuleCheck {
+            rule,
+            rule_name: SUBJECTS_MUST_MATCH_FILES.to_string(),
+            index: RuleCheck::ENGINE_CHECK,
+            severity: Severity::Error,
+            path: None,
+            line: None,
+            violation,
+        });
+    }
+
+    fn record_check(&mut self, check: RuleCheck) {
+        if let Some(on_violation) = self.on_violation {
+            if check.violation.is_some() {
+                on_violation(&check);
+            }
+        }
+        self.checks.push(check);
+    }
+
+    fn into_report(mut self, start: Instant) -> Report {
+        if let Some(cache) = &self.cache {
+            self.stats.files_parsed += cache.files_parsed();
+            if let Err(e) = cache.save() {
+                warn!("⚠️ Failed to save the dependency cache: {}", e);
+            }
+        }
+
+        let violations_with = |severity: Severity| {
+            self.checks
+                .iter()
+                .filter(|check| check.severity == severity)
+                .filter_map(|check| check.violation.clone())
+                .collect()
+        };
+
+        Report {
+            violations: violations_with(Severity::Error),
+            warnings: violations_with(Severity::Warning),
+            diagnostics: self.diagnostics,
+            checks: self.checks,
+            traces: self.traces,
+            scanned_files: self.scanned_files,
+            unmatched_subjects: self.unmatched_subjects,
+            stats: Stats {
+                elapsed: start.elapsed(),
+                ..self.stats
+            },
+        }
     }
 
-    fn validate_workspace(&mut self, workspace_path: &str) {
+    fn collect_workspace_files(
+        &self,
+        workspace_path: &str,
+        files: &mut Vec<PathBuf>,
+    ) -> Result<(), ArkitectError> {
         let cargo_toml_path = Path::new(workspace_path).join("Cargo.toml");
+        let invalid_manifest = |reason: String| ArkitectError::InvalidManifest {
+            path: cargo_toml_path.display().to_string(),
+            reason,
+        };
 
-        let cargo_toml_content = fs::read_to_string(&cargo_toml_path)
-            .unwrap_or_else(|_| panic!("Failed to read Cargo.toml in '{}'", workspace_path));
+        let cargo_toml_content =
+            fs::read_to_string(&cargo_toml_path).map_err(|e| invalid_manifest(e.to_string()))?;
 
-        let parsed: Value = toml::from_str(&cargo_toml_content)
-            .unwrap_or_else(|_| panic!("Failed to parse Cargo.toml in '{}'", workspace_path));
+        let parsed: Value =
+            toml::from_str(&cargo_toml_content).map_err(|e| invalid_manifest(e.to_string()))?;
 
-        let members = parsed
-            .get("workspace")
-            .and_then(|workspace| workspace.get("members"))
-            .and_then(|members| members.as_array())
-            .unwrap_or(&vec![])
-            .iter()
-            .filter_map(|member| member.as_str())
-            .map(String::from)
-            .collect::<Vec<String>>();
+        let Some(workspace) = parsed.get("workspace") else {
+            return Ok(());
+        };
+        let members = workspace_members(Path::new(workspace_path), workspace, &self.options())
+            .map_err(|e| invalid_manifest(format!("invalid workspace members: {}", e)))?;
 
-        for member in members {
-            let member_path = Path::new(workspace_path).join(&member);
+        for member_path in members {
             if member_path.is_dir() {
-                if is_crate(member_path.to_str().unwrap()).is_ok() {
-                    self.validate_dir(member_path.to_str().unwrap());
+                let member_dir =
+                    member_path
+                        .to_str()
+                        .ok_or_else(|| ArkitectError::NonUtf8Path {
+                            path: member_path.display().to_string(),
+                        })?;
+                if is_crate(member_dir).is_ok() {
+                    self.collect_dir_files(&member_path, files)?;
                 } else {
                     debug!("Skipping invalid crate '{}'", member_path.display());
                 }
             }
         }
+
+        Ok(())
     }
 
-    fn validate_dir(&mut self, dir: &str) {
-        let entries =
-            fs::read_dir(dir).unwrap_or_else(|_| panic!("Error reading root directory '{}'", dir));
+    fn collect_dir_files(&self, dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), ArkitectError> {
+        let io_error = |e: std::io::Error| ArkitectError::Io {
+            path: dir.display().to_string(),
+            reason: e.to_string(),
+        };
 
-        for file in entries {
-            match file {
-                Ok(file) => {
-                    if file.metadata().unwrap().is_dir() {
-                        self.validate_dir(file.path().to_str().unwrap());
-                    } else if file.path().extension().map_or(false, |ext| ext == "rs") {
-                        self.apply_rules(file.path());
-                    }
+        for file in fs::read_dir(dir).map_err(io_error)? {
+            let file = file.map_err(io_error)?;
+            if file.metadata().map_err(io_error)?.is_dir() {
+                if self.is_ignored(&file.path()) {
+                    debug!("Skipping ignored directory '{}'", file.path().display());
+                    continue;
                 }
-                Err(_) => panic!("Error reading file"),
+                self.collect_dir_files(&file.path(), files)?;
+            } else if file.path().extension().is_some_and(|ext| ext == "rs") {
+                files.push(file.path());
             }
         }
+
+        Ok(())
+    }
+
+    fn is_ignored(&self, dir: &Path) -> bool {
+        dir.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                self.ignored_directories
+                    .iter()
+                    .any(|ignored| ignored == name)
+            })
     }
 
     fn apply_rules(&mut self, file: PathBuf) {
-        let file_name = file.to_str().unwrap();
-        let bold = Style::new().bold().fg(RGB(0, 255, 0));
-        let file = RustFile::from_file_system(file_name);
+        let Some(file_name) = file.to_str() else {
+            let error = ArkitectError::NonUtf8Path {
+                path: file.display().to_string(),
+            };
+            warn!("⚠️ Skipping {}", error);
+            self.diagnostics.push(ParseDiagnostic {
+                path: file.display().to_string(),
+                line: None,
+                message: error.to_string(),
+            });
+            return;
+        };
+        let parsing = Instant::now();
+        let (rules, dry_run) = (self.rules, self.dry_run);
+        let parsed = match &mut self.cache {
+            Some(cache) => cache.file(file_name, &mut self.crate_names, |file| {
+                !dry_run
+                    && rules
+                        .iter()
+                        .any(|rule| rule.needs_syntax_tree() && rule.is_applicable(file))
+            }),
+            None => RustFile::try_from_file_system_with_features(
+                file_name,
+                &mut self.crate_names,
+                self.features.as_deref(),
+            )
+            .inspect(|_| self.stats.files_parsed += 1),
+        };
+        self.stats.parsing += parsing.elapsed();
+        let file = match parsed {
+            Ok(file) => file,
+            Err(error) => {
+                warn!("⚠️ Skipping {}: {}", file_name, error);
+                let line = match &error {
+                    ArkitectError::Parse { line, .. } => Some(*line),
+                    _ => None,
+                };
+                self.diagnostics.push(ParseDiagnostic {
+                    path: file_name.to_string(),
+                    line,
+                    message: error.to_string(),
+                });
+                return;
+            }
+        };
+        self.stats.files_scanned += 1;
+        self.scanned_files.push(file.path.clone());
         info!(
             "🛠Applying rules to {} ({})",
             &file.logical_path,
-            bold.paint(&file.path)
+            self.styling.path(&file.path)
         );
-        for rule in self.rules {
-            if rule.is_applicable(&file) {
-                debug!("🟢 Rule {} applied", rule);
-                match rule.apply(&file) {
-                    Ok(_) => info!("\u{2705} Rule {} respected", rule),
-                    Err(e) => {
-                        error!("🟥 Rule {} violated: {}", rule, e);
-                        self.violations.push(e)
-                    }
-                }
+        let applicable: Vec<bool> = self
+            .rules
+            .iter()
+            .map(|rule| rule.is_applicable(&file))
+            .collect();
+        for (matched, applicable) in self.matched.iter_mut().zip(&applicable) {
+            *matched |= applicable;
+        }
+        let overridden: Vec<Option<usize>> = self
+            .overriding
+            .iter()
+            .map(|overriding| {
+                overriding
+                    .iter()
+                    .copied()
+                    .find(|&other| !self.all_matching_rules && applicable[other])
+            })
+            .collect();
+
+        let subjects = &self.subjects;
+        // The subjects a rule overrides on this file, named in its violations
+        let won_over = |index: usize| -> Vec<&str> {
+            (0..overridden.len())
+                .filter(|&other| overridden[other] == Some(index))
+                .filter_map(|other| subjects[other].as_deref())
+                .collect()
+        };
+
+        let styling = self.styling;
+        let mut checks = Vec::new();
+        for (index, (rule, trace)) in self.rules.iter().zip(self.traces.iter_mut()).enumerate() {
+            let rule_text = || rule.render(&styling);
+            if !applicable[index] {
+                debug!("❌ Rule {} not applied", rule_text());
+            } else if let Some(other) = overridden[index] {
+                debug!(
+                    "❌ Rule {} not applied, overridden by {}",
+                    rule_text(),
+                    self.rules[other].render(&styling)
+                );
+            } else if self.dry_run {
+                self.stats.applicable_matches += 1;
+                trace.applicable.push(file.path.clone());
             } else {
-                debug!("❌ Rule {} not applied", rule);
+                debug!("🟢 Rule {} applied", rule_text());
+                self.stats.applicable_matches += 1;
+                trace.applicable.push(file.path.clone());
+                let evaluation = Instant::now();
+                let result = rule.apply_located(&file);
+                self.stats.evaluation += evaluation.elapsed();
+                self.stats.rules_evaluated += 1;
+                let (violation, line) = match result {
+                    Ok(_) => {
+                        info!("\u{2705} Rule {} respected", rule_text());
+                        (None, None)
+                    }
+                    Err((e, _)) if file.suppressed_rules.iter().any(|name| name == rule.name()) => {
+                        info!(
+                            "🔕 Rule {} violation suppressed by arkitect:allow({}) in {}: {}",
+                            rule_text(),
+                            rule.name(),
+                            file.path,
+                            e
+                        );
+                        (None, None)
+                    }
+                    Err((e, line)) => {
+                        let won_over = won_over(index);
+                        let e = match &subjects[index] {
+                            Some(subject) if !won_over.is_empty() => format!(
+                                "{} (subject {} overrides {})",
+                                e,
+                                subject,
+                                won_over.join(", ")
+                            ),
+                            _ => e,
+                        };
+                        log_violation(&styling, &rule_text(), rule.name(), rule.severity(), &e);
+                        (Some(e), line)
+                    }
+                };
+                checks.push(RuleCheck {
+                    rule: rule.to_string(),
+                    rule_name: rule.name().to_string(),
+                    index,
+                    severity: rule.severity(),
+                    path: Some(file.path.clone()),
+                    line,
+                    violation,
+                });
             }
         }
+        for check in checks {
+            self.record_check(check);
+        }
     }
 }
 
+/// For each rule, the rules overriding it on the files they both apply to, most specific first:
+/// the `ALLOW_LIST_RULES` of the same kind whose subject is nested in its own, like
+/// `my_crate::domain::models` in `my_crate::domain`. Only subjects that are module paths are
+/// compared, a regex subject neither overrides nor is overridden.
+fn overriding_rules(rules: &[Box<dyn Rule>], subjects: &[Option<String>]) -> Vec<Vec<usize>> {
+    let allow_list = |index: usize| {
+        let name = rules[index].name();
+        match &subjects[index] {
+            Some(subject) if ALLOW_LIST_RULES.contains(&name) && is_module_path(subject) => {
+                Some((name, subject.as_str()))
+            }
+            _ => None,
+        }
+    };
+
+    (0..rules.len())
+        .map(|index| {
+            let Some((name, subject)) = allow_list(index) else {
+                return vec![];
+            };
+            let mut overriding: Vec<(usize, &str)> = (0..rules.len())
+                .filter_map(|other| match allow_list(other) {
+                    Some((other_name, nested))
+                        if other_name == name
+                            && nested.is_child_of(subject)
+                            && nested != subject =>
+                    {
+                        Some((other, nested))
+                    }
+                    _ => None,
+                })
+                .collect();
+            overriding.sort_by_key(|(_, nested)| Reverse(nested.split("::").count()));
+            overriding.into_iter().map(|(other, _)| other).collect()
+        })
+        .collect()
+}
+
+fn is_module_path(subject: &str) -> bool {
+    subject.split("::").all(|segment| {
+        !segment.is_empty() && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
 fn is_crate(path: &str) -> Result<(), String> {
     let dir_path = Path::new(path);
 
@@ -184,9 +760,360 @@ fn is_directory(path: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// The violation of `subjects_must_match_files` for the `unmatched` subjects.
+pub(crate) fn unmatched_subjects_violation(unmatched: &[&str]) -> String {
+    format!("Subjects matching no file: {}", unmatched.join(", "))
+}
+
+fn log_violation(styling: &Styling, rule: &str, name: &str, severity: Severity, violation: &str) {
+    let violation = styling.forbidden(violation);
+    match severity {
+        Severity::Error => error!("🟥 Rule {} ({}) violated: {}", rule, name, violation),
+        Severity::Warning => warn!("🟨 Rule {} ({}) violated: {}", rule, name, violation),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::builtin_rules::configured::DependencyRule;
+    use crate::builtin_rules::may_depend_on::MayDependOnRule;
+    use crate::builtin_rules::must_not_depend_on::MustNotDependOnRule;
+    use crate::builtin_rules::subject::Subject;
+    use crate::fixtures::temp_project;
+    use tempfile::TempDir;
+
+    /// Builds a throwaway crate named `name` in a temporary directory with the given files.
+    fn temp_crate(name: &str, files: &[(&str, &str)]) -> TempDir {
+        let manifest = format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name);
+        let mut files = files.to_vec();
+        files.push(("Cargo.toml", &manifest));
+
+        temp_project(&files)
+    }
+
+    /// A crate whose `target/` directory contains a file violating `forbid_dependencies_on_forbidden`.
+    fn crate_with_target_dir(name: &str) -> TempDir {
+        temp_crate(
+            name,
+            &[
+                ("src/lib.rs", "pub fn clean() {}\n"),
+                ("target/debug/build/generated.rs", "use forbidden::Thing;\n"),
+            ],
+        )
+    }
+
+    fn forbid_dependencies_on_forbidden(crate_name: &str) -> Vec<Box<dyn Rule>> {
+        vec![Box::new(MustNotDependOnRule::new(
+            crate_name.to_string(),
+            vec!["forbidden".to_string()],
+        ))]
+    }
+
+    #[test]
+    fn test_target_directory_is_skipped() {
+        let root = crate_with_target_dir("target_is_skipped");
+        let rules = forbid_dependencies_on_forbidden("target_is_skipped");
+
+        let violations = Engine::new(root.path().to_str().unwrap(), &rules, &[])
+            .compute_violations()
+            .violations;
+
+        assert!(
+            violations.is_empty(),
+            "Expected files under target/ to be skipped, got: {:?}",
+            violations
+        );
+    }
+
+    #[test]
+    fn test_ignored_directories_can_be_overridden() {
+        let root = crate_with_target_dir("target_is_scanned");
+        let rules = forbid_dependencies_on_forbidden("target_is_scanned");
+
+        let violations = Engine::new(root.path().to_str().unwrap(), &rules, &[])
+            .with_ignored_directories(&[])
+            .compute_violations()
+            .violations;
+
+        assert_eq!(violations.len(), 1);
+    }
+
+    #[test]
+    fn test_excluded_workspace_members_are_not_walked() {
+        let root = temp_project(&[
+            ("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n"),
+            ("crates/clean/Cargo.toml", "[package]\nname = \"clean\"\n"),
+            ("crates/clean/src/lib.rs", "pub fn clean() {}\n"),
+            ("crates/legacy/Cargo.toml", "[package]\nname = \"legacy\"\n"),
+            ("crates/legacy/src/lib.rs", "use forbidden::Thing;\n"),
+        ]);
+        let rules = forbid_dependencies_on_forbidden("legacy");
+
+        let report = Engine::new(root.path().to_str().unwrap(), &rules, &[]).compute_violations();
+        assert_eq!(report.violations.len(), 1);
+
+        let report = Engine::new(root.path().to_str().unwrap(), &rules, &[])
+            .with_members(None, &["legacy".to_string()])
+            .compute_violations();
+        assert!(report.violations.is_empty());
+        assert!(report
+            .checks
+            .iter()
+            .all(|check| !check.path.as_ref().unwrap().contains("legacy")));
+    }
+
+    #[test]
+    fn test_most_specific_allow_list_wins() {
+        let root = temp_crate(
+            "nested_subjects",
+            &[
+                ("src/lib.rs", "mod domain;\n"),
+                ("src/domain.rs", "pub mod models;\n"),
+                ("src/domain/models.rs", "use serde::Serialize;\n"),
+            ],
+        );
+        let may_depend_on = |subject: &str, allowed: &[&str]| -> Box<dyn Rule> {
+            Box::new(MayDependOnRule::new(
+                subject,
+                allowed.iter().map(|a| a.to_string()).collect(),
+            ))
+        };
+        let must_not_depend_on = |subject: &str, forbidden: &str| -> Box<dyn Rule> {
+            Box::new(MustNotDependOnRule::new(
+                subject.to_string(),
+                vec![forbidden.to_string()],
+            ))
+        };
+        let rules = vec![
+            may_depend_on("nested_subjects::domain", &[]),
+            may_depend_on("nested_subjects::domain::models", &["serde"]),
+            must_not_depend_on("nested_subjects::domain", "serde"),
+            must_not_depend_on("nested_subjects::domain::models", "rand"),
+        ];
+        let root = root.path().to_str().unwrap();
+        let violated = |report: &Report| -> Vec<usize> {
+            report
+                .checks
+                .iter()
+                .filter(|check| check.violation.is_some())
+                .map(|check| check.index)
+                .collect()
+        };
+
+        // Prohibitions are cumulative, only the broader allow-list is overridden
+        let report = Engine::new(root, &rules, &[]).compute_violations();
+        assert_eq!(violated(&report), vec![2]);
+        assert!(report.checks.iter().any(|check| check.index == 1));
+        assert!(report.traces[0]
+            .not_applicable(&report.scanned_files)
+            .any(|path| path.ends_with("models.rs")));
+
+        let report = Engine::new(root, &rules, &[])
+            .with_all_matching_rules(true)
+            .compute_violations();
+        assert_eq!(violated(&report), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_violations_name_the_winning_subject() {
+        let root = temp_crate(
+            "winning_subject",
+            &[
+                ("src/lib.rs", "mod domain;\n"),
+                ("src/domain.rs", "pub mod models;\n"),
+                ("src/domain/models.rs", "use serde::Serialize;\n"),
+            ],
+        );
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(MayDependOnRule::new(
+                "winning_subject::domain",
+                vec!["serde".to_string()],
+            )),
+            Box::new(MayDependOnRule::new(
+                "winning_subject::domain::models",
+                vec![],
+            )),
+        ];
+
+        let report = Engine::new(root.path().to_str().unwrap(), &rules, &[]).compute_violations();
+
+        assert_eq!(report.violations.len(), 1);
+        assert!(report.violations[0].ends_with(
+            "(subject winning_subject::domain::models overrides winning_subject::domain)"
+        ));
+    }
+
+    #[test]
+    fn test_regex_subjects_are_never_overridden() {
+        let root = temp_crate(
+            "regex_subjects",
+            &[
+                ("src/lib.rs", "mod domain;\n"),
+                ("src/domain.rs", "pub mod models;\n"),
+                ("src/domain/models.rs", "use serde::Serialize;\n"),
+            ],
+        );
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(
+                MayDependOnRule::new("", vec![])
+                    .matching(Subject::regex(r"regex_subjects::dom\w+").unwrap()),
+            ),
+            Box::new(MayDependOnRule::new(
+                "regex_subjects::domain::models",
+                vec!["serde".to_string()],
+            )),
+        ];
+
+        let report = Engine::new(root.path().to_str().unwrap(), &rules, &[]).compute_violations();
+
+        assert_eq!(report.violations.len(), 1);
+    }
+
+    #[test]
+    fn test_unparseable_files_are_reported_as_diagnostics() {
+        let root = temp_crate(
+            "unparseable_file",
+            &[
+                ("src/lib.rs", "mod broken;\nuse forbidden::Thing;\n"),
+                ("src/broken.rs", "fn broken( {\n"),
+            ],
+        );
+        let rules = forbid_dependencies_on_forbidden("unparseable_file");
+
+        let report = Engine::new(root.path().to_str().unwrap(), &rules, &[]).compute_violations();
+
+        assert_eq!(report.diagnostics.len(), 1);
+        assert!(report.diagnostics[0].path.ends_with("broken.rs"));
+        assert_eq!(report.diagnostics[0].line, Some(1));
+        assert!(report.diagnostics[0]
+            .message
+            .starts_with("Failed to parse file"));
+        assert_eq!(
+            report.violations.len(),
+            1,
+            "Other files must still be analyzed"
+        );
+    }
+
+    #[test]
+    fn test_suppressed_violations_are_not_reported() {
+        let root = temp_crate(
+            "suppressed_violation",
+            &[
+                ("src/lib.rs", "mod legacy;\nmod orders;\n"),
+                (
+                    "src/legacy.rs",
+                    "// arkitect:allow(must_not_depend_on)\nuse forbidden::Thing;\n",
+                ),
+                ("src/orders.rs", "use forbidden::Thing;\n"),
+            ],
+        );
+        let rules = forbid_dependencies_on_forbidden("suppressed_violation");
+
+        let report = Engine::new(root.path().to_str().unwrap(), &rules, &[]).compute_violations();
+
+        assert_eq!(report.violations.len(), 1);
+        assert!(report.violations[0].contains("orders.rs"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_paths_are_reported_as_diagnostics() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let root = temp_crate(
+            "non_utf8_path",
+            &[("src/lib.rs", "use forbidden::Thing;\n")],
+        );
+        let dir = root
+            .path()
+            .join("src")
+            .join(OsStr::from_bytes(b"invalid_\xff"));
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join(OsStr::from_bytes(b"file_\xff.rs")), "").unwrap();
+        let rules = forbid_dependencies_on_forbidden("non_utf8_path");
+
+        let report = Engine::new(root.path().to_str().unwrap(), &rules, &[]).compute_violations();
+
+        assert_eq!(report.diagnostics.len(), 1);
+        assert!(report.diagnostics[0]
+            .message
+            .ends_with("is not valid UTF-8"));
+        assert_eq!(report.violations.len(), 1);
+    }
+
+    #[test]
+    fn test_malformed_fixture_does_not_stop_the_analysis() {
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(MustNotDependOnRule::new(
+            "malformed_project::domain".to_string(),
+            vec!["malformed_project::infrastructure".to_string()],
+        ))];
+
+        let report = Engine::new("examples/malformed_project", &rules, &[]).compute_violations();
+
+        assert_eq!(report.diagnostics.len(), 1);
+        assert!(report.diagnostics[0].path.ends_with("broken.rs"));
+        assert_eq!(report.diagnostics[0].line, Some(4));
+        assert_eq!(report.violations.len(), 1);
+        assert!(report.violations[0].contains("src/domain.rs"));
+    }
+
+    #[test]
+    fn test_checks_record_respected_and_violated_rules() {
+        let root = temp_crate(
+            "rule_checks",
+            &[
+                ("src/lib.rs", "mod clean;\nuse forbidden::Thing;\n"),
+                ("src/clean.rs", "pub fn clean() {}\n"),
+            ],
+        );
+        let rules = forbid_dependencies_on_forbidden("rule_checks");
+
+        let report = Engine::new(root.path().to_str().unwrap(), &rules, &[]).compute_violations();
+
+        assert_eq!(report.checks.len(), 2);
+        let failed: Vec<_> = report
+            .checks
+            .iter()
+            .filter(|check| check.violation.is_some())
+            .collect();
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].path.as_ref().unwrap().ends_with("lib.rs"));
+        assert_eq!(failed[0].rule_name, "must_not_depend_on");
+        assert_eq!(failed[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_dry_run_traces_applicability_without_violations() {
+        let root = temp_crate(
+            "dry_run",
+            &[
+                ("src/lib.rs", "mod clean;\nuse forbidden::Thing;\n"),
+                ("src/clean.rs", "pub fn clean() {}\n"),
+            ],
+        );
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(MustNotDependOnRule::new(
+            "dry_run::clean",
+            vec!["forbidden".to_string()],
+        ))];
+
+        let report = Engine::new(root.path().to_str().unwrap(), &rules, &[])
+            .with_dry_run()
+            .compute_violations();
+
+        assert!(report.violations.is_empty());
+        assert!(report.checks.is_empty());
+        assert_eq!(report.traces.len(), 1);
+        assert_eq!(report.traces[0].applicable.len(), 1);
+        assert!(report.traces[0].applicable[0].ends_with("clean.rs"));
+        let not_applicable: Vec<&String> = report.traces[0]
+            .not_applicable(&report.scanned_files)
+            .collect();
+        assert_eq!(not_applicable.len(), 1);
+        assert!(not_applicable[0].ends_with("lib.rs"));
+    }
 
     #[test]
     fn test_is_workspace_valid() {
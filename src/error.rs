@@ -0,0 +1,81 @@
+use std::fmt::{Display, Formatter};
+
+/// Why a project or a file couldn't be loaded, returned by the `try_` constructors so that tools
+/// embedding arkitect can recover instead of panicking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArkitectError {
+    /// The path doesn't exist or can't be resolved.
+    PathNotFound { path: String, reason: String },
+    /// The path has no parent directory to resolve a relative path from.
+    NoParentDirectory { path: String },
+    /// The path isn't valid UTF-8.
+    NonUtf8Path { path: String },
+    /// The `Cargo.toml` is missing, unreadable or invalid, arkitect metadata included.
+    InvalidManifest { path: String, reason: String },
+    /// The path is neither a crate nor a workspace.
+    NotAProject { path: String, reason: String },
+    /// A file couldn't be read.
+    Io { path: String, reason: String },
+    /// The module of a Rust file can't be derived from its path and crate.
+    ModulePath { path: String, reason: String },
+    /// A Rust file isn't valid Rust, `line` is where `syn` gave up.
+    Parse {
+        path: String,
+        line: usize,
+        reason: String,
+    },
+    /// An environment variable such as `CARGO_MANIFEST_DIR` is missing.
+    Environment(String),
+    /// Project rules, named in `rules`, were given for the single file at `path`.
+    ProjectRulesOnSingleFile { path: String, rules: Vec<String> },
+}
+
+impl Display for ArkitectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArkitectError::PathNotFound { path, reason } => {
+                write!(f, "The provided path '{}' does not exist: {}", path, reason)
+            }
+            ArkitectError::NoParentDirectory { path } => {
+                write!(f, "The path '{}' has no parent directory", path)
+            }
+            ArkitectError::NonUtf8Path { path } => {
+                write!(f, "The path '{}' is not valid UTF-8", path)
+            }
+            ArkitectError::InvalidManifest { path, reason } => {
+                write!(f, "Invalid `Cargo.toml` at '{}': {}", path, reason)
+            }
+            ArkitectError::NotAProject { path, reason } => {
+                write!(
+                    f,
+                    "The path '{}' is not a workspace or crate: {}",
+                    path, reason
+                )
+            }
+            ArkitectError::Io { path, reason } => {
+                write!(f, "Failed to read file file://{}: {}", path, reason)
+            }
+            ArkitectError::ModulePath { path, reason } => {
+                write!(f, "Failed to compute module path {}: {}", path, reason)
+            }
+            ArkitectError::Parse { path, line, reason } => {
+                write!(
+                    f,
+                    "Failed to parse file file://{}:{}: {}",
+                    path, line, reason
+                )
+            }
+            ArkitectError::Environment(variable) => write!(f, "{} is not set", variable),
+            ArkitectError::ProjectRulesOnSingleFile { path, rules } => {
+                write!(
+                    f,
+                    "Project rules [{}] need the whole project and can't be checked on the single file file://{}",
+                    rules.join(", "),
+                    path
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArkitectError {}
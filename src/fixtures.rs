@@ -0,0 +1,17 @@
+//! Throwaway projects for the tests, each in its own temporary directory.
+
+use std::fs;
+use tempfile::TempDir;
+
+/// Writes `files`, given as `(path from the root, content)` pairs, to a new temporary directory
+/// removed when the returned `TempDir` is dropped.
+pub(crate) fn temp_project(files: &[(&str, &str)]) -> TempDir {
+    let root = tempfile::tempdir().unwrap();
+    for (path, content) in files {
+        let path = root.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    root
+}
@@ -1,8 +1,18 @@
 mod dependency_parsing;
 mod engine;
+#[cfg(test)]
+mod fixtures;
 
 pub mod builtin_rules;
+pub mod dependency_cache;
 pub mod dsl;
+pub mod error;
+pub mod report;
 pub mod rule;
 pub mod rust_file;
 pub mod rust_project;
+pub mod styling;
+
+/// Extracts the dependencies of a parsed file. Exposed for the benchmarks, not a stable API.
+#[doc(hidden)]
+pub use dependency_parsing::get_dependencies_in_file;
@@ -0,0 +1,504 @@
+use crate::rule::Severity;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// A file that was skipped because it could not be read or parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDiagnostic {
+    pub path: String,
+    /// The line of the syntax error, `None` when the file couldn't be read.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// A rule applied to a file, or to the whole project when `path` is `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleCheck {
+    /// The rule's `Display`.
+    pub rule: String,
+    /// The rule's stable `name`, e.g. `may_depend_on`.
+    pub rule_name: String,
+    /// Position of the rule among the module rules, or among the project rules when `path` is `None`.
+    /// `RuleCheck::ENGINE_CHECK` for the checks made by the engine itself, which have no rule.
+    pub index: usize,
+    pub severity: Severity,
+    pub path: Option<String>,
+    /// The line of `path` the violation points to, when the rule locates it, see
+    /// `Rule::apply_located`.
+    pub line: Option<usize>,
+    /// The violation message, `None` when the rule is respected.
+    pub violation: Option<String>,
+}
+
+impl RuleCheck {
+    /// The `index` of checks made by the engine itself, like `subjects_must_match_files`.
+    pub const ENGINE_CHECK: usize = usize::MAX;
+}
+
+/// The scanned files a module rule applies to. The other ones are derived from the scanned files
+/// by `not_applicable`, so that a trace doesn't grow with every file the rule skips.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleTrace {
+    /// The rule's `Display`.
+    pub rule: String,
+    /// The files `is_applicable` is true for, in scan order.
+    pub applicable: Vec<String>,
+}
+
+impl RuleTrace {
+    /// The files among `scanned_files`, in scan order like `applicable`, the rule doesn't apply to.
+    pub fn not_applicable<'a>(
+        &'a self,
+        scanned_files: &'a [String],
+    ) -> impl Iterator<Item = &'a String> {
+        let mut applicable = self.applicable.iter().peekable();
+        scanned_files
+            .iter()
+            .filter(move |file| applicable.next_if_eq(file).is_none())
+    }
+}
+
+/// Counters and timings of an analysis, e.g. to track its performance over time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The Rust files the module rules were applied to, without the ones that couldn't be parsed.
+    pub files_scanned: usize,
+    /// The Rust files parsed, for the module and for the project rules; the others were read
+    /// from the dependency cache.
+    pub files_parsed: usize,
+    /// The rules applied, once per file for module rules and once for project rules.
+    pub rules_evaluated: usize,
+    /// The module rules found applicable to a file, summed over the files.
+    pub applicable_matches: usize,
+    /// Time spent reading and parsing files, including the project view of project rules.
+    pub parsing: Duration,
+    /// Time spent applying rules.
+    pub evaluation: Duration,
+    pub elapsed: Duration,
+}
+
+/// The outcome of an analysis: rule violations plus the files that could not be analyzed.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// Violations of rules with `Severity::Error`.
+    pub violations: Vec<String>,
+    /// Violations of rules with `Severity::Warning`, which don't fail a check.
+    pub warnings: Vec<String>,
+    pub diagnostics: Vec<ParseDiagnostic>,
+    /// Every rule application, respected or not, in the order they were made.
+    pub checks: Vec<RuleCheck>,
+    /// One trace per module rule, in definition order.
+    pub traces: Vec<RuleTrace>,
+    /// The files the module rules were considered for, in scan order.
+    pub scanned_files: Vec<String>,
+    /// The subjects of the module rules matching no file, when subjects must match files.
+    pub unmatched_subjects: Vec<String>,
+    pub stats: Stats,
+}
+
+/// Which files each module rule applies to, computed without applying the rules.
+#[derive(Debug, Default)]
+pub struct ExplainReport {
+    pub rules: Vec<RuleTrace>,
+    /// The files the module rules were considered for, see `RuleTrace::not_applicable`.
+    pub scanned_files: Vec<String>,
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl Display for ExplainReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for trace in &self.rules {
+            writeln!(f, "{}", trace.rule)?;
+            writeln!(f, "  applies to:")?;
+            for path in &trace.applicable {
+                writeln!(f, "    {}", path)?;
+            }
+            writeln!(f, "  does not apply to:")?;
+            for path in trace.not_applicable(&self.scanned_files) {
+                writeln!(f, "    {}", path)?;
+            }
+        }
+        for diagnostic in &self.diagnostics {
+            writeln!(
+                f,
+                "not analyzed: {} ({})",
+                diagnostic.path, diagnostic.message
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Report {
+    /// Counts the violations, e.g. `3 errors, 5 warnings across 2 rules`.
+    pub fn summary(&self) -> String {
+        let mut violated_rules: Vec<(bool, usize)> = Vec::new();
+        for check in self.checks.iter().filter(|c| c.violation.is_some()) {
+            let rule = (check.path.is_some(), check.index);
+            if !violated_rules.contains(&rule) {
+                violated_rules.push(rule);
+            }
+        }
+
+        format!(
+            "{} {}, {} {} across {} {}",
+            self.violations.len(),
+            plural(self.violations.len(), "error"),
+            self.warnings.len(),
+            plural(self.warnings.len(), "warning"),
+            violated_rules.len(),
+            plural(violated_rules.len(), "rule")
+        )
+    }
+
+    /// Renders the checks as JUnit XML: a `<testsuite>` per rule and a `<testcase>` per checked
+    /// file (`project` for project rules), failing when the rule was violated.
+    pub fn to_junit_xml(&self) -> String {
+        let mut rules: Vec<&str> = Vec::new();
+        for check in &self.checks {
+            if !rules.contains(&check.rule.as_str()) {
+                rules.push(&check.rule);
+            }
+        }
+
+        let failures = self.checks.iter().filter(|c| c.violation.is_some()).count();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"rust_arkitect\" tests=\"{}\" failures=\"{}\">\n",
+            self.checks.len(),
+            failures
+        ));
+
+        for rule in rules {
+            let checks: Vec<&RuleCheck> = self.checks.iter().filter(|c| c.rule == rule).collect();
+            let rule_failures = checks.iter().filter(|c| c.violation.is_some()).count();
+            let rule = escape_xml(&strip_ansi(rule));
+
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+                rule,
+                checks.len(),
+                rule_failures
+            ));
+            for check in checks {
+                let name = escape_xml(check.path.as_deref().unwrap_or("project"));
+                match &check.violation {
+                    None => xml.push_str(&format!(
+                        "    <testcase name=\"{}\" classname=\"{}\"/>\n",
+                        name, rule
+                    )),
+                    Some(violation) => {
+                        let violation = strip_ansi(violation);
+                        let message = violation.lines().next().unwrap_or_default();
+                        xml.push_str(&format!(
+                            "    <testcase name=\"{}\" classname=\"{}\">\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                            name,
+                            rule,
+                            escape_xml(message),
+                            escape_xml(&violation)
+                        ));
+                    }
+                }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
+    /// Renders a self-contained HTML page: a summary header, the violations grouped by rule, and a
+    /// collapsible section per component, i.e. per directory holding violating files.
+    pub fn to_html(&self) -> String {
+        let violated: Vec<&RuleCheck> = self
+            .checks
+            .iter()
+            .filter(|c| c.violation.is_some())
+            .collect();
+
+        let mut rules: Vec<&str> = Vec::new();
+        for check in &self.checks {
+            if !rules.contains(&check.rule.as_str()) {
+                rules.push(&check.rule);
+            }
+        }
+
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Rust Arkitect report</title>\n<style>\nbody { font-family: sans-serif; margin: 2em; }\ntable { border-collapse: collapse; width: 100%; margin-bottom: 1em; }\nth, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }\n.warning { color: #a60; }\n.error { color: #c00; }\n</style>\n</head>\n<body>\n<h1>Rust Arkitect report</h1>\n",
+        );
+        html.push_str(&format!(
+            "<ul class=\"summary\">\n  <li>Files scanned: {}</li>\n  <li>Rules: {}</li>\n  <li>Violations: {}</li>\n  <li>Warnings: {}</li>\n</ul>\n",
+            self.scanned_files.len(),
+            rules.len(),
+            self.violations.len(),
+            self.warnings.len()
+        ));
+
+        html.push_str("<h2>Violations by rule</h2>\n");
+        for rule in &rules {
+            let checks: Vec<&&RuleCheck> = violated.iter().filter(|c| c.rule == *rule).collect();
+            if checks.is_empty() {
+                continue;
+            }
+            html.push_str(&format!(
+                "<h3>{} <code>{}</code></h3>\n<table>\n<tr><th>Severity</th><th>File</th><th>Line</th><th>Violation</th></tr>\n",
+                escape_xml(&strip_ansi(rule)),
+                escape_xml(&checks[0].rule_name)
+            ));
+            for check in checks {
+                html.push_str(&html_row(check));
+            }
+            html.push_str("</table>\n");
+        }
+
+        let mut components: Vec<String> = Vec::new();
+        for check in &violated {
+            let component = component_of(check);
+            if !components.contains(&component) {
+                components.push(component);
+            }
+        }
+
+        html.push_str("<h2>Violations by component</h2>\n");
+        for component in components {
+            let checks: Vec<&&RuleCheck> = violated
+                .iter()
+                .filter(|c| component_of(c) == component)
+                .collect();
+            html.push_str(&format!(
+                "<details>\n<summary>{} ({})</summary>\n<table>\n<tr><th>Severity</th><th>File</th><th>Line</th><th>Violation</th></tr>\n",
+                escape_xml(&component),
+                checks.len()
+            ));
+            for check in checks {
+                html.push_str(&html_row(check));
+            }
+            html.push_str("</table>\n</details>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// The directory of the checked file, or `project` for project rules.
+fn component_of(check: &RuleCheck) -> String {
+    match check.path.as_deref() {
+        None => "project".to_string(),
+        Some(path) => std::path::Path::new(path)
+            .parent()
+            .map(|parent| parent.display().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+fn html_row(check: &RuleCheck) -> String {
+    let (class, severity) = match check.severity {
+        Severity::Error => ("error", "error"),
+        Severity::Warning => ("warning", "warning"),
+    };
+    let file = match &check.path {
+        None => "project".to_string(),
+        Some(path) => format!(
+            "<a href=\"file://{}\">{}</a>",
+            escape_xml(path),
+            escape_xml(path)
+        ),
+    };
+    let line = check.line.map(|l| l.to_string()).unwrap_or_default();
+    let violation = strip_ansi(check.violation.as_deref().unwrap_or_default());
+
+    format!(
+        "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td><pre>{}</pre></td></tr>\n",
+        class,
+        severity,
+        file,
+        line,
+        escape_xml(&violation)
+    )
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Removes the terminal color codes added by the styling helpers.
+fn strip_ansi(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        word.to_string()
+    } else {
+        format!("{}s", word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(rule: &str, path: Option<&str>, violation: Option<&str>) -> RuleCheck {
+        RuleCheck {
+            rule: rule.to_string(),
+            rule_name: "custom".to_string(),
+            index: 0,
+            severity: Severity::Error,
+            path: path.map(str::to_string),
+            line: None,
+            violation: violation.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_rule_trace_derives_the_files_it_does_not_apply_to() {
+        let scanned_files: Vec<String> = ["src/lib.rs", "src/domain.rs", "src/domain/user.rs"]
+            .iter()
+            .map(|path| path.to_string())
+            .collect();
+        let trace = RuleTrace {
+            rule: "domain may not depend on any modules".to_string(),
+            applicable: vec!["src/domain.rs".to_string()],
+        };
+
+        let not_applicable: Vec<&String> = trace.not_applicable(&scanned_files).collect();
+
+        assert_eq!(not_applicable, vec!["src/lib.rs", "src/domain/user.rs"]);
+    }
+
+    #[test]
+    fn test_to_junit_xml() {
+        let report = Report {
+            violations: vec![],
+            warnings: vec![],
+            diagnostics: vec![],
+            traces: vec![],
+            scanned_files: vec![],
+            unmatched_subjects: vec![],
+            stats: Stats::default(),
+            checks: vec![
+                check("\u{1b}[1mdomain\u{1b}[0m may not depend on any modules", Some("src/domain.rs"), None),
+                check(
+                    "\u{1b}[1mdomain\u{1b}[0m may not depend on any modules",
+                    Some("src/domain/user.rs"),
+                    Some("Forbidden dependencies to [crate::infrastructure::Db<T>] in file://src/domain/user.rs"),
+                ),
+                check("Must not have circular dependencies", None, Some("Circular dependency:\na -> b -> a")),
+            ],
+        };
+
+        let xml = report.to_junit_xml();
+        let document = roxmltree::Document::parse(&xml).expect("well-formed XML");
+
+        let root = document.root_element();
+        assert_eq!(root.attribute("tests"), Some("3"));
+        assert_eq!(root.attribute("failures"), Some("2"));
+
+        let suites: Vec<_> = root
+            .children()
+            .filter(|n| n.has_tag_name("testsuite"))
+            .collect();
+        assert_eq!(suites.len(), 2);
+        assert_eq!(
+            suites[0].attribute("name"),
+            Some("domain may not depend on any modules")
+        );
+        assert_eq!(suites[0].attribute("tests"), Some("2"));
+        assert_eq!(suites[0].attribute("failures"), Some("1"));
+
+        let testcases = document
+            .descendants()
+            .filter(|n| n.has_tag_name("testcase"))
+            .count();
+        let failures: Vec<_> = document
+            .descendants()
+            .filter(|n| n.has_tag_name("failure"))
+            .collect();
+        assert_eq!(testcases, 3);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(
+            failures[0].text(),
+            Some("Forbidden dependencies to [crate::infrastructure::Db<T>] in file://src/domain/user.rs")
+        );
+        assert_eq!(
+            failures[1].attribute("message"),
+            Some("Circular dependency:")
+        );
+    }
+
+    #[test]
+    fn test_to_html() {
+        let mut forbidden = check(
+            "\u{1b}[1mdomain\u{1b}[0m may not depend on any modules",
+            Some("src/domain/user.rs"),
+            Some("Forbidden dependencies to [crate::infrastructure::Db<T>] in file://src/domain/user.rs:4"),
+        );
+        forbidden.rule_name = "must_not_depend_on_anything".to_string();
+        forbidden.line = Some(4);
+        let mut cycles = check(
+            "Must not have circular dependencies",
+            None,
+            Some("Circular dependency:\na -> b -> a"),
+        );
+        cycles.rule_name = "must_not_have_circular_dependencies".to_string();
+
+        let report = Report {
+            violations: vec![
+                forbidden.violation.clone().unwrap(),
+                cycles.violation.clone().unwrap(),
+            ],
+            warnings: vec![],
+            diagnostics: vec![],
+            traces: vec![],
+            scanned_files: vec![
+                "src/lib.rs".to_string(),
+                "src/domain.rs".to_string(),
+                "src/domain/user.rs".to_string(),
+            ],
+            unmatched_subjects: vec![],
+            stats: Stats::default(),
+            checks: vec![
+                check(
+                    "domain may not depend on any modules",
+                    Some("src/domain.rs"),
+                    None,
+                ),
+                forbidden,
+                cycles,
+            ],
+        };
+
+        let html = report.to_html();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<li>Violations: 2</li>"));
+        assert!(html.contains("<li>Files scanned: 3</li>"));
+        assert!(html.contains("must_not_depend_on_anything"));
+        assert!(html.contains("must_not_have_circular_dependencies"));
+        assert!(html.contains(
+            "<a href=\"file://src/domain/user.rs\">src/domain/user.rs</a></td><td>4</td>"
+        ));
+        assert!(html.contains("crate::infrastructure::Db&lt;T&gt;"));
+        assert!(html.contains("<summary>src/domain (1)</summary>"));
+        assert!(!html.contains('\u{1b}'));
+    }
+}
@@ -1,13 +1,106 @@
 use crate::rust_file::RustFile;
 use crate::rust_project::RustProject;
+use crate::styling::Styling;
 use std::fmt::Display;
 
+/// The configuration of a rule, for auditing and documentation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleDescription {
+    /// Position of the rule in its rule set, e.g. `module:0` or `project:1`.
+    pub id: String,
+    /// The rule's `name`.
+    pub kind: String,
+    pub subject: Option<String>,
+    pub allowed: Vec<String>,
+    pub forbidden: Vec<String>,
+}
+
+impl RuleDescription {
+    pub fn of_kind(kind: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A violation message with the line of the file it points to, when the rule locates it.
+pub type LocatedViolation = (String, Option<usize>);
+
+/// Whether a violation of the rule fails the check or is only reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    #[default]
+    Error,
+}
+
+/// A rule checked against each file its subject matches.
 pub trait Rule: Display {
     fn apply(&self, file: &RustFile) -> Result<(), String>;
 
+    /// The description of the rule as printed by the engine, with its subject and dependencies
+    /// painted by `styling`. Defaults to the plain `Display`.
+    fn render(&self, styling: &Styling) -> String {
+        let _ = styling;
+        self.to_string()
+    }
+
+    /// Like `apply`, with the line of `file` the violation points to, e.g. where the first
+    /// forbidden dependency is used. Rules locating their violations override it.
+    fn apply_located(&self, file: &RustFile) -> Result<(), LocatedViolation> {
+        self.apply(file).map_err(|message| (message, None))
+    }
+
     fn is_applicable(&self, file: &RustFile) -> bool;
+
+    /// Stable snake case identifier of the rule, e.g. `may_depend_on`, to baseline or suppress
+    /// violations by. Unlike `Display`, it doesn't depend on the rule's configuration.
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription::of_kind(self.name())
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// Whether the rule reads the syntax tree of the files, not only their paths and
+    /// dependencies. When it doesn't, the files may be read from the dependency cache set with
+    /// `Project::with_cache_dir` instead of being parsed.
+    fn needs_syntax_tree(&self) -> bool {
+        true
+    }
 }
 
 pub trait ProjectRule: Display {
     fn apply(&self, file: &RustProject) -> Result<(), String>;
+
+    /// Like `Rule::render`, for project rules.
+    fn render(&self, styling: &Styling) -> String {
+        let _ = styling;
+        self.to_string()
+    }
+
+    /// Stable snake case identifier of the rule, e.g. `may_depend_on`, to baseline or suppress
+    /// violations by. Unlike `Display`, it doesn't depend on the rule's configuration.
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+
+    fn describe(&self) -> RuleDescription {
+        RuleDescription::of_kind(self.name())
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// Like `Rule::needs_syntax_tree`, for the files of the project.
+    fn needs_syntax_tree(&self) -> bool {
+        true
+    }
 }
@@ -1,57 +1,654 @@
-use crate::dependency_parsing::get_dependencies_in_file;
-use std::path::Path;
-use syn::File;
+use crate::dependency_parsing::{
+    get_declared_modules_in_file, get_located_and_test_dependencies_in_crate,
+    get_reexport_origins_in_file, get_reexports_in_file, item_attrs,
+};
+use crate::error::ArkitectError;
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, Expr, ExprLit, Fields, File, ImplItem, Item, ItemMod, Lit, Meta, Token, TraitItem,
+    Visibility,
+};
 use toml::Value;
 
 pub struct RustFile {
+    /// The path the file was read from, with `/` separators on every platform.
     pub path: String,
+    /// The path of the file from the root of its crate, e.g. `tests/checkout.rs`, with `/`
+    /// separators; the `path` itself when the file wasn't read from the file system.
+    pub relative_path: String,
     pub module_name: String,
     pub crate_name: String,
     pub logical_path: String,
     pub dependencies: Vec<String>,
+    /// The line each of the `dependencies` first appears on.
+    pub dependency_lines: HashMap<String, usize>,
+    /// Paths re-exported with `pub use`; they are also listed in `dependencies`.
+    pub reexports: Vec<String>,
+    /// See `reexport_origins`, computed on first use.
+    pub(crate) reexport_origins: OnceLock<HashMap<String, String>>,
+    /// Whether the whole file is test code: it lives under `tests/` or is `#![cfg(test)]`.
+    pub is_test: bool,
+    /// The `dependencies` only used inside `#[cfg(test)]` items.
+    pub test_dependencies: Vec<String>,
+    /// The names of the rules suppressed by `// arkitect:allow(rule_name)` comments at the top of
+    /// the file.
+    pub suppressed_rules: Vec<String>,
+    /// The modules declared in the file with `mod`, like `model` for `mod model;`: paths such as
+    /// `model::Order` start with them.
+    pub local_modules: Vec<String>,
+    /// The crates declared as dependencies in the `Cargo.toml` of the file's crate, so that bare
+    /// paths like `serde_json::to_string(..)` count as dependencies. Empty when the file wasn't
+    /// read from the file system.
+    pub external_crates: Vec<String>,
     pub ast: File,
 }
 
 impl RustFile {
     pub fn from_file_system(path: &str) -> Self {
-        let content = match std::fs::read_to_string(path) {
-            Ok(content) => content,
-            Err(e) => panic!("Failed to read file file://{}: {}", path, e),
-        };
+        Self::try_from_file_system(path).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `from_file_system`, returning an error when the file can't be read or parsed.
+    pub fn try_from_file_system(path: &str) -> Result<Self, ArkitectError> {
+        Self::try_from_file_system_cached(path, &mut CrateNameCache::default())
+    }
+
+    /// Like `try_from_file_system`, reusing the crate names already read into `cache`.
+    pub fn try_from_file_system_cached(
+        path: &str,
+        cache: &mut CrateNameCache,
+    ) -> Result<Self, ArkitectError> {
+        Self::try_from_file_system_with_features(path, cache, None)
+    }
+
+    /// Like `try_from_file_system_cached`, reading the file as compiled with only `features`
+    /// enabled when set: items behind a `#[cfg]` on features that evaluates to false, directly or
+    /// through `#[cfg_attr]`, are left out. Predicates other than `feature` are unknown and never
+    /// exclude an item.
+    pub fn try_from_file_system_with_features(
+        path: &str,
+        cache: &mut CrateNameCache,
+        features: Option<&[String]>,
+    ) -> Result<Self, ArkitectError> {
+        #[cfg(test)]
+        FILES_READ.with(|count| count.set(count.get() + 1));
+
+        let content = std::fs::read_to_string(path).map_err(|e| ArkitectError::Io {
+            path: with_forward_slashes(path),
+            reason: e.to_string(),
+        })?;
+
+        let (logical_path, relative_path) =
+            parse_module_logical_path_cached(path, cache).map_err(|reason| {
+                ArkitectError::ModulePath {
+                    path: with_forward_slashes(path),
+                    reason,
+                }
+            })?;
 
-        let binding = parse_module_logical_path(path)
-            .expect(&format!("Failed to compute module path {path}"));
-        let logical_path = binding.as_str();
+        let external_crates = cache.external_crates_of(path);
 
-        Self::from_content(path, logical_path, &content)
+        Self::try_from_content_in_crate(
+            path,
+            &logical_path,
+            &content,
+            CrateLocation {
+                relative_path,
+                external_crates,
+                features,
+            },
+        )
     }
 
     pub fn from_content(path: &str, logical_path: &str, content: &str) -> Self {
-        let ast = match syn::parse_str(&content) {
-            Ok(ast) => ast,
-            Err(e) => panic!("Failed to parse file file://{}: {}", path, e),
+        Self::try_from_content(path, logical_path, content).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `from_content`, returning an error when the content is not valid Rust.
+    pub fn try_from_content(
+        path: &str,
+        logical_path: &str,
+        content: &str,
+    ) -> Result<Self, ArkitectError> {
+        Self::try_from_content_in_crate(path, logical_path, content, CrateLocation::unknown(path))
+    }
+
+    fn try_from_content_in_crate(
+        path: &str,
+        logical_path: &str,
+        content: &str,
+        location: CrateLocation,
+    ) -> Result<Self, ArkitectError> {
+        let ast = syn::parse_str(content).map_err(|e| ArkitectError::Parse {
+            path: with_forward_slashes(path),
+            line: e.span().start().line,
+            reason: e.to_string(),
+        })?;
+        let ast = match location.features {
+            Some(features) => without_inactive_items(ast, features),
+            None => ast,
         };
 
-        Self::from_ast(path, logical_path, ast)
+        Ok(Self {
+            suppressed_rules: suppressed_rules(content),
+            ..Self::from_ast_in_crate(path, logical_path, ast, location)
+        })
     }
 
     pub fn from_ast(path: &str, logical_path: &str, ast: File) -> Self {
+        Self::from_ast_in_crate(path, logical_path, ast, CrateLocation::unknown(path))
+    }
+
+    fn from_ast_in_crate(
+        path: &str,
+        logical_path: &str,
+        ast: File,
+        location: CrateLocation,
+    ) -> Self {
+        let CrateLocation {
+            relative_path,
+            external_crates,
+            ..
+        } = location;
         let module_name = logical_path.split("::").last().unwrap_or("").to_string();
         let crate_name = logical_path.split("::").next().unwrap_or("").to_string();
-        let dependencies = get_dependencies_in_file(&logical_path, &ast);
+        let (located_dependencies, test_dependencies) = get_located_and_test_dependencies_in_crate(
+            logical_path,
+            &ast,
+            &external_crates,
+            |attrs| attrs.iter().any(is_cfg_test),
+        );
+        let dependencies: Vec<String> = located_dependencies
+            .iter()
+            .map(|(dependency, _)| dependency.clone())
+            .collect();
+        let dependency_lines = located_dependencies.into_iter().collect();
+        let reexports = get_reexports_in_file(logical_path, &ast);
+        let is_test = is_under_tests_directory(&relative_path) || ast.attrs.iter().any(is_cfg_test);
 
         RustFile {
-            path: path.to_string(),
+            path: with_forward_slashes(path),
+            relative_path,
             logical_path: logical_path.to_string(),
             module_name,
             crate_name,
             dependencies,
+            dependency_lines,
+            reexports,
+            reexport_origins: OnceLock::new(),
+            is_test,
+            test_dependencies,
+            suppressed_rules: vec![],
+            local_modules: get_declared_modules_in_file(&ast),
+            external_crates,
             ast,
         }
     }
+
+    /// The dependencies of the file, leaving out test code when `exclude_test_code` is set.
+    pub fn dependencies(&self, exclude_test_code: bool) -> Vec<&String> {
+        if exclude_test_code && self.is_test {
+            return vec![];
+        }
+
+        let test_dependencies: HashSet<&String> = if exclude_test_code {
+            self.test_dependencies.iter().collect()
+        } else {
+            HashSet::new()
+        };
+        self.dependencies
+            .iter()
+            .filter(|dependency| !test_dependencies.contains(dependency))
+            .collect()
+    }
+
+    /// The paths exposed by `pub use` items, e.g. `my_crate::api::Thing`, mapped to the paths
+    /// they re-export. Only the dependency graph following re-exports needs them, so they are
+    /// computed from the syntax tree the first time they are asked for.
+    pub fn reexport_origins(&self) -> &HashMap<String, String> {
+        self.reexport_origins
+            .get_or_init(|| get_reexport_origins_in_file(&self.logical_path, &self.ast))
+    }
+
+    /// Whether `name` is known inside the file without referring to a crate: a module it declares
+    /// or a name one of its `use` items imports, like `model` for `use crate::domain::model;`.
+    pub fn declares_local_name(&self, name: &str) -> bool {
+        self.local_modules.iter().any(|module| module == name)
+            || self.dependencies.iter().any(|dependency| {
+                dependency
+                    .rsplit_once("::")
+                    .is_some_and(|(parent, last)| !parent.is_empty() && last == name)
+            })
+    }
+
+    /// The line `dependency` first appears on, when it is known.
+    pub fn line_of(&self, dependency: &str) -> Option<usize> {
+        self.dependency_lines.get(dependency).copied()
+    }
+
+    /// Where `dependency` first appears, as `path:line`, or just the path when it's unknown.
+    pub fn location_of(&self, dependency: &str) -> String {
+        match self.dependency_lines.get(dependency) {
+            Some(line) => format!("{}:{}", self.path, line),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// The rules listed by `// arkitect:allow(rule_name, ..)` directives in the comments opening
+/// `content`, before any code.
+fn suppressed_rules(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with("//"))
+        .filter_map(|line| {
+            line.trim_start_matches('/')
+                .trim_start_matches('!')
+                .trim()
+                .strip_prefix("arkitect:allow(")?
+                .strip_suffix(')')
+        })
+        .flat_map(|rules| rules.split(','))
+        .map(|rule| rule.trim().to_string())
+        .filter(|rule| !rule.is_empty())
+        .collect()
 }
 
+/// Whether a path from the root of a crate, with `/` or `\\` separators, is in the crate's
+/// `tests` directory, the one of its integration tests.
+pub fn is_under_tests_directory(relative_path: &str) -> bool {
+    relative_path
+        .trim_start_matches("./")
+        .split(['/', '\\'])
+        .next()
+        == Some("tests")
+}
+
+/// Whether the attribute is a `#[cfg]` that only holds in test builds, like `#[cfg(test)]` or
+/// `#[cfg(all(test, feature = "x"))]`; `#[cfg(any(test, feature = "x"))]` may hold outside of
+/// them and is not.
+pub fn is_cfg_test(attr: &Attribute) -> bool {
+    if !attr.path().is_ident("cfg") {
+        return false;
+    }
+
+    let outside_of_tests = CfgContext {
+        features: None,
+        test: Some(false),
+    };
+    attr.parse_args::<Meta>()
+        .is_ok_and(|predicate| evaluate_cfg(&predicate, outside_of_tests) == Some(false))
+}
+
+/// The AST without the items disabled by the `features`, at any nesting level.
+fn without_inactive_items(mut ast: File, features: &[String]) -> File {
+    fn strip(items: &mut Vec<Item>, features: &[String]) {
+        items.retain(|item| is_active(item_attrs(item), features));
+        for item in items {
+            if let Item::Mod(ItemMod {
+                content: Some((_, items)),
+                ..
+            }) = item
+            {
+                strip(items, features);
+            }
+        }
+    }
+
+    if is_active(&ast.attrs, features) {
+        strip(&mut ast.items, features);
+    } else {
+        ast.items.clear();
+    }
+
+    ast
+}
+
+/// Whether no `#[cfg]` among `attrs`, including those applied by a `#[cfg_attr]` whose predicate
+/// holds, is known to be false with the `features` enabled.
+fn is_active(attrs: &[Attribute], features: &[String]) -> bool {
+    let context = CfgContext {
+        features: Some(features),
+        test: None,
+    };
+    let metas = |attr: &Attribute| {
+        attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            .map(|metas| metas.into_iter().collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+
+    attrs.iter().all(|attr| {
+        if attr.path().is_ident("cfg") {
+            match metas(attr).first() {
+                Some(predicate) => evaluate_cfg(predicate, context) != Some(false),
+                None => true,
+            }
+        } else if attr.path().is_ident("cfg_attr") {
+            let metas = metas(attr);
+            let Some((predicate, applied)) = metas.split_first() else {
+                return true;
+            };
+            evaluate_cfg(predicate, context) != Some(true)
+                || applied.iter().all(|meta| match meta {
+                    Meta::List(cfg) if cfg.path.is_ident("cfg") => {
+                        cfg.parse_args::<Meta>().map_or(true, |predicate| {
+                            evaluate_cfg(&predicate, context) != Some(false)
+                        })
+                    }
+                    _ => true,
+                })
+        } else {
+            true
+        }
+    })
+}
+
+/// What is known when evaluating a cfg predicate: the enabled features and whether it's a test
+/// build, each `None` when unknown.
+#[derive(Clone, Copy)]
+struct CfgContext<'a> {
+    features: Option<&'a [String]>,
+    test: Option<bool>,
+}
+
+/// Evaluates a cfg predicate in `context`, `None` when it depends on anything unknown, such as
+/// the target.
+fn evaluate_cfg(predicate: &Meta, context: CfgContext) -> Option<bool> {
+    match predicate {
+        Meta::Path(option) if option.is_ident("test") => context.test,
+        Meta::NameValue(option) if option.path.is_ident("feature") => match &option.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(feature),
+                ..
+            }) => Some(context.features?.contains(&feature.value())),
+            _ => None,
+        },
+        Meta::List(list) => {
+            let operands: Vec<Option<bool>> = list
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()?
+                .iter()
+                .map(|operand| evaluate_cfg(operand, context))
+                .collect();
+            if list.path.is_ident("not") {
+                operands.first().copied().flatten().map(|value| !value)
+            } else if list.path.is_ident("all") {
+                if operands.contains(&Some(false)) {
+                    Some(false)
+                } else {
+                    operands.iter().all(Option::is_some).then_some(true)
+                }
+            } else if list.path.is_ident("any") {
+                if operands.contains(&Some(true)) {
+                    Some(true)
+                } else {
+                    operands.iter().all(Option::is_some).then_some(false)
+                }
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Files read from disk by the current thread, to check how often a project is traversed.
+    pub(crate) static FILES_READ: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// A `pub` item of a file, identified by its full path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicItem {
+    pub path: String,
+    pub signature: String,
+}
+
+impl RustFile {
+    /// Lists the `pub` items of the file (including `pub` methods and `pub mod` contents)
+    /// with a textual signature, bodies excluded.
+    pub fn public_api(&self) -> Vec<PublicItem> {
+        let mut items = Vec::new();
+        collect_public_items(&self.ast.items, &self.logical_path, &mut items);
+        items
+    }
+}
+
+fn collect_public_items(items: &[Item], module: &str, public_items: &mut Vec<PublicItem>) {
+    let mut push = |name: String, signature: String| {
+        public_items.push(PublicItem {
+            path: format!("{}::{}", module, name),
+            signature,
+        })
+    };
+    let mut nested_modules = Vec::new();
+
+    for item in items {
+        match item {
+            Item::Fn(item) if is_public(&item.vis) => {
+                push(item.sig.ident.to_string(), tokens(&item.sig));
+            }
+            Item::Struct(item) if is_public(&item.vis) => {
+                let fields: Vec<String> = match &item.fields {
+                    Fields::Named(fields) => fields
+                        .named
+                        .iter()
+                        .filter(|field| is_public(&field.vis))
+                        .map(|field| {
+                            let name = field.ident.as_ref().map(|i| i.to_string());
+                            format!("{}: {}", name.unwrap_or_default(), tokens(&field.ty))
+                        })
+                        .collect(),
+                    Fields::Unnamed(fields) => fields
+                        .unnamed
+                        .iter()
+                        .filter(|field| is_public(&field.vis))
+                        .map(|field| tokens(&field.ty))
+                        .collect(),
+                    Fields::Unit => vec![],
+                };
+                push(
+                    item.ident.to_string(),
+                    format!(
+                        "struct {}{} {{ {} }}",
+                        item.ident,
+                        tokens(&item.generics),
+                        fields.join(", ")
+                    ),
+                );
+            }
+            Item::Enum(item) if is_public(&item.vis) => {
+                let mut item = item.clone();
+                item.attrs.clear();
+                push(item.ident.to_string(), tokens(&item));
+            }
+            Item::Trait(item) if is_public(&item.vis) => {
+                let methods: Vec<String> = item
+                    .items
+                    .iter()
+                    .filter_map(|trait_item| match trait_item {
+                        TraitItem::Fn(method) => Some(tokens(&method.sig)),
+                        _ => None,
+                    })
+                    .collect();
+                push(
+                    item.ident.to_string(),
+                    format!(
+                        "trait {}{} {{ {} }}",
+                        item.ident,
+                        tokens(&item.generics),
+                        methods.join("; ")
+                    ),
+                );
+            }
+            Item::Const(item) if is_public(&item.vis) => {
+                push(
+                    item.ident.to_string(),
+                    format!("const {}: {}", item.ident, tokens(&item.ty)),
+                );
+            }
+            Item::Static(item) if is_public(&item.vis) => {
+                push(
+                    item.ident.to_string(),
+                    format!("static {}: {}", item.ident, tokens(&item.ty)),
+                );
+            }
+            Item::Type(item) if is_public(&item.vis) => {
+                let mut item = item.clone();
+                item.attrs.clear();
+                push(item.ident.to_string(), tokens(&item));
+            }
+            Item::Impl(item) if item.trait_.is_none() => {
+                let self_ty = tokens(&item.self_ty);
+                for impl_item in &item.items {
+                    if let ImplItem::Fn(method) = impl_item {
+                        if is_public(&method.vis) {
+                            push(
+                                format!("{}::{}", self_ty, method.sig.ident),
+                                tokens(&method.sig),
+                            );
+                        }
+                    }
+                }
+            }
+            Item::Mod(item) if is_public(&item.vis) => {
+                if let Some((_, items)) = &item.content {
+                    nested_modules.push((format!("{}::{}", module, item.ident), items));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (module, items) in nested_modules {
+        collect_public_items(items, &module, public_items);
+    }
+}
+
+fn is_public(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+fn tokens(node: &impl ToTokens) -> String {
+    node.to_token_stream().to_string()
+}
+
+/// Crate names and dependencies read from `Cargo.toml`, keyed by crate root, so that each
+/// manifest is read and parsed once per run instead of once per file.
+#[derive(Debug, Default)]
+pub struct CrateNameCache {
+    crate_names: HashMap<PathBuf, String>,
+    external_crates: HashMap<PathBuf, Vec<String>>,
+    manifests_parsed: usize,
+}
+
+impl CrateNameCache {
+    /// Number of `Cargo.toml` files read so far.
+    pub fn manifests_parsed(&self) -> usize {
+        self.manifests_parsed
+    }
+
+    fn crate_name(&mut self, crate_root: &Path) -> Result<String, String> {
+        if let Some(crate_name) = self.crate_names.get(crate_root) {
+            return Ok(crate_name.clone());
+        }
+
+        let (crate_name, external_crates) = read_manifest(crate_root)?;
+        self.manifests_parsed += 1;
+        self.crate_names
+            .insert(crate_root.to_path_buf(), crate_name.clone());
+        self.external_crates
+            .insert(crate_root.to_path_buf(), external_crates);
+
+        Ok(crate_name)
+    }
+
+    /// The dependencies of the crate of the file at `file_path`, once its crate name was read.
+    fn external_crates_of(&self, file_path: &str) -> Vec<String> {
+        crate_root_of(Path::new(file_path))
+            .and_then(|crate_root| self.external_crates.get(crate_root))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// The `Cargo.toml` of the crate of the file at `path`, if any.
+pub(crate) fn manifest_of(path: &str) -> Option<PathBuf> {
+    crate_root_of(Path::new(path)).map(|crate_root| crate_root.join("Cargo.toml"))
+}
+
+/// The closest directory containing `path` with a `Cargo.toml`.
+fn crate_root_of(path: &Path) -> Option<&Path> {
+    path.ancestors()
+        .find(|ancestor| ancestor.join("Cargo.toml").exists())
+}
+
+/// The name modules of the crate are rooted at: `[lib].name` when set, else `package.name`,
+/// with dashes turned into underscores as Cargo does. Along with it, the names the crate's
+/// dependencies, dev and build ones included, are referred to by in code.
+fn read_manifest(crate_root: &Path) -> Result<(String, Vec<String>), String> {
+    let cargo_toml_path = crate_root.join("Cargo.toml");
+    let cargo_toml_content = std::fs::read_to_string(&cargo_toml_path).map_err(|_| {
+        format!(
+            "Failed to read Cargo.toml in '{}'",
+            cargo_toml_path.display()
+        )
+    })?;
+
+    toml::from_str::<Value>(&cargo_toml_content)
+        .and_then(|parsed| {
+            let crate_name = parsed
+                .get("lib")
+                .and_then(|lib| lib.get("name"))
+                .or_else(|| parsed.get("package").and_then(|pkg| pkg.get("name")))
+                .and_then(|name| name.as_str())
+                .map(|name| name.replace('-', "_"))
+                .ok_or_else(|| serde::de::Error::custom("Missing 'package.name' in Cargo.toml"))?;
+
+            Ok((crate_name, dependency_names(&parsed)))
+        })
+        .map_err(|err| format!("Failed to parse crate name: {}", err))
+}
+
+/// The keys of the dependency tables of a manifest, platform-specific ones included.
+fn dependency_names(manifest: &Value) -> Vec<String> {
+    let tables = ["dependencies", "dev-dependencies", "build-dependencies"];
+    let targets = manifest
+        .get("target")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values());
+
+    let mut names: Vec<String> = std::iter::once(manifest)
+        .chain(targets)
+        .flat_map(|section| tables.iter().filter_map(|table| section.get(table)))
+        .filter_map(Value::as_table)
+        .flat_map(|dependencies| dependencies.keys())
+        .map(|name| name.replace('-', "_"))
+        .collect();
+    names.sort();
+    names.dedup();
+
+    names
+}
+
+#[cfg(test)]
 fn parse_module_logical_path(file_path: &str) -> Result<String, String> {
+    parse_module_logical_path_cached(file_path, &mut CrateNameCache::default())
+        .map(|(logical_path, _)| logical_path)
+}
+
+/// The module defined by the file at `file_path`, along with its path from the crate root.
+fn parse_module_logical_path_cached(
+    file_path: &str,
+    cache: &mut CrateNameCache,
+) -> Result<(String, String), String> {
     let path = Path::new(file_path);
 
     if path.is_dir() {
@@ -70,28 +667,10 @@ fn parse_module_logical_path(file_path: &str) -> Result<String, String> {
         ));
     }
 
-    let crate_root = path
-        .ancestors()
-        .find(|ancestor| ancestor.join("Cargo.toml").exists())
+    let crate_root = crate_root_of(path)
         .ok_or_else(|| format!("File is not part of a Rust crate: {}", file_path))?;
 
-    let cargo_toml_path = crate_root.join("Cargo.toml");
-    let cargo_toml_content = std::fs::read_to_string(&cargo_toml_path).map_err(|_| {
-        format!(
-            "Failed to read Cargo.toml in '{}'",
-            cargo_toml_path.display()
-        )
-    })?;
-    let crate_name = toml::from_str::<Value>(&cargo_toml_content)
-        .and_then(|parsed| {
-            parsed
-                .get("package")
-                .and_then(|pkg| pkg.get("name"))
-                .and_then(|name| name.as_str())
-                .map(str::to_string)
-                .ok_or_else(|| serde::de::Error::custom("Missing 'package.name' in Cargo.toml"))
-        })
-        .map_err(|err| format!("Failed to parse crate name: {}", err))?;
+    let crate_name = cache.crate_name(crate_root)?;
 
     let relative_path = path.strip_prefix(crate_root).map_err(|_| {
         format!(
@@ -101,42 +680,80 @@ fn parse_module_logical_path(file_path: &str) -> Result<String, String> {
         )
     })?;
 
-    let mut comps = relative_path.components().peekable();
+    let relative_path = with_forward_slashes(&relative_path.to_string_lossy());
+    let logical_path = logical_path_in_crate(&crate_name, &relative_path)
+        .ok_or_else(|| format!("Failed to determine module path for '{}'", file_path))?;
 
-    if comps.clone().any(|c| c.as_os_str() == "src") {
-        while let Some(c) = comps.next() {
-            if c.as_os_str() == "src" {
-                break;
-            }
-        }
-    }
+    Ok((logical_path, relative_path))
+}
 
-    let mut parts = vec![];
-    for comp in comps {
-        let s = comp.as_os_str().to_str().unwrap_or_default();
-        parts.push(s.to_string());
-    }
+/// The module defined by the file at `relative_path` from the root of the crate, split on both
+/// `/` and `\\` so that Windows paths give the same module. `None` for an empty path.
+fn logical_path_in_crate(crate_name: &str, relative_path: &str) -> Option<String> {
+    let components: Vec<&str> = relative_path
+        .split(['/', '\\'])
+        .filter(|component| !component.is_empty() && *component != ".")
+        .collect();
+    let after_src = match components.iter().position(|&component| component == "src") {
+        Some(src) => &components[src + 1..],
+        None => &components[..],
+    };
 
+    let mut parts: Vec<&str> = after_src.to_vec();
     if let Some(last) = parts.last_mut() {
-        if last.ends_with(".rs") {
-            *last = last.trim_end_matches(".rs").to_string();
-        }
+        *last = last.strip_suffix(".rs").unwrap_or(last);
     }
 
     if parts.is_empty() {
-        return Err(format!(
-            "Failed to determine module path for '{}'",
-            file_path
-        ));
+        return None;
+    }
+
+    // `foo/mod.rs` and `bin/foo/main.rs` define `foo`, `src/lib.rs` and `src/main.rs` the crate
+    // root. Other `main.rs` files are plain modules named `main`.
+    let is_target_main = matches!(
+        parts.as_slice(),
+        [target_dir, _, "main"] if ["bin", "examples", "tests", "benches"].contains(target_dir)
+    );
+    if parts.len() > 1 && (parts.last() == Some(&"mod") || is_target_main) {
+        parts.pop();
+    } else if parts.len() == 1 && (parts[0] == "lib" || parts[0] == "main") {
+        return Some(crate_name.to_string());
+    }
+
+    Some(format!("{}::{}", crate_name, parts.join("::")))
+}
+
+/// Where a file is within its crate, as far as it's known, and the features it's compiled with.
+struct CrateLocation<'a> {
+    relative_path: String,
+    external_crates: Vec<String>,
+    features: Option<&'a [String]>,
+}
+
+impl CrateLocation<'_> {
+    /// A file that wasn't read from the file system: its `path` stands for the relative one.
+    fn unknown(path: &str) -> Self {
+        Self {
+            relative_path: with_forward_slashes(path),
+            external_crates: vec![],
+            features: None,
+        }
     }
+}
 
-    let module_path = parts.join("::");
-    Ok(format!("{}::{}", crate_name, module_path))
+/// `path` with `/` separators, as used in `file://` links on every platform.
+fn with_forward_slashes(path: &str) -> String {
+    path.replace('\\', "/")
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::rust_file::{parse_module_logical_path, RustFile};
+    use crate::error::ArkitectError;
+    use crate::fixtures::temp_project;
+    use crate::rust_file::{
+        logical_path_in_crate, parse_module_logical_path, CrateLocation, CrateNameCache,
+        PublicItem, RustFile,
+    };
 
     #[test]
     fn test_rust_file_from_path() {
@@ -148,6 +765,223 @@ mod tests {
         assert_eq!(file.module_name, "rust_file".to_string());
     }
 
+    #[test]
+    fn test_try_from_content_with_invalid_syntax() {
+        let result = RustFile::try_from_content("src/broken.rs", "my_crate::broken", "fn {");
+
+        assert!(matches!(
+            result.err(),
+            Some(ArkitectError::Parse { path, line: 1, .. }) if path == "src/broken.rs"
+        ));
+    }
+
+    #[test]
+    fn test_feature_gated_uses_are_excluded_unless_the_feature_is_enabled() {
+        let dependencies = |features: Option<&[String]>| {
+            let location = CrateLocation {
+                features,
+                ..CrateLocation::unknown("src/lib.rs")
+            };
+            RustFile::try_from_content_in_crate(
+                "src/lib.rs",
+                "my_crate",
+                r#"
+                use crate::core::Engine;
+                #[cfg(feature = "extra")]
+                use crate::extra::Thing;
+                #[cfg(all(unix, not(feature = "extra")))]
+                use crate::fallback::Thing;
+                #[cfg_attr(feature = "legacy", cfg(feature = "extra"))]
+                use crate::legacy::Shim;
+                #[cfg(target_os = "linux")]
+                use crate::linux::Epoll;
+                "#,
+                location,
+            )
+            .unwrap()
+            .dependencies
+        };
+
+        assert_eq!(
+            dependencies(Some(&[])),
+            vec![
+                "my_crate::core::Engine",
+                "my_crate::fallback::Thing",
+                "my_crate::legacy::Shim",
+                "my_crate::linux::Epoll"
+            ]
+        );
+        assert_eq!(
+            dependencies(Some(&["extra".to_string()])),
+            vec![
+                "my_crate::core::Engine",
+                "my_crate::extra::Thing",
+                "my_crate::legacy::Shim",
+                "my_crate::linux::Epoll"
+            ]
+        );
+        assert_eq!(
+            dependencies(Some(&["legacy".to_string()])),
+            vec![
+                "my_crate::core::Engine",
+                "my_crate::fallback::Thing",
+                "my_crate::linux::Epoll"
+            ]
+        );
+        assert_eq!(dependencies(None).len(), 5);
+    }
+
+    #[test]
+    fn test_suppression_directives_at_the_top_of_the_file() {
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            "//! Orders.\n// arkitect:allow(may_depend_on)\n\n//arkitect:allow(must_not_depend_on, custom)\nuse crate::infrastructure::Db;\n// arkitect:allow(must_have_module_doc)\n",
+        );
+
+        assert_eq!(
+            file.suppressed_rules,
+            vec!["may_depend_on", "must_not_depend_on", "custom"]
+        );
+    }
+
+    #[test]
+    fn test_file_under_tests_is_test_code() {
+        let file = RustFile::from_content(
+            "tests/architecture.rs",
+            "my_crate::tests::architecture",
+            "use my_crate::application::Service;",
+        );
+
+        assert!(file.is_test);
+        assert!(file.dependencies(true).is_empty());
+        assert_eq!(file.dependencies(false).len(), 1);
+    }
+
+    #[test]
+    fn test_only_the_tests_directory_of_the_crate_is_test_code() {
+        let file = RustFile::from_content(
+            "src/tests/fixtures.rs",
+            "my_crate::tests::fixtures",
+            "use my_crate::application::Service;",
+        );
+
+        assert!(!file.is_test);
+    }
+
+    #[test]
+    fn test_cfg_test_module_dependencies_are_test_code() {
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            r#"
+            use crate::shared::Id;
+
+            #[cfg(test)]
+            mod tests {
+                use crate::application::Service;
+                use crate::shared::Id;
+            }
+            "#,
+        );
+
+        assert!(!file.is_test);
+        assert_eq!(
+            file.test_dependencies,
+            vec!["my_crate::application::Service"]
+        );
+        assert_eq!(file.dependencies(true), vec!["my_crate::shared::Id"]);
+        assert_eq!(file.dependencies(false).len(), 2);
+    }
+
+    #[test]
+    fn test_only_predicates_requiring_test_builds_are_test_code() {
+        let file = RustFile::from_content(
+            "src/domain.rs",
+            "my_crate::domain",
+            r#"
+            #[cfg(all(not(test)))]
+            use crate::shared::Id;
+            #[cfg(any(test, feature = "fixtures"))]
+            use crate::shared::Fixture;
+            #[cfg(all(test, feature = "fixtures"))]
+            use crate::application::Service;
+            #[cfg(not(not(test)))]
+            use crate::application::Handler;
+            "#,
+        );
+
+        assert_eq!(
+            file.test_dependencies,
+            vec![
+                "my_crate::application::Service",
+                "my_crate::application::Handler"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_public_api() {
+        let file = RustFile::from_content(
+            "src/api.rs",
+            "my_crate::api",
+            r#"
+            pub fn get_user(id: u32) -> Option<User> { todo!() }
+            fn private_helper() {}
+
+            pub struct User {
+                pub name: String,
+                password: String,
+            }
+
+            impl User {
+                pub fn new(name: String) -> Self { todo!() }
+                fn hash(&self) {}
+            }
+
+            pub mod admin {
+                pub const MAX_ADMINS: usize = 3;
+            }
+            "#,
+        );
+
+        let api: Vec<PublicItem> = file.public_api();
+        let paths: Vec<&str> = api.iter().map(|item| item.path.as_str()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "my_crate::api::get_user",
+                "my_crate::api::User",
+                "my_crate::api::User::new",
+                "my_crate::api::admin::MAX_ADMINS",
+            ]
+        );
+        assert_eq!(
+            api[0].signature,
+            "fn get_user (id : u32) -> Option < User >"
+        );
+        assert_eq!(api[1].signature, "struct User { name: String }");
+    }
+
+    #[test]
+    fn test_manifest_is_parsed_once_per_crate() {
+        let mut cache = CrateNameCache::default();
+
+        for file in ["domain", "application", "infrastructure", "lib"] {
+            let path = format!("./examples/workspace_project/conversion/src/{}.rs", file);
+            RustFile::try_from_file_system_cached(&path, &mut cache).unwrap();
+        }
+        assert_eq!(cache.manifests_parsed(), 1);
+
+        RustFile::try_from_file_system_cached(
+            "./examples/workspace_project/contracts/src/lib.rs",
+            &mut cache,
+        )
+        .unwrap();
+        assert_eq!(cache.manifests_parsed(), 2);
+    }
+
     #[test]
     fn test_get_module() {
         let module =
@@ -157,6 +991,106 @@ mod tests {
         assert_eq!(module, "conversion::application")
     }
 
+    #[test]
+    fn test_get_module_of_crate_roots_and_mod_files() {
+        let root = temp_project(&[
+            ("Cargo.toml", "[package]\nname = \"roots\"\n"),
+            ("src/lib.rs", ""),
+            ("src/main.rs", ""),
+            ("src/domain/mod.rs", ""),
+            ("src/domain/user/mod.rs", ""),
+            ("src/bin/tool/main.rs", ""),
+        ]);
+
+        let module =
+            |path: &str| parse_module_logical_path(root.path().join(path).to_str().unwrap());
+
+        assert_eq!(module("src/lib.rs"), Ok("roots".to_string()));
+        assert_eq!(module("src/main.rs"), Ok("roots".to_string()));
+        assert_eq!(module("src/domain/mod.rs"), Ok("roots::domain".to_string()));
+        assert_eq!(
+            module("src/domain/user/mod.rs"),
+            Ok("roots::domain::user".to_string())
+        );
+        assert_eq!(
+            module("src/bin/tool/main.rs"),
+            Ok("roots::bin::tool".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bare_paths_to_manifest_dependencies() {
+        let root = temp_project(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"bare\"\n\n[dependencies]\nserde-json = \"1\"\n\n\
+                 [target.'cfg(unix)'.dev-dependencies]\nlibc = \"0.2\"\n",
+            ),
+            (
+                "src/lib.rs",
+                "pub fn run() {\n    serde_json::to_string(&1);\n    libc::getpid();\n}\n",
+            ),
+        ]);
+
+        let file = RustFile::from_file_system(root.path().join("src/lib.rs").to_str().unwrap());
+
+        assert_eq!(file.external_crates, vec!["libc", "serde_json"]);
+        assert_eq!(
+            file.dependencies,
+            vec!["serde_json::to_string", "libc::getpid"]
+        );
+    }
+
+    #[test]
+    fn test_get_module_with_either_separator() {
+        for relative_path in ["src/domain/orders/mod.rs", "src\\domain\\orders\\mod.rs"] {
+            assert_eq!(
+                logical_path_in_crate("shop", relative_path),
+                Some("shop::domain::orders".to_string())
+            );
+        }
+        assert_eq!(
+            logical_path_in_crate("shop", "src\\lib.rs"),
+            Some("shop".to_string())
+        );
+        assert_eq!(
+            logical_path_in_crate("shop", "tests\\checkout.rs"),
+            Some("shop::tests::checkout".to_string())
+        );
+    }
+
+    #[test]
+    fn test_only_target_main_files_define_their_directory() {
+        assert_eq!(
+            logical_path_in_crate("shop", "src/bin/import/main.rs"),
+            Some("shop::bin::import".to_string())
+        );
+        assert_eq!(
+            logical_path_in_crate("shop", "examples/demo/main.rs"),
+            Some("shop::examples::demo".to_string())
+        );
+        assert_eq!(
+            logical_path_in_crate("shop", "src/cli/main.rs"),
+            Some("shop::cli::main".to_string())
+        );
+    }
+
+    #[test]
+    fn test_windows_paths_give_forward_slash_links() {
+        let file = RustFile::from_content(
+            "tests\\checkout.rs",
+            "shop::tests::checkout",
+            "use shop::domain::Order;\n",
+        );
+
+        assert_eq!(file.path, "tests/checkout.rs");
+        assert_eq!(
+            file.location_of("shop::domain::Order"),
+            "tests/checkout.rs:1"
+        );
+        assert!(file.is_test);
+    }
+
     #[test]
     fn test_get_module_on_a_random_file() {
         let module = parse_module_logical_path("./examples/workspace_project/assets/file_1.txt");
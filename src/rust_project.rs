@@ -1,17 +1,501 @@
-use std::collections::{HashMap, HashSet};
+use log::warn;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use toml::Value;
 use walkdir::WalkDir;
 
-use crate::rust_file::RustFile;
+use crate::dependency_cache::DependencyCache;
+use crate::rust_file::{CrateNameCache, RustFile};
+
+/// Directory names that are never walked while scanning a project, at any depth.
+pub const DEFAULT_IGNORED_DIRECTORIES: [&str; 2] = ["target", ".git"];
+
+/// Settings read from `[workspace.metadata.arkitect]` or `[package.metadata.arkitect]`:
+///
+/// ```toml
+/// [package.metadata.arkitect]
+/// source_dirs = ["generated"]
+/// ignored_directories = ["target", "fixtures"]
+/// baseline = 3
+/// include_targets = true
+/// exclude_members = ["legacy"]
+/// features = ["extra"]
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct ArkitectOptions {
+    /// Directories scanned besides each crate's source directory, relative to the project root.
+    pub source_dirs: Vec<String>,
+    /// Replaces the default ignored directories when set.
+    pub ignored_directories: Option<Vec<String>>,
+    pub baseline: Option<usize>,
+    /// Also scans `examples/`, `tests/`, `benches/` and the paths of `[[bin]]`/`[[example]]` targets.
+    pub include_targets: bool,
+    /// Restricts a workspace scan to these members, given as path or directory name.
+    pub members: Option<Vec<String>>,
+    /// Workspace members left out of the scan, given as path or directory name.
+    pub excluded_members: Vec<String>,
+    /// The Cargo features considered enabled. When set, items behind a `#[cfg]` on other
+    /// features are left out; when `None`, every item is analyzed.
+    pub features: Option<Vec<String>>,
+}
+
+impl ArkitectOptions {
+    /// Reads the options from the `Cargo.toml` in `root_dir`; missing manifest or table means defaults.
+    pub fn from_manifest(root_dir: &Path) -> Result<Self, String> {
+        let cargo_toml_path = root_dir.join("Cargo.toml");
+        let Ok(cargo_toml_content) = fs::read_to_string(&cargo_toml_path) else {
+            return Ok(Self::default());
+        };
+        let cargo_toml: Value = toml::from_str(&cargo_toml_content)
+            .map_err(|e| format!("Failed to parse {}: {}", cargo_toml_path.display(), e))?;
+
+        let metadata = ["workspace", "package"].iter().find_map(|section| {
+            cargo_toml
+                .get(section)
+                .and_then(|table| table.get("metadata"))
+                .and_then(|metadata| metadata.get("arkitect"))
+        });
+
+        let Some(metadata) = metadata else {
+            return Ok(Self::default());
+        };
+
+        let strings = |key: &str| -> Result<Option<Vec<String>>, String> {
+            metadata
+                .get(key)
+                .map(|value| {
+                    value
+                        .as_array()
+                        .and_then(|values| {
+                            values
+                                .iter()
+                                .map(|v| v.as_str().map(str::to_string))
+                                .collect::<Option<Vec<String>>>()
+                        })
+                        .ok_or_else(|| {
+                            format!("metadata.arkitect.{} must be an array of strings", key)
+                        })
+                })
+                .transpose()
+        };
+
+        let baseline = metadata
+            .get("baseline")
+            .map(|value| {
+                value
+                    .as_integer()
+                    .and_then(|baseline| usize::try_from(baseline).ok())
+                    .ok_or_else(|| {
+                        "metadata.arkitect.baseline must be a non-negative integer".to_string()
+                    })
+            })
+            .transpose()?;
+
+        let include_targets = metadata
+            .get("include_targets")
+            .map(|value| {
+                value.as_bool().ok_or_else(|| {
+                    "metadata.arkitect.include_targets must be a boolean".to_string()
+                })
+            })
+            .transpose()?
+            .unwrap_or(false);
+
+        Ok(Self {
+            source_dirs: strings("source_dirs")?.unwrap_or_default(),
+            ignored_directories: strings("ignored_directories")?,
+            baseline,
+            include_targets,
+            members: strings("members")?,
+            excluded_members: strings("exclude_members")?.unwrap_or_default(),
+            features: strings("features")?,
+        })
+    }
+}
+
+/// The member crates of the workspace whose `[workspace]` table is `workspace`, in manifest order.
+/// Glob entries such as `crates/*` expand to the matching directories holding a `Cargo.toml`,
+/// except those under a `[workspace].exclude` path as Cargo does, then `options.members` and
+/// `options.excluded_members` are applied.
+pub(crate) fn workspace_members(
+    workspace_root: &Path,
+    workspace: &Value,
+    options: &ArkitectOptions,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let Some(members) = workspace.get("members") else {
+        return Ok(vec![]);
+    };
+
+    let cargo_excluded: Vec<PathBuf> = workspace
+        .get("exclude")
+        .and_then(|exclude| exclude.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|path| path.as_str())
+        .map(|path| workspace_root.join(path))
+        .collect();
+
+    let mut member_paths = Vec::new();
+    for member in members
+        .as_array()
+        .ok_or("Invalid workspace.members format")?
+    {
+        let pattern = member.as_str().ok_or("Invalid workspace.members format")?;
+        if pattern.contains(['*', '?']) {
+            member_paths.extend(
+                expand_member_pattern(workspace_root, pattern)
+                    .into_iter()
+                    .filter(|path| path.join("Cargo.toml").is_file())
+                    .filter(|path| {
+                        !cargo_excluded
+                            .iter()
+                            .any(|excluded| path.starts_with(excluded))
+                    }),
+            );
+        } else {
+            member_paths.push(workspace_root.join(pattern));
+        }
+    }
+
+    let is_listed = |path: &Path, selectors: &[String]| {
+        let relative = path
+            .strip_prefix(workspace_root)
+            .unwrap_or(path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let name = path.file_name().map(|name| name.to_string_lossy());
+
+        selectors
+            .iter()
+            .any(|selector| *selector == relative || Some(selector.as_str()) == name.as_deref())
+    };
+
+    Ok(member_paths
+        .into_iter()
+        .filter(|path| match &options.members {
+            Some(members) => is_listed(path, members),
+            None => true,
+        })
+        .filter(|path| !is_listed(path, &options.excluded_members))
+        .collect())
+}
+
+/// The directories under `root` matching `pattern`, whose components may use `*` and `?`.
+fn expand_member_pattern(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut paths = vec![root.to_path_buf()];
+
+    for component in pattern.split('/').filter(|c| !c.is_empty() && *c != ".") {
+        if !component.contains(['*', '?']) {
+            paths = paths.into_iter().map(|path| path.join(component)).collect();
+            continue;
+        }
+
+        paths = paths
+            .into_iter()
+            .flat_map(|path| {
+                let mut matches: Vec<PathBuf> = fs::read_dir(&path)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| {
+                        entry.path().is_dir()
+                            && matches_wildcard(component, &entry.file_name().to_string_lossy())
+                    })
+                    .map(|entry| entry.path())
+                    .collect();
+                matches.sort();
+                matches
+            })
+            .collect();
+    }
+
+    paths
+}
+
+/// Whether `name` matches `pattern`, where `*` stands for any run of characters and `?` for one.
+fn matches_wildcard(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| matches(rest, &name[skip..])),
+            Some(('?', rest)) => !name.is_empty() && matches(rest, &name[1..]),
+            Some((c, rest)) => name.first() == Some(c) && matches(rest, &name[1..]),
+        }
+    }
+
+    matches(&pattern, &name)
+}
+
+/// A workspace member as reported by `cargo metadata`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CargoPackage {
+    /// The package name, e.g. `billing-core`.
+    pub name: String,
+    /// The name modules are rooted at: the library target's, e.g. `billing_core`.
+    pub crate_name: String,
+    /// The directory holding the package's `Cargo.toml`.
+    pub manifest_dir: PathBuf,
+}
+
+/// The workspace layout resolved by Cargo itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CargoMetadata {
+    pub workspace_root: PathBuf,
+    pub members: Vec<CargoPackage>,
+}
+
+impl CargoMetadata {
+    /// Runs `cargo metadata --format-version 1 --no-deps` in `manifest_dir`.
+    pub fn read(manifest_dir: &Path) -> Result<Self, String> {
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let output = Command::new(cargo)
+            .args([
+                "metadata",
+                "--format-version",
+                "1",
+                "--no-deps",
+                "--offline",
+            ])
+            .current_dir(manifest_dir)
+            .output()
+            .map_err(|e| format!("Failed to run cargo metadata: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "cargo metadata failed in {}: {}",
+                manifest_dir.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Self::parse(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Parses the JSON printed by `cargo metadata --format-version 1`.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let metadata: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| format!("Invalid cargo metadata: {}", e))?;
+        let invalid = |field: &str| format!("Invalid cargo metadata: missing {}", field);
+
+        let workspace_root = metadata["workspace_root"]
+            .as_str()
+            .ok_or_else(|| invalid("workspace_root"))?;
+        let member_ids: Vec<&str> = metadata["workspace_members"]
+            .as_array()
+            .ok_or_else(|| invalid("workspace_members"))?
+            .iter()
+            .filter_map(|id| id.as_str())
+            .collect();
+
+        let mut members = Vec::new();
+        for package in metadata["packages"]
+            .as_array()
+            .ok_or_else(|| invalid("packages"))?
+        {
+            if !package["id"]
+                .as_str()
+                .is_some_and(|id| member_ids.contains(&id))
+            {
+                continue;
+            }
+
+            let name = package["name"]
+                .as_str()
+                .ok_or_else(|| invalid("package name"))?;
+            let manifest_path = package["manifest_path"]
+                .as_str()
+                .ok_or_else(|| invalid("package manifest_path"))?;
+            let lib_name = package["targets"].as_array().and_then(|targets| {
+                targets
+                    .iter()
+                    .find(|target| {
+                        target["kind"].as_array().is_some_and(|kinds| {
+                            kinds.iter().any(|kind| {
+                                matches!(kind.as_str(), Some("lib" | "rlib" | "proc-macro"))
+                            })
+                        })
+                    })
+                    .and_then(|target| target["name"].as_str())
+            });
+
+            members.push(CargoPackage {
+                name: name.to_string(),
+                crate_name: lib_name.unwrap_or(name).replace('-', "_"),
+                manifest_dir: Path::new(manifest_path)
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default(),
+            });
+        }
+
+        Ok(Self {
+            workspace_root: PathBuf::from(workspace_root),
+            members,
+        })
+    }
+}
+
+/// Runs `git diff --name-only <base>` in `dir`, listing the files changed since `base` (deleted
+/// ones excluded) relative to `dir`. Fails when `dir` isn't in a git repository.
+pub fn git_changed_files(dir: &Path, base: &str) -> Result<Vec<String>, String> {
+    let output = Command::new("git")
+        .args(["diff", "--name-only", "--relative", "--diff-filter=d", base])
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git diff failed in {}: {}",
+            dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
 pub struct RustProject {
     pub files: Vec<RustFile>,
 }
 
 impl RustProject {
     pub fn from_directory(root_dir: &str) -> Result<Self, Box<dyn Error>> {
+        let ignored_directories: Vec<String> = DEFAULT_IGNORED_DIRECTORIES
+            .iter()
+            .map(|dir| dir.to_string())
+            .collect();
+
+        Self::from_directory_ignoring(root_dir, &ignored_directories)
+    }
+
+    /// Like `from_directory`, skipping every directory whose name is in `ignored_directories`.
+    pub fn from_directory_ignoring(
+        root_dir: &str,
+        ignored_directories: &[String],
+    ) -> Result<Self, Box<dyn Error>> {
+        let options = ArkitectOptions {
+            ignored_directories: Some(ignored_directories.to_vec()),
+            ..Default::default()
+        };
+
+        Self::from_directory_with_options(root_dir, &options)
+    }
+
+    /// Like `from_directory`, scanning the extra source directories and targets set in `options`.
+    pub fn from_directory_with_options(
+        root_dir: &str,
+        options: &ArkitectOptions,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut rust_files = Vec::new();
+        let mut crate_names = CrateNameCache::default();
+        for path in Self::rust_file_paths(root_dir, options)? {
+            match RustFile::try_from_file_system_with_features(
+                &path,
+                &mut crate_names,
+                options.features.as_deref(),
+            ) {
+                Ok(rust_file) => rust_files.push(rust_file),
+                Err(e) => warn!("Skipping {}: {}", path, e),
+            }
+        }
+
+        if rust_files.is_empty() {
+            return Err("No Rust source files found.".into());
+        }
+
+        Ok(Self { files: rust_files })
+    }
+
+    /// Like `from_directory_with_options`, only parsing the files modified since they were stored
+    /// in `cache`, which is updated but not saved. The files read from the cache have an empty
+    /// syntax tree, so this only serves the rules and tools working on dependencies alone.
+    pub fn from_directory_cached(
+        root_dir: &str,
+        options: &ArkitectOptions,
+        cache: &mut DependencyCache,
+    ) -> Result<Self, Box<dyn Error>> {
+        let paths = Self::rust_file_paths(root_dir, options)?;
+        cache.use_features(&options.features);
+        cache.retain_files(&paths);
+
+        let mut rust_files = Vec::new();
+        let mut crate_names = CrateNameCache::default();
+        for path in &paths {
+            match cache.file(path, &mut crate_names, |_| false) {
+                Ok(rust_file) => rust_files.push(rust_file),
+                Err(e) => warn!("Skipping {}: {}", path, e),
+            }
+        }
+
+        if rust_files.is_empty() {
+            return Err("No Rust source files found.".into());
+        }
+
+        Ok(Self { files: rust_files })
+    }
+
+    /// Builds the graph of `to_dependency_graph` for the project at `root_dir`, only parsing the
+    /// files modified since they were stored in `cache`, which is updated but not saved.
+    pub fn dependency_graph_cached(
+        root_dir: &str,
+        options: &ArkitectOptions,
+        cache: &mut DependencyCache,
+    ) -> Result<HashMap<String, Vec<String>>, Box<dyn Error>> {
+        Ok(Self::from_directory_cached(root_dir, options, cache)?.to_dependency_graph())
+    }
+
+    /// The paths of the Rust files of the project, walking the directories set in `options`.
+    fn rust_file_paths(
+        root_dir: &str,
+        options: &ArkitectOptions,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let default_ignored_directories: Vec<String> = DEFAULT_IGNORED_DIRECTORIES
+            .iter()
+            .map(|dir| dir.to_string())
+            .collect();
+        let ignored_directories = options
+            .ignored_directories
+            .as_ref()
+            .unwrap_or(&default_ignored_directories);
+
+        // 3. Cerchiamo tutti i file `.rs` nelle directory sorgenti
+        let mut rust_files = Vec::new();
+        for src_dir in Self::source_dirs(root_dir, options)? {
+            let walker = WalkDir::new(&src_dir).into_iter().filter_entry(|e| {
+                !(e.file_type().is_dir()
+                    && ignored_directories
+                        .iter()
+                        .any(|ignored| e.file_name() == ignored.as_str()))
+            });
+            for entry in walker.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if Self::is_rust_file(path) {
+                    rust_files.push(path.to_string_lossy().to_string());
+                }
+            }
+        }
+
+        Ok(rust_files)
+    }
+
+    /// The directories walked for the Rust files of the project at `root_dir`: the source
+    /// directories of its crates, their targets when `options` includes them, and the extra
+    /// source directories of `options`.
+    pub(crate) fn source_dirs(
+        root_dir: &str,
+        options: &ArkitectOptions,
+    ) -> Result<Vec<PathBuf>, Box<dyn Error>> {
         // 1. Troviamo e leggiamo il `Cargo.toml`
         let cargo_toml_path = Path::new(root_dir).join("Cargo.toml");
         if !cargo_toml_path.exists() {
@@ -26,42 +510,33 @@ impl RustProject {
 
         if let Some(workspace) = cargo_toml.get("workspace") {
             // È un workspace: troviamo i membri e analizziamo i loro `Cargo.toml`
-            if let Some(members) = workspace.get("members") {
-                for member in members
-                    .as_array()
-                    .ok_or("Invalid workspace.members format")?
-                {
-                    let member_path = Path::new(root_dir).join(member.as_str().unwrap());
-                    source_dirs.push(Self::find_source_dir(&member_path)?);
+            for member_path in workspace_members(Path::new(root_dir), workspace, options)? {
+                source_dirs.extend(Self::find_source_dirs(&member_path)?);
+                if options.include_targets {
+                    source_dirs.extend(Self::find_target_paths(&member_path)?);
                 }
             }
         } else {
             // È un crate singolo: cerchiamo la directory sorgente
-            source_dirs.push(Self::find_source_dir(Path::new(root_dir))?);
-        }
-
-        // 3. Cerchiamo tutti i file `.rs` nelle directory sorgenti
-        let mut rust_files = Vec::new();
-        for src_dir in source_dirs {
-            for entry in WalkDir::new(&src_dir).into_iter().filter_map(|e| e.ok()) {
-                let path = entry.path();
-                if Self::is_rust_file(path) {
-                    let path_str = path.to_string_lossy().to_string();
-                    let rust_file = RustFile::from_file_system(&path_str);
-                    rust_files.push(rust_file);
-                }
+            source_dirs.extend(Self::find_source_dirs(Path::new(root_dir))?);
+            if options.include_targets {
+                source_dirs.extend(Self::find_target_paths(Path::new(root_dir))?);
             }
         }
-
-        if rust_files.is_empty() {
-            return Err("No Rust source files found.".into());
+        for extra_source_dir in &options.source_dirs {
+            source_dirs.push(Path::new(root_dir).join(extra_source_dir));
         }
+        // The `source_dirs` of a crate at the root are also in `options`
+        let mut unique = HashSet::new();
+        source_dirs.retain(|dir| unique.insert(dir.clone()));
 
-        Ok(Self { files: rust_files })
+        Ok(source_dirs)
     }
 
-    /// Determina la directory sorgente di un crate leggendo il suo `Cargo.toml`.
-    fn find_source_dir(crate_dir: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    /// The source directories of a crate, read from its `Cargo.toml`: `src/`, or the legacy
+    /// `package.metadata.source`, followed by the `package.metadata.arkitect.source_dirs`
+    /// scanned besides it, relative to the crate.
+    fn find_source_dirs(crate_dir: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
         let cargo_toml_path = crate_dir.join("Cargo.toml");
         if !cargo_toml_path.exists() {
             return Err(format!(
@@ -74,41 +549,259 @@ impl RustProject {
         let cargo_toml_content = fs::read_to_string(&cargo_toml_path)?;
         let cargo_toml: Value = toml::from_str(&cargo_toml_content)?;
 
-        // Per default, la directory sorgente è `src/`
-        if let Some(package) = cargo_toml.get("package") {
-            if let Some(metadata) = package.get("metadata") {
-                if let Some(custom_source) = metadata.get("source") {
-                    let custom_dir = crate_dir.join(custom_source.as_str().unwrap());
-                    return Ok(custom_dir);
+        let metadata = cargo_toml
+            .get("package")
+            .and_then(|package| package.get("metadata"));
+
+        let mut source_dirs = vec![match metadata.and_then(|metadata| metadata.get("source")) {
+            Some(custom_source) => crate_dir.join(custom_source.as_str().unwrap()),
+            None => crate_dir.join("src"),
+        }];
+
+        if let Some(extra_source_dirs) = metadata
+            .and_then(|metadata| metadata.get("arkitect"))
+            .and_then(|arkitect| arkitect.get("source_dirs"))
+        {
+            let extra_source_dirs = extra_source_dirs
+                .as_array()
+                .and_then(|dirs| {
+                    dirs.iter()
+                        .map(|dir| dir.as_str())
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| {
+                    format!(
+                        "metadata.arkitect.source_dirs must be an array of strings in {}",
+                        cargo_toml_path.display()
+                    )
+                })?;
+            source_dirs.extend(extra_source_dirs.into_iter().map(|dir| crate_dir.join(dir)));
+        }
+
+        Ok(source_dirs)
+    }
+
+    /// Finds the targets living outside the source directory: the `path` of `[[bin]]`,
+    /// `[[example]]`, `[[test]]` and `[[bench]]` entries plus the conventional
+    /// `examples/`, `tests/` and `benches/` directories.
+    fn find_target_paths(crate_dir: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        let cargo_toml_content = fs::read_to_string(crate_dir.join("Cargo.toml"))?;
+        let cargo_toml: Value = toml::from_str(&cargo_toml_content)?;
+        let source_dirs = Self::find_source_dirs(crate_dir)?;
+
+        let mut target_paths: Vec<PathBuf> = ["examples", "tests", "benches"]
+            .iter()
+            .map(|dir| crate_dir.join(dir))
+            .filter(|dir| dir.is_dir())
+            .collect();
+
+        for section in ["bin", "example", "test", "bench"] {
+            let targets = cargo_toml
+                .get(section)
+                .and_then(|targets| targets.as_array());
+            for target in targets.into_iter().flatten() {
+                if let Some(path) = target.get("path").and_then(|path| path.as_str()) {
+                    let path = crate_dir.join(path);
+                    let already_scanned = target_paths
+                        .iter()
+                        .chain(&source_dirs)
+                        .any(|dir| path.starts_with(dir));
+                    if !already_scanned {
+                        target_paths.push(path);
+                    }
                 }
             }
         }
 
-        Ok(crate_dir.join("src")) // Default directory sorgente
+        Ok(target_paths)
     }
 
     fn is_rust_file(path: &Path) -> bool {
         path.extension().map(|ext| ext == "rs").unwrap_or(false)
     }
 
+    /// Builds a module-level dependency graph.
+    ///
+    /// Every file is a node, and so is every dependency target, even when no scanned file defines it.
+    /// Dependencies are mapped to the most specific scanned module containing them, falling back to
+    /// the dependency without its final item segment (`ext::fmt::Display` becomes `ext::fmt`).
     pub fn to_dependency_graph(&self) -> HashMap<String, Vec<String>> {
-        let mut graph = HashMap::new();
-        for f in &self.files {
-            graph.insert(
-                f.logical_path.clone(),
-                f.dependencies.iter().map(extract_module).collect(),
-            );
+        self.dependency_graph(false)
+    }
+
+    /// Like `to_dependency_graph`, following `pub use` re-exports to their origin: when
+    /// `my_crate::api` re-exports `my_crate::domain::Thing`, a dependency on `my_crate::api::Thing`
+    /// is an edge to `my_crate::domain`, not to `my_crate::api`.
+    pub fn to_dependency_graph_through_reexports(&self) -> HashMap<String, Vec<String>> {
+        self.dependency_graph(true)
+    }
+
+    fn dependency_graph(&self, through_reexports: bool) -> HashMap<String, Vec<String>> {
+        let origins: HashMap<&str, &str> = if through_reexports {
+            self.files
+                .iter()
+                .flat_map(|f| f.reexport_origins())
+                .map(|(exposed, origin)| (exposed.as_str(), origin.as_str()))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        let nodes: Vec<(&str, &[String])> = self
+            .files
+            .iter()
+            .map(|f| (f.logical_path.as_str(), f.dependencies.as_slice()))
+            .collect();
+
+        graph_of(&nodes, &origins)
+    }
+
+    /// Renders the dependencies between the scanned modules as a Mermaid `graph LR` definition.
+    pub fn to_mermaid(&self) -> String {
+        let modules: Vec<&str> = self.files.iter().map(|f| f.logical_path.as_str()).collect();
+
+        let edges =
+            self.collapsed_edges(|module| modules.contains(&module).then(|| module.to_string()));
+
+        render_mermaid(&edges)
+    }
+
+    /// Like `to_mermaid`, collapsing modules into components given as `(name, module prefix)`
+    /// pairs, e.g. `("Domain", "my_crate::domain")`. The longest matching prefix wins and
+    /// modules outside every component are left out.
+    pub fn to_mermaid_by_component(&self, components: &[(&str, &str)]) -> String {
+        let edges = self.collapsed_edges(|module| {
+            components
+                .iter()
+                .filter(|(_, prefix)| {
+                    module == *prefix || module.starts_with(&format!("{}::", prefix))
+                })
+                .max_by_key(|(_, prefix)| prefix.len())
+                .map(|(name, _)| name.to_string())
+        });
+
+        render_mermaid(&edges)
+    }
+
+    /// The graph edges between the nodes returned by `node_of`, without self-loops.
+    fn collapsed_edges(
+        &self,
+        node_of: impl Fn(&str) -> Option<String>,
+    ) -> BTreeSet<(String, String)> {
+        let mut edges = BTreeSet::new();
+        for (module, dependencies) in self.to_dependency_graph() {
+            let Some(from) = node_of(&module) else {
+                continue;
+            };
+            for dependency in dependencies {
+                if let Some(to) = node_of(&dependency) {
+                    if from != to {
+                        edges.insert((from.clone(), to));
+                    }
+                }
+            }
         }
-        remove_duplicates(&graph)
+
+        edges
+    }
+}
+
+fn render_mermaid(edges: &BTreeSet<(String, String)>) -> String {
+    let id = |node: &str| node.replace("::", "_");
+
+    let mut mermaid = String::from("graph LR\n");
+    for (from, to) in edges {
+        mermaid.push_str(&format!(
+            "    {}[\"{}\"] --> {}[\"{}\"]\n",
+            id(from),
+            from,
+            id(to),
+            to
+        ));
     }
+
+    mermaid
 }
 
-fn extract_module(logical_path: &String) -> String {
+/// The module-level dependency graph of modules given with their dependencies, see
+/// `RustProject::to_dependency_graph`.
+fn graph_of(
+    nodes: &[(&str, &[String])],
+    origins: &HashMap<&str, &str>,
+) -> HashMap<String, Vec<String>> {
+    let modules: HashSet<&str> = nodes.iter().map(|(module, _)| *module).collect();
+
+    let mut graph: HashMap<String, Vec<String>> = HashMap::new();
+    for (module, dependencies) in nodes {
+        let dependencies = dependencies
+            .iter()
+            .map(|d| resolve_module(&resolve_reexport(d, origins), &modules));
+        graph
+            .entry(module.to_string())
+            .or_default()
+            .extend(dependencies);
+    }
+
+    let targets: Vec<String> = graph.values().flatten().cloned().collect();
+    for target in targets {
+        graph.entry(target).or_default();
+    }
+
+    remove_duplicates(&graph)
+}
+
+/// The longest of the `modules` `dependency` is or belongs to, found by trimming it one segment
+/// at a time, or its parent module when there's none.
+fn resolve_module(dependency: &str, modules: &HashSet<&str>) -> String {
+    let mut prefix = dependency;
+    loop {
+        if modules.contains(prefix) {
+            return prefix.to_string();
+        }
+        match prefix.rsplit_once("::") {
+            Some((parent, _)) => prefix = parent,
+            None => return extract_module(dependency),
+        }
+    }
+}
+
+/// Follows the re-exports `dependency` goes through, up to one hop per known re-export so that
+/// re-exports pointing at each other can't loop forever.
+fn resolve_reexport(dependency: &str, origins: &HashMap<&str, &str>) -> String {
+    let mut resolved = dependency.to_string();
+    for _ in 0..origins.len() {
+        match longest_exposed_prefix(&resolved, origins) {
+            Some((exposed, origin)) => {
+                resolved = format!("{}{}", origin, &resolved[exposed.len()..])
+            }
+            None => break,
+        }
+    }
+
+    resolved
+}
+
+/// The longest re-exported path `dependency` is or belongs to, with its origin, found by
+/// trimming it one segment at a time.
+fn longest_exposed_prefix<'a>(
+    dependency: &'a str,
+    origins: &HashMap<&str, &'a str>,
+) -> Option<(&'a str, &'a str)> {
+    let mut prefix = dependency;
+    loop {
+        if let Some(origin) = origins.get(prefix) {
+            return Some((prefix, origin));
+        }
+        prefix = prefix.rsplit_once("::")?.0;
+    }
+}
+
+fn extract_module(logical_path: &str) -> String {
     logical_path
-        .rsplitn(2, "::")
-        .nth(1)
-        .map(String::from)
-        .expect("A valid module path")
+        .rsplit_once("::")
+        .map(|(parent, _)| parent)
+        .unwrap_or(logical_path)
+        .to_string()
 }
 
 fn remove_duplicates(map: &HashMap<String, Vec<String>>) -> HashMap<String, Vec<String>> {
@@ -127,7 +820,11 @@ fn remove_duplicates(map: &HashMap<String, Vec<String>>) -> HashMap<String, Vec<
 
 #[cfg(test)]
 mod tests {
-    use crate::rust_project::RustProject;
+    use crate::dependency_cache::DependencyCache;
+    use crate::fixtures::temp_project;
+    use crate::rust_file::RustFile;
+    use crate::rust_project::{ArkitectOptions, RustProject};
+    use std::path::Path;
 
     #[test]
     fn test_rust_project_from_directory() {
@@ -138,8 +835,333 @@ mod tests {
 
         let graph = project.to_dependency_graph();
 
-        assert!(graph.len() > 0);
-        assert_eq!(graph.len(), project.files.len());
+        assert!(!graph.is_empty());
+        assert!(project
+            .files
+            .iter()
+            .all(|f| graph.contains_key(&f.logical_path)));
+    }
+
+    #[test]
+    fn test_dependency_graph_connects_modules() {
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/a.rs",
+                    "my_crate::a",
+                    r#"
+                    use crate::b::helper;
+                    use crate::b::inner::Thing;
+                    use std::fmt::Display;
+                    "#,
+                ),
+                RustFile::from_content("src/b.rs", "my_crate::b", "pub fn helper() {}"),
+            ],
+        };
+
+        let graph = project.to_dependency_graph();
+
+        assert_eq!(graph["my_crate::a"].len(), 2);
+        assert!(graph["my_crate::a"].contains(&"my_crate::b".to_string()));
+        assert!(graph["my_crate::a"].contains(&"std::fmt".to_string()));
+        assert_eq!(graph["std::fmt"], Vec::<String>::new());
+        assert_eq!(graph["my_crate::b"], Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_dependency_graph_through_reexports() {
+        let project = RustProject {
+            files: vec![
+                RustFile::from_content(
+                    "src/web.rs",
+                    "my_crate::web",
+                    "use crate::api::{Thing, nested::Other};\n",
+                ),
+                RustFile::from_content(
+                    "src/api.rs",
+                    "my_crate::api",
+                    "pub use crate::facade::Thing;\npub mod nested { pub use crate::domain::Other; }\n",
+                ),
+                RustFile::from_content(
+                    "src/facade.rs",
+                    "my_crate::facade",
+                    "pub use crate::domain::Thing;\n",
+                ),
+                RustFile::from_content("src/domain.rs", "my_crate::domain", "pub struct Thing;\n"),
+            ],
+        };
+
+        assert_eq!(
+            project.to_dependency_graph()["my_crate::web"],
+            vec!["my_crate::api"]
+        );
+        assert_eq!(
+            project.to_dependency_graph_through_reexports()["my_crate::web"],
+            vec!["my_crate::domain"]
+        );
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let project = RustProject::from_directory("examples/sample_project").unwrap();
+
+        let mermaid = project.to_mermaid();
+
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains(
+            "sample_project_conversion_infrastructure[\"sample_project::conversion::infrastructure\"] --> sample_project_conversion_application[\"sample_project::conversion::application\"]"
+        ));
+    }
+
+    #[test]
+    fn test_to_mermaid_by_component() {
+        let project = RustProject::from_directory("examples/sample_project").unwrap();
+
+        let mermaid = project.to_mermaid_by_component(&[
+            ("Domain", "sample_project::conversion::domain"),
+            ("Application", "sample_project::conversion::application"),
+            (
+                "Infrastructure",
+                "sample_project::conversion::infrastructure",
+            ),
+            ("Contracts", "sample_project::contracts"),
+        ]);
+
+        assert_eq!(
+            mermaid,
+            "graph LR\n\
+             \x20   Application[\"Application\"] --> Contracts[\"Contracts\"]\n\
+             \x20   Application[\"Application\"] --> Domain[\"Domain\"]\n\
+             \x20   Infrastructure[\"Infrastructure\"] --> Application[\"Application\"]\n"
+        );
+    }
+
+    #[test]
+    fn test_arkitect_options_from_manifest() {
+        let options = ArkitectOptions::from_manifest(Path::new("examples/metadata_project"));
+
+        assert_eq!(
+            options,
+            Ok(ArkitectOptions {
+                source_dirs: vec!["generated".to_string()],
+                ignored_directories: None,
+                baseline: Some(1),
+                include_targets: false,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_arkitect_options_default_without_metadata() {
+        let options = ArkitectOptions::from_manifest(Path::new("examples/sample_project"));
+
+        assert_eq!(options, Ok(ArkitectOptions::default()));
+    }
+
+    #[test]
+    fn test_extra_source_dirs_are_scanned() {
+        let options = ArkitectOptions {
+            source_dirs: vec!["generated".to_string()],
+            ..Default::default()
+        };
+        let project =
+            RustProject::from_directory_with_options("examples/metadata_project", &options)
+                .unwrap();
+
+        let api = project
+            .files
+            .iter()
+            .find(|f| f.logical_path == "metadata_project::generated::api")
+            .expect("generated/api.rs should be scanned");
+        assert_eq!(api.dependencies, vec!["metadata_project::internal::Helper"]);
+    }
+
+    #[test]
+    fn test_every_configured_source_directory_is_scanned() {
+        let root = temp_project(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"sources\"\n\n[package.metadata.arkitect]\nsource_dirs = [\"generated\"]\n",
+            ),
+            ("src/lib.rs", "pub fn run() {}\n"),
+            ("generated/api.rs", "use crate::run;\n"),
+            ("benches/ignored.rs", "fn main() {}\n"),
+        ]);
+
+        // The manifest options list the same `source_dirs`, they are scanned once
+        let options = ArkitectOptions::from_manifest(root.path()).unwrap();
+        let project =
+            RustProject::from_directory_with_options(root.path().to_str().unwrap(), &options)
+                .unwrap();
+        let mut modules: Vec<&str> = project
+            .files
+            .iter()
+            .map(|f| f.logical_path.as_str())
+            .collect();
+        modules.sort();
+
+        assert_eq!(modules, vec!["sources", "sources::generated::api"]);
+    }
+
+    #[test]
+    fn test_targets_are_scanned_when_included() {
+        let root = temp_project(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"targets\"\n\n[[bin]]\nname = \"tool\"\npath = \"tools/tool.rs\"\n",
+            ),
+            ("src/lib.rs", "pub fn run() {}\n"),
+            ("examples/demo.rs", "use targets::run;\nfn main() { run() }\n"),
+            ("tools/tool.rs", "fn main() {}\n"),
+        ]);
+        let root = root.path().to_str().unwrap();
+
+        let modules = |project: RustProject| -> Vec<String> {
+            let mut modules: Vec<String> =
+                project.files.into_iter().map(|f| f.logical_path).collect();
+            modules.sort();
+            modules
+        };
+
+        let without_targets = RustProject::from_directory(root).unwrap();
+        assert_eq!(modules(without_targets), vec!["targets"]);
+
+        let options = ArkitectOptions {
+            include_targets: true,
+            ..Default::default()
+        };
+        let with_targets = RustProject::from_directory_with_options(root, &options).unwrap();
+        assert_eq!(
+            modules(with_targets),
+            vec!["targets", "targets::examples::demo", "targets::tools::tool"]
+        );
+    }
+
+    #[test]
+    fn test_dependency_graph_cached_parses_modified_files_only() {
+        let root = temp_project(&[
+            ("Cargo.toml", "[package]\nname = \"cached\"\n"),
+            ("src/lib.rs", "pub mod domain;\n"),
+            ("src/domain.rs", "use crate::shared::Money;\n"),
+        ]);
+        let cache_dir = root.path().join("target/arkitect");
+        let project_dir = root.path().to_str().unwrap();
+        let options = ArkitectOptions::default();
+        let graph = |cache: &mut DependencyCache| {
+            RustProject::dependency_graph_cached(project_dir, &options, cache).unwrap()
+        };
+
+        let mut cache = DependencyCache::open(&cache_dir);
+        let first_run = graph(&mut cache);
+        cache.save().unwrap();
+        assert_eq!(cache.files_parsed(), 2);
+        assert_eq!(
+            first_run,
+            RustProject::from_directory(project_dir)
+                .unwrap()
+                .to_dependency_graph()
+        );
+
+        let mut cache = DependencyCache::open(&cache_dir);
+        assert_eq!(graph(&mut cache), first_run);
+        assert_eq!(cache.files_parsed(), 0);
+
+        let domain = root.path().join("src/domain.rs");
+        std::fs::write(&domain, "use std::fmt::Display;\n").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&domain)
+            .unwrap()
+            .set_modified(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap();
+        let third_run = graph(&mut cache);
+        assert_eq!(cache.files_parsed(), 1);
+        assert_ne!(third_run, first_run);
+        assert_eq!(
+            third_run,
+            RustProject::from_directory(project_dir)
+                .unwrap()
+                .to_dependency_graph()
+        );
+    }
+
+    #[test]
+    fn test_workspace_members_globs_and_exclusions() {
+        let root = temp_project(&[
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\", \"tools/cli\"]\n",
+            ),
+            (
+                "crates/billing/Cargo.toml",
+                "[package]\nname = \"billing\"\n",
+            ),
+            ("crates/billing/src/lib.rs", "pub fn bill() {}\n"),
+            ("crates/orders/Cargo.toml", "[package]\nname = \"orders\"\n"),
+            ("crates/orders/src/lib.rs", "pub fn order() {}\n"),
+            ("crates/notes/README.md", "Not a crate\n"),
+            ("tools/cli/Cargo.toml", "[package]\nname = \"cli\"\n"),
+            ("tools/cli/src/lib.rs", "pub fn run() {}\n"),
+        ]);
+        let root = root.path().to_str().unwrap();
+
+        let modules = |options: ArkitectOptions| -> Vec<String> {
+            let project = RustProject::from_directory_with_options(root, &options).unwrap();
+            let mut modules: Vec<String> =
+                project.files.into_iter().map(|f| f.logical_path).collect();
+            modules.sort();
+            modules
+        };
+
+        assert_eq!(
+            modules(ArkitectOptions::default()),
+            vec!["billing", "cli", "orders"]
+        );
+        assert_eq!(
+            modules(ArkitectOptions {
+                excluded_members: vec!["orders".to_string()],
+                ..Default::default()
+            }),
+            vec!["billing", "cli"]
+        );
+        assert_eq!(
+            modules(ArkitectOptions {
+                members: Some(vec!["crates/billing".to_string(), "cli".to_string()]),
+                excluded_members: vec!["tools/cli".to_string()],
+                ..Default::default()
+            }),
+            vec!["billing"]
+        );
+    }
+
+    #[test]
+    fn test_glob_members_resolve_to_member_crates() {
+        let root = temp_project(&[
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/experimental\"]\n",
+            ),
+            ("crates/domain/Cargo.toml", "[package]\nname = \"domain\"\n"),
+            ("crates/domain/src/lib.rs", "pub struct Order;\n"),
+            ("crates/api/Cargo.toml", "[package]\nname = \"api\"\n"),
+            ("crates/api/src/lib.rs", "use domain::Order;\n"),
+            (
+                "crates/experimental/Cargo.toml",
+                "[package]\nname = \"experimental\"\n",
+            ),
+            ("crates/experimental/src/lib.rs", "pub fn try_it() {}\n"),
+        ]);
+
+        let project = RustProject::from_directory(root.path().to_str().unwrap()).unwrap();
+        let mut modules: Vec<&str> = project
+            .files
+            .iter()
+            .map(|f| f.logical_path.as_str())
+            .collect();
+        modules.sort();
+
+        assert_eq!(modules, vec!["api", "domain"]);
     }
 
     fn get_workspace_project_path() -> String {
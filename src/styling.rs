@@ -0,0 +1,198 @@
+//! Terminal styling shared by the engine and the rules.
+//!
+//! Rule descriptions and violations are plain text. Colors are only applied where output is
+//! rendered: `Rule::render` receives a `Styling`, e.g. the one given to the engine by
+//! `Arkitect::with_color_mode` and `Arkitect::with_theme`, and paints the subjects, forbidden
+//! dependencies and paths with the colors of its `Theme`, or leaves them plain when colors are
+//! disabled.
+//!
+//! In `ColorMode::Auto`, the default, colors are disabled when the `NO_COLOR` environment
+//! variable is set to a non-empty value or when stdout is not a terminal, so logs written to
+//! files or CI contain no escape sequences.
+
+use ansi_term::Style;
+use std::env;
+use std::fmt::Display;
+use std::io::IsTerminal;
+
+/// Whether rules and the engine color their output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    Always,
+    Never,
+    /// Colors only when stdout is a terminal and `NO_COLOR` is unset or empty.
+    #[default]
+    Auto,
+}
+
+/// A terminal color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Purple,
+    Cyan,
+    White,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn to_ansi(self) -> ansi_term::Color {
+        match self {
+            Color::Black => ansi_term::Color::Black,
+            Color::Red => ansi_term::Color::Red,
+            Color::Green => ansi_term::Color::Green,
+            Color::Yellow => ansi_term::Color::Yellow,
+            Color::Blue => ansi_term::Color::Blue,
+            Color::Purple => ansi_term::Color::Purple,
+            Color::Cyan => ansi_term::Color::Cyan,
+            Color::White => ansi_term::Color::White,
+            Color::Rgb(r, g, b) => ansi_term::Color::RGB(r, g, b),
+        }
+    }
+}
+
+/// The colors of the styled parts of messages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Subjects of rules and module names, orange by default.
+    pub subject: Color,
+    /// Forbidden dependencies and misplaced modules, red by default.
+    pub forbidden: Color,
+    /// File paths, green by default.
+    pub path: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            subject: Color::Rgb(255, 165, 0),
+            forbidden: Color::Rgb(255, 0, 0),
+            path: Color::Rgb(0, 255, 0),
+        }
+    }
+}
+
+/// How the styled parts of messages are rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Styling {
+    pub mode: ColorMode,
+    pub theme: Theme,
+}
+
+impl Styling {
+    /// Returns whether output should be colored.
+    pub fn colors_enabled(&self) -> bool {
+        match self.mode {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => colors_enabled_for(
+                env::var("NO_COLOR").ok().as_deref(),
+                std::io::stdout().is_terminal(),
+            ),
+        }
+    }
+
+    /// Returns a styling that never colors, as used by the `Display` of the rules.
+    pub fn plain() -> Self {
+        Styling {
+            mode: ColorMode::Never,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Paints the subject of a rule or a module name.
+    pub fn subject(&self, text: impl Display) -> String {
+        self.paint(self.theme.subject, text)
+    }
+
+    /// Paints forbidden dependencies and misplaced modules.
+    pub fn forbidden(&self, text: impl Display) -> String {
+        self.paint(self.theme.forbidden, text)
+    }
+
+    /// Paints file paths.
+    pub fn path(&self, text: impl Display) -> String {
+        self.paint(self.theme.path, text)
+    }
+
+    fn paint(&self, color: Color, text: impl Display) -> String {
+        if self.colors_enabled() {
+            Style::new()
+                .bold()
+                .fg(color.to_ansi())
+                .paint(text.to_string())
+                .to_string()
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+fn colors_enabled_for(no_color: Option<&str>, is_terminal: bool) -> bool {
+    is_terminal && no_color.unwrap_or_default().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_color_disables_colors() {
+        assert!(!colors_enabled_for(Some("1"), true));
+    }
+
+    #[test]
+    fn test_empty_no_color_is_ignored() {
+        assert!(colors_enabled_for(Some(""), true));
+    }
+
+    #[test]
+    fn test_non_terminal_disables_colors() {
+        assert!(!colors_enabled_for(None, false));
+    }
+
+    #[test]
+    fn test_terminal_enables_colors() {
+        assert!(colors_enabled_for(None, true));
+    }
+
+    #[test]
+    fn test_never_yields_escape_free_output() {
+        let styling = Styling {
+            mode: ColorMode::Never,
+            theme: Theme::default(),
+        };
+
+        let styled = styling.subject("my_crate::domain")
+            + &styling.forbidden("my_crate::infrastructure")
+            + &styling.path("src");
+
+        assert_eq!(styled, "my_crate::domainmy_crate::infrastructuresrc");
+    }
+
+    #[test]
+    fn test_always_uses_the_theme() {
+        let styling = Styling {
+            mode: ColorMode::Always,
+            theme: Theme {
+                forbidden: Color::Purple,
+                ..Theme::default()
+            },
+        };
+
+        let styled = styling.forbidden("my_crate::db");
+
+        assert_eq!(
+            styled,
+            Style::new()
+                .bold()
+                .fg(ansi_term::Color::Purple)
+                .paint("my_crate::db")
+                .to_string()
+        );
+    }
+}
@@ -19,51 +19,109 @@ fn test_architectural_rules() {
             .it_may_depend_on(&[
                 "rust_arkitect::engine",
                 "rust_arkitect::builtin_rules",
+                "rust_arkitect::error",
+                "rust_arkitect::fixtures",              // Only used by the DSL tests
+                "rust_arkitect::report",
                 "rust_arkitect::rule",
                 "rust_arkitect::rust_file",
+                "rust_arkitect::rust_project",
+                "rust_arkitect::styling",
                 "std::collections",
                 "std::marker::PhantomData",
                 "std::path",
                 "std::fmt",
                 "std::env",
-                "std::fs"
+                "std::fs",
+                "log",
+                "env_logger",                           // Used by `Arkitect::init_logger`
+                "serde",                                // Used to deserialize the rules config
+                "toml",                                 // Used to read the rules config
             ])
 
         .rules_for_module("rust_arkitect::engine")
             .it_may_depend_on(&[
+                "rust_arkitect::builtin_rules",         // IsChild to nest subjects, and the rules of the engine tests
+                "rust_arkitect::dependency_cache",      // Used to skip parsing unchanged files
+                "rust_arkitect::error",
+                "rust_arkitect::fixtures",              // Only used by the engine tests
+                "rust_arkitect::report",
                 "rust_arkitect::rule",
                 "rust_arkitect::rust_file",
                 "rust_arkitect::rust_project",
+                "rust_arkitect::styling",
                 "ansi_term",
                 "log",
+                "std::cmp::Reverse",                    // Used to sort overriding rules
                 "std::env",
                 "std::fs",
+                "std::io",
                 "std::path",
+                "std::time",                            // Used to time parsing and rule evaluation
+                "tempfile",                             // Only used by the engine tests
                 "toml"
             ])
 
         .rules_for_module("rust_arkitect::builtin_rules")
             .it_may_depend_on(&[
+                "rust_arkitect::fixtures",              // Only used by the rule tests
                 "rust_arkitect::rust_file",
                 "rust_arkitect::rust_project",
                 "rust_arkitect::rule",
+                "rust_arkitect::styling",
                 "ansi_term",
                 "log",
+                "proc_macro2",
+                "quote",
+                "regex",
+                "syn",
+                "std::env",                             // Used to tell when snapshots must be updated
                 "std::fmt",
                 "std::collections",
+                "std::fs",
+                "std::path",
+                "std::str::FromStr",
+            ])
+
+        .rules_for_module("rust_arkitect::report")
+            .it_may_depend_on(&[
+                "rust_arkitect::rule",
+                "std::fmt",
+                "std::time",
+                "roxmltree",
+            ])
+
+        .rules_for_module("rust_arkitect::error")
+            .it_may_depend_on(&[
+                "std::error",
+                "std::fmt",
+            ])
+
+        .rules_for_module("rust_arkitect::styling")
+            .it_may_depend_on(&[
+                "ansi_term",
+                "std::env",
+                "std::fmt",
+                "std::io",
             ])
 
         .rules_for_crate("rust_arkitect::rule")
             .it_may_depend_on(&[
                 "rust_arkitect::rust_file",
                 "rust_arkitect::rust_project",
+                "rust_arkitect::styling",               // Used to render rules with colors
                 "std::fmt",
             ])
 
         .rules_for_crate("rust_arkitect::rust_file")
             .it_may_depend_on(&[
                 "rust_arkitect::dependency_parsing",    // Used to parse dependencies, only this module is allowed to depend on it
+                "rust_arkitect::error",                 // Used to report files that can't be read or parsed
+                "rust_arkitect::fixtures",              // Only used by the tests
+                "quote",                                // Used to render the signatures of public items
+                "serde",                                // Used to report an invalid Cargo.toml
+                "std::collections",                     // Used to cache crate names by crate root
                 "std::path",                            // Used to navigate the file system and get the logical name of the module
+                "std::sync::OnceLock",                  // Used to compute re-export origins on first use
                 "syn",                                  // Used to parse Rust code and build the AST
                 "toml",                                 // Used to read Cargo.toml and find the crate of the file
             ])
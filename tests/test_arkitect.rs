@@ -0,0 +1,433 @@
+#![cfg(test)]
+
+use rust_arkitect::dsl::architectural_rules::{ArchitecturalRules, LayerOrder};
+use rust_arkitect::dsl::arkitect::{Arkitect, Rules};
+use rust_arkitect::dsl::project::Project;
+use rust_arkitect::error::ArkitectError;
+use rust_arkitect::styling::ColorMode;
+use std::fs;
+use tempfile::TempDir;
+
+/// Writes `files`, given as `(path from the root, content)` pairs, to a new temporary directory.
+fn temp_project(files: &[(&str, &str)]) -> TempDir {
+    let root = tempfile::tempdir().unwrap();
+    for (path, content) in files {
+        let path = root.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    root
+}
+
+fn sample_three_tier_layers(order: LayerOrder) -> Vec<&'static str> {
+    let mut layers = vec![
+        "sample_project::conversion::domain",
+        "sample_project::conversion::application",
+        "sample_project::conversion::infrastructure",
+    ];
+    if order == LayerOrder::TopDown {
+        layers.reverse();
+    }
+    layers
+}
+
+#[test]
+fn test_layers_on_three_tier_sample() {
+    for order in [LayerOrder::BottomUp, LayerOrder::TopDown] {
+        let rules = ArchitecturalRules::define()
+            .layers_in_order(&sample_three_tier_layers(order), order)
+            .build();
+
+        let violations = Arkitect::ensure_that(Project::from_path("examples/sample_project"))
+            .complies_with(rules)
+            .unwrap_err();
+
+        // The application layer also calls into `sample_project::contracts`, which is not a layer
+        assert_eq!(violations.len(), 1, "{:?}", violations);
+        assert!(violations[0].contains("sample_project::contracts"));
+    }
+}
+
+#[test]
+fn test_layers_in_wrong_order_are_violated() {
+    let layers = sample_three_tier_layers(LayerOrder::TopDown);
+
+    let rules = ArchitecturalRules::define()
+        .layers_in_order(&layers, LayerOrder::BottomUp)
+        .build();
+
+    let violations = Arkitect::ensure_that(Project::from_path("examples/sample_project"))
+        .complies_with(rules)
+        .unwrap_err();
+
+    assert!(violations
+        .iter()
+        .any(|v| v.contains("sample_project::conversion::domain")));
+}
+
+fn sample_rule_sets() -> Vec<Rules> {
+    #[rustfmt::skip]
+    let conversion = ArchitecturalRules::define()
+        .rules_for_module("sample_project::conversion")
+            .it_may_depend_on(&["sample_project::conversion"])
+        .build();
+
+    #[rustfmt::skip]
+    let policy_management = ArchitecturalRules::define()
+        .rules_for_project()
+            .it_must_not_have_circular_dependencies(10)
+        .rules_for_module("sample_project::policy_management")
+            .it_must_not_depend_on_anything()
+        .build();
+
+    vec![conversion, policy_management]
+}
+
+#[test]
+fn test_merged_rules_are_all_evaluated() {
+    let mut rule_sets = sample_rule_sets().into_iter();
+    let conversion = rule_sets.next().unwrap();
+    let policy_management = rule_sets.next().unwrap();
+    let expected_len = conversion.len() + policy_management.len();
+
+    let rules = conversion.merge(policy_management.with_baseline(2));
+
+    assert_eq!(rules.len(), expected_len);
+    assert_eq!(rules.module_rules.len(), 2);
+    assert_eq!(rules.project_rules.len(), 1);
+    assert_eq!(rules.baseline(), Some(2));
+
+    let report =
+        Arkitect::ensure_that(Project::from_path("examples/sample_project")).analyze(rules);
+
+    let mut evaluated: Vec<(bool, usize)> = report
+        .checks
+        .iter()
+        .map(|check| (check.path.is_some(), check.index))
+        .collect();
+    evaluated.sort();
+    evaluated.dedup();
+    assert_eq!(evaluated, vec![(false, 0), (true, 0), (true, 1)]);
+}
+
+#[test]
+fn test_warnings_are_reported_without_failing() {
+    #[rustfmt::skip]
+    let rules = || ArchitecturalRules::define()
+        .rules_for_project()
+            .it_must_not_have_circular_dependencies(10)
+            .as_warning()
+        .rules_for_module("sample_project::conversion")
+            .it_may_depend_on(&["sample_project::conversion"])
+            .as_warning()
+        .build();
+
+    let mut arkitect = Arkitect::ensure_that(Project::from_path("examples/sample_project"));
+    let report = arkitect.analyze(rules());
+
+    assert!(report.violations.is_empty());
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("sample_project::contracts"));
+    assert_eq!(report.summary(), "0 errors, 1 warning across 1 rule");
+    assert_eq!(arkitect.complies_with(rules()), Ok(vec![]));
+}
+
+#[test]
+fn test_details_keep_the_violations_within_the_baseline() {
+    #[rustfmt::skip]
+    let rules = || ArchitecturalRules::define()
+        .rules_for_module("sample_project::policy_management")
+            .it_must_not_depend_on(&["sample_project::policy_management::model"])
+        .build();
+
+    let mut arkitect =
+        Arkitect::ensure_that(Project::from_path("examples/sample_project")).with_baseline(2);
+    let details = arkitect.complies_with_details(rules());
+
+    assert_eq!(details.all_violations.len(), 2);
+    assert_eq!(details.baseline, 2);
+    assert!(!details.exceeds_baseline);
+
+    let details = arkitect.with_baseline(1).complies_with_details(rules());
+    assert!(details.exceeds_baseline);
+}
+
+#[test]
+fn test_rule_set_baseline_overrides_the_arkitect_one() {
+    #[rustfmt::skip]
+    let rules = || ArchitecturalRules::define()
+        .rules_for_module("sample_project::policy_management")
+            .it_must_not_depend_on(&["sample_project::policy_management::model"])
+        .build();
+    let mut arkitect = Arkitect::ensure_that(Project::from_path("examples/sample_project"));
+
+    let details = arkitect.complies_with_details(rules().with_baseline(2));
+    assert_eq!(details.baseline, 2);
+    assert!(!details.exceeds_baseline);
+    assert!(arkitect.complies_with(rules().with_baseline(2)).is_ok());
+    assert!(arkitect.run(rules().with_baseline(2)).passed);
+    assert!(!arkitect.run(rules()).passed);
+}
+
+#[test]
+fn test_analysis_stats() {
+    #[rustfmt::skip]
+    let rules = || ArchitecturalRules::define()
+        .rules_for_project()
+            .it_must_not_have_circular_dependencies(3)
+        .rules_for_module("sample_project::policy_management")
+            .it_must_not_depend_on(&["sample_project::policy_management::model"])
+        .build();
+
+    let mut arkitect = Arkitect::ensure_that(Project::from_path("examples/sample_project"));
+    let stats = arkitect.analyze(rules()).stats;
+
+    assert!(stats.files_scanned > 0);
+    assert!(stats.applicable_matches > 0);
+    assert_eq!(stats.rules_evaluated, stats.applicable_matches + 1);
+    assert!(stats.elapsed >= stats.parsing + stats.evaluation);
+
+    let details = arkitect.complies_with_details(rules());
+    assert_eq!(details.stats.files_scanned, stats.files_scanned);
+}
+
+#[test]
+fn test_unchanged_files_are_read_from_the_dependency_cache() {
+    let root = temp_project(&[
+        ("Cargo.toml", "[package]\nname = \"cached\"\n"),
+        ("src/lib.rs", "pub mod domain;\npub mod infrastructure;\n"),
+        ("src/domain.rs", "use crate::infrastructure::Db;\n"),
+        ("src/infrastructure.rs", "pub struct Db;\n"),
+    ]);
+    let project_dir = root.path().to_str().unwrap();
+    #[rustfmt::skip]
+    let dependency_rules = || ArchitecturalRules::define()
+        .rules_for_project()
+            .it_must_not_have_circular_dependencies(3)
+        .rules_for_module("cached::domain")
+            .it_must_not_depend_on(&["cached::infrastructure"])
+        .build();
+    let analyze = |rules: Rules| {
+        let project = Project::from_path(project_dir).with_cache_dir("target/arkitect");
+        Arkitect::ensure_that(project).analyze(rules)
+    };
+
+    let first_run = analyze(dependency_rules());
+    assert_eq!(first_run.stats.files_parsed, 3);
+    assert_eq!(first_run.violations.len(), 1);
+
+    let second_run = analyze(dependency_rules());
+    assert_eq!(second_run.stats.files_parsed, 0);
+    assert_eq!(second_run.stats.files_scanned, 3);
+    assert_eq!(second_run.violations, first_run.violations);
+
+    #[rustfmt::skip]
+    let syntax_rules = ArchitecturalRules::define()
+        .rules_for_module("cached::domain")
+            .it_must_not_have_unused_imports()
+        .build();
+    assert_eq!(analyze(syntax_rules).stats.files_parsed, 1);
+
+    std::fs::File::options()
+        .write(true)
+        .open(root.path().join("Cargo.toml"))
+        .unwrap()
+        .set_modified(std::time::SystemTime::UNIX_EPOCH)
+        .unwrap();
+    assert_eq!(analyze(dependency_rules()).stats.files_parsed, 3);
+}
+
+#[test]
+fn test_subjects_must_match_files() {
+    #[rustfmt::skip]
+    let rules = || ArchitecturalRules::define()
+        .rules_for_module("sample_project::conversion")
+            .it_must_not_depend_on(&["sample_project::policy_management"])
+        .rules_for_module("sample_project::convrsion")
+            .it_must_not_depend_on(&["sample_project::contracts"])
+        .build();
+    let arkitect = || Arkitect::ensure_that(Project::from_path("examples/sample_project"));
+
+    assert_eq!(arkitect().complies_with(rules()), Ok(vec![]));
+
+    let violations = arkitect()
+        .subjects_must_match_files()
+        .with_color_mode(ColorMode::Never)
+        .complies_with(rules())
+        .unwrap_err();
+    assert_eq!(
+        violations,
+        vec!["Subjects matching no file: sample_project::convrsion".to_string()]
+    );
+
+    #[rustfmt::skip]
+    let matching_rules = ArchitecturalRules::define()
+        .rules_for_module("sample_project::conversion")
+            .it_must_not_depend_on(&["sample_project::policy_management"])
+        .build();
+    let results = arkitect()
+        .subjects_must_match_files()
+        .complies_with_all(vec![rules(), matching_rules]);
+    assert_eq!(
+        results,
+        vec![
+            Err(vec![
+                "Subjects matching no file: sample_project::convrsion".to_string()
+            ]),
+            Ok(vec![]),
+        ]
+    );
+}
+
+#[test]
+fn test_run_outcome() {
+    #[rustfmt::skip]
+    let rules = || ArchitecturalRules::define()
+        .rules_for_module("sample_project::policy_management")
+            .it_must_not_depend_on(&["sample_project::policy_management::model"])
+        .build();
+
+    let outcome = Arkitect::ensure_that(Project::from_path("examples/sample_project")).run(rules());
+
+    assert_eq!(outcome.violations.len(), 2);
+    assert_eq!(outcome.error, None);
+    assert!(!outcome.passed);
+    assert_eq!(outcome.exit_code(), 1);
+
+    let outcome = Arkitect::ensure_that(Project::from_path("examples/sample_project"))
+        .with_baseline(2)
+        .run(rules());
+    assert!(outcome.passed);
+    assert_eq!(outcome.exit_code(), 0);
+}
+
+#[test]
+fn test_progress_and_violation_callbacks() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[rustfmt::skip]
+    let rules = ArchitecturalRules::define()
+        .rules_for_module("sample_project::policy_management")
+            .it_must_not_depend_on(&["sample_project::policy_management::model"])
+        .build();
+
+    let progress = Rc::new(RefCell::new(Vec::new()));
+    let violations = Rc::new(RefCell::new(0));
+    let report = Arkitect::ensure_that(Project::from_path("examples/sample_project"))
+        .on_progress({
+            let progress = progress.clone();
+            move |scanned, total| progress.borrow_mut().push((scanned, total))
+        })
+        .on_violation({
+            let violations = violations.clone();
+            move |_| *violations.borrow_mut() += 1
+        })
+        .analyze(rules);
+
+    let trace = &report.traces[0];
+    let scanned = trace.applicable.len() + trace.not_applicable(&report.scanned_files).count();
+    let progress = progress.borrow();
+    assert_eq!(progress.len(), scanned);
+    assert_eq!(progress.last(), Some(&(scanned, scanned)));
+    assert_eq!(*violations.borrow(), report.violations.len());
+    assert_eq!(report.violations.len(), 2);
+}
+
+#[test]
+fn test_only_files_checks_the_given_subset() {
+    #[rustfmt::skip]
+    let rules = || ArchitecturalRules::define()
+        .rules_for_project()
+            .it_must_not_have_circular_dependencies(10)
+        .rules_for_module("sample_project::policy_management")
+            .it_must_not_depend_on(&["sample_project::policy_management::model"])
+        .build();
+
+    let report = Arkitect::ensure_that(Project::from_path("examples/sample_project"))
+        .only_files(&["src/policy_management/controller.rs", "README.md"])
+        .analyze(rules());
+
+    assert_eq!(report.violations.len(), 1);
+    assert!(report.violations[0].contains("controller.rs"));
+    assert!(report.checks.iter().all(|check| check.path.is_some()));
+    assert!(report.checks.iter().all(|check| check
+        .path
+        .as_deref()
+        .unwrap()
+        .ends_with("controller.rs")));
+}
+
+#[test]
+fn test_single_file_analysis() {
+    #[rustfmt::skip]
+    let rules = || ArchitecturalRules::define()
+        .rules_for_module("sample_project::conversion")
+            .it_may_depend_on(&["sample_project::conversion"])
+        .build();
+    let file = "examples/sample_project/src/conversion/application.rs";
+
+    let report = Arkitect::ensure_that_file(file).analyze(rules());
+
+    assert_eq!(report.checks.len(), 1);
+    assert_eq!(report.violations.len(), 1);
+    assert!(report.violations[0].contains("sample_project::contracts"));
+
+    #[rustfmt::skip]
+    let with_project_rules = rules().merge(ArchitecturalRules::define()
+        .rules_for_project()
+            .it_must_not_have_circular_dependencies(10)
+        .build());
+    assert_eq!(
+        Arkitect::ensure_that_file(file)
+            .try_analyze(with_project_rules)
+            .err(),
+        Some(ArkitectError::ProjectRulesOnSingleFile {
+            path: file.to_string(),
+            rules: vec!["must_not_have_circular_dependencies".to_string()],
+        })
+    );
+}
+
+#[test]
+fn test_explain_lists_the_files_of_each_rule() {
+    #[rustfmt::skip]
+    let rules = ArchitecturalRules::define()
+        .rules_for_module("sample_project::conversion")
+            .it_must_not_depend_on_anything()
+        .build();
+
+    let explanation =
+        Arkitect::ensure_that(Project::from_path("examples/sample_project")).explain(rules);
+
+    assert_eq!(explanation.rules.len(), 1);
+    assert!(!explanation.rules[0].applicable.is_empty());
+    assert!(explanation.rules[0]
+        .applicable
+        .iter()
+        .all(|path| path.contains("/conversion")));
+    assert!(explanation.rules[0]
+        .not_applicable(&explanation.scanned_files)
+        .any(|path| path.ends_with("lib.rs")));
+}
+
+#[test]
+fn test_project_rules_see_metadata_source_dirs() {
+    #[rustfmt::skip]
+    let rules = ArchitecturalRules::define()
+        .rules_for_project()
+            .it_must_respect_maturity_tiers(&[
+                ("metadata_project::generated", 2),
+                ("metadata_project::internal", 0),
+            ])
+        .build();
+
+    // `baseline = 1` comes from the fixture's `[package.metadata.arkitect]`
+    let violations = Arkitect::ensure_that(Project::from_path("examples/metadata_project"))
+        .complies_with(rules)
+        .unwrap();
+
+    assert_eq!(violations.len(), 1, "{:?}", violations);
+    assert!(violations[0].contains("metadata_project::generated::api"));
+}
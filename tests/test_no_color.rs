@@ -0,0 +1,24 @@
+#![cfg(test)]
+
+use rust_arkitect::builtin_rules::may_depend_on::MayDependOnRule;
+use rust_arkitect::rule::Rule;
+use rust_arkitect::rust_file::RustFile;
+use rust_arkitect::styling::Styling;
+
+#[test]
+fn test_no_color_removes_escape_sequences() {
+    std::env::set_var("NO_COLOR", "1");
+
+    let rule = MayDependOnRule::new("my_crate::domain", vec!["std::fmt".to_string()]);
+    let file = RustFile::from_content(
+        "src/domain/model.rs",
+        "my_crate::domain::model",
+        "use crate::infrastructure::Db;",
+    );
+
+    let rendered = rule.render(&Styling::default());
+
+    assert_eq!(rendered, "my_crate::domain may depend on [std::fmt]");
+    assert_eq!(rule.to_string(), rendered);
+    assert!(!rule.apply(&file).unwrap_err().contains('\u{1b}'));
+}